@@ -36,6 +36,8 @@ structure Posting where
   heading_level : Nat := 0
   /-- Pre-computed score (scaled by 10 for Nat) -/
   score : Nat := 0
+  /-- Part of a compound word rather than a word of its own -/
+  compound_part : Bool := false
   deriving DecidableEq, Repr, Inhabited
 
 /-- A posting is well-formed if it points to a valid location -/
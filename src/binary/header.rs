@@ -3,7 +3,7 @@
 
 //! Binary format header and footer structures.
 //!
-//! The header is 52 bytes of fixed-size fields, designed to be parsed in one
+//! The header is 56 bytes of fixed-size fields (52 before v14), designed to be parsed in one
 //! read before anything else. It tells you exactly where every section lives,
 //! so you can seek directly to what you need or dispatch parallel decodes.
 //!
@@ -29,8 +29,14 @@ pub const MAGIC: [u8; 4] = [0x53, 0x4F, 0x52, 0x58];
 /// Footer magic: "XROS" (reversed, marks valid file end)
 pub const FOOTER_MAGIC: [u8; 4] = [0x58, 0x52, 0x4F, 0x53];
 
-/// Current format version (v12: WASM first for streaming, v10+ encoding only)
-pub const VERSION: u8 = 12;
+/// Current format version (v12: WASM first for streaming, v10+ encoding only;
+/// v14: a second flags byte and an extensions section)
+pub const VERSION: u8 = 14;
+
+/// Oldest format version readers still accept. v12 files are v14 files whose
+/// second flags byte was reserved, and so zero, and which have no extensions
+/// section.
+pub const MIN_VERSION: u8 = 12;
 
 /// Block size for PFOR encoding (Lucene uses 128)
 pub const BLOCK_SIZE: usize = 128;
@@ -68,13 +74,23 @@ pub const MAX_SKIP_LEVELS: usize = 8;
 // ============================================================================
 
 /// Format flags
+///
+/// The low byte is the header's flags byte; the high byte (v14+) follows
+/// `dict_table_len`, in what was the first reserved byte in v12.
 #[derive(Debug, Clone, Copy, Default)]
-pub struct FormatFlags(pub(crate) u8);
+pub struct FormatFlags(pub(crate) u16);
 
 impl FormatFlags {
-    pub const HAS_SKIP_LISTS: u8 = 0b0000_0001;
-    pub const HAS_POSITIONS: u8 = 0b0000_0010;
-    pub const HAS_PAYLOADS: u8 = 0b0000_0100;
+    pub const HAS_SKIP_LISTS: u16 = 0b0000_0001;
+    pub const HAS_POSITIONS: u16 = 0b0000_0010;
+    pub const HAS_PAYLOADS: u16 = 0b0000_0100;
+    pub const HAS_TEXT: u16 = 0b0000_1000;
+    pub const HAS_VOCAB_TRIE: u16 = 0b0001_0000;
+    pub const HAS_PHONETIC: u16 = 0b0010_0000;
+    pub const HAS_STEMS: u16 = 0b0100_0000;
+    pub const CODE_TOKENS: u16 = 0b1000_0000;
+    pub const TRANSLITERATED: u16 = 0b0001_0000_0000;
+    pub const HAS_SYNONYMS: u16 = 0b0010_0000_0000;
 
     pub fn new() -> Self {
         Self(0)
@@ -88,13 +104,94 @@ impl FormatFlags {
     pub fn has_skip_lists(self) -> bool {
         self.0 & Self::HAS_SKIP_LISTS != 0
     }
+
+    /// Postings section ends with a word-position stream (see `encode_positions`)
+    pub fn with_positions(mut self) -> Self {
+        self.0 |= Self::HAS_POSITIONS;
+        self
+    }
+
+    pub fn has_positions(self) -> bool {
+        self.0 & Self::HAS_POSITIONS != 0
+    }
+
+    /// Docs section ends with a stored-text stream (see `encode_doc_texts`)
+    pub fn with_text(mut self) -> Self {
+        self.0 |= Self::HAS_TEXT;
+        self
+    }
+
+    pub fn has_text(self) -> bool {
+        self.0 & Self::HAS_TEXT != 0
+    }
+
+    /// Extensions section holds a vocabulary skip table (see `VocabTrie`)
+    pub fn with_vocab_trie(mut self) -> Self {
+        self.0 |= Self::HAS_VOCAB_TRIE;
+        self
+    }
+
+    pub fn has_vocab_trie(self) -> bool {
+        self.0 & Self::HAS_VOCAB_TRIE != 0
+    }
+
+    /// Extensions section holds per-term phonetic keys (see `PhoneticIndex`)
+    pub fn with_phonetic(mut self) -> Self {
+        self.0 |= Self::HAS_PHONETIC;
+        self
+    }
+
+    pub fn has_phonetic(self) -> bool {
+        self.0 & Self::HAS_PHONETIC != 0
+    }
+
+    /// Extensions section holds per-language stem tables (see `StemIndex`)
+    pub fn with_stems(mut self) -> Self {
+        self.0 |= Self::HAS_STEMS;
+        self
+    }
+
+    pub fn has_stems(self) -> bool {
+        self.0 & Self::HAS_STEMS != 0
+    }
+
+    /// Words were cut by the code tokenizer, so queries must be too
+    pub fn with_code_tokens(mut self) -> Self {
+        self.0 |= Self::CODE_TOKENS;
+        self
+    }
+
+    pub fn has_code_tokens(self) -> bool {
+        self.0 & Self::CODE_TOKENS != 0
+    }
+
+    /// Words in Cyrillic, Greek and Devanagari are indexed romanized too, so
+    /// queries in those scripts are romanized
+    pub fn with_transliteration(mut self) -> Self {
+        self.0 |= Self::TRANSLITERATED;
+        self
+    }
+
+    pub fn has_transliteration(self) -> bool {
+        self.0 & Self::TRANSLITERATED != 0
+    }
+
+    /// Extensions section holds the synonym table (see `Synonyms`)
+    pub fn with_synonyms(mut self) -> Self {
+        self.0 |= Self::HAS_SYNONYMS;
+        self
+    }
+
+    pub fn has_synonyms(self) -> bool {
+        self.0 & Self::HAS_SYNONYMS != 0
+    }
 }
 
 // ============================================================================
 // HEADER
 // ============================================================================
 
-/// Binary format header (52 bytes fixed size, v7)
+/// Binary format header (56 bytes fixed size, v14)
 #[derive(Debug, Clone)]
 pub struct SorexHeader {
     pub version: u8,
@@ -118,11 +215,27 @@ pub struct SorexHeader {
     /// Dictionary tables length (new in v7)
     /// Parquet-style compression for category, author, tags, href_prefix
     pub dict_table_len: u32,
+    /// Extensions section length (new in v14)
+    /// The vocabulary trie, phonetic keys, stems and synonyms, each if flagged
+    pub ext_len: u32,
 }
 
 impl SorexHeader {
-    // 4 (magic) + 1 (version) + 1 (flags) + 11*4 (u32s) + 2 (reserved) = 52
-    pub const SIZE: usize = 52;
+    // 4 (magic) + 1 (version) + 1 (flags) + 11*4 (u32s) + 1 (more flags)
+    // + 1 (reserved) + 4 (ext_len) = 56
+    pub const SIZE: usize = 56;
+
+    /// Header size before v14, which had no `ext_len`
+    pub const MIN_SIZE: usize = 52;
+
+    /// This header's size on disk, which depends on its version.
+    pub fn size(&self) -> usize {
+        if self.version >= 14 {
+            Self::SIZE
+        } else {
+            Self::MIN_SIZE
+        }
+    }
 
     /// Compute section byte offsets for this header.
     /// This is the SINGLE SOURCE OF TRUTH for the v12 file layout.
@@ -133,7 +246,8 @@ impl SorexHeader {
     pub fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
         w.write_all(&MAGIC)?;
         w.write_all(&[self.version])?;
-        w.write_all(&[self.flags.0])?;
+        let [flags, more_flags] = self.flags.0.to_le_bytes();
+        w.write_all(&[flags])?;
         w.write_all(&self.doc_count.to_le_bytes())?;
         w.write_all(&self.term_count.to_le_bytes())?;
         w.write_all(&self.vocab_len.to_le_bytes())?;
@@ -145,7 +259,9 @@ impl SorexHeader {
         w.write_all(&self.docs_len.to_le_bytes())?;
         w.write_all(&self.wasm_len.to_le_bytes())?; // v7: embedded WASM
         w.write_all(&self.dict_table_len.to_le_bytes())?; // v7: dictionary tables
-        w.write_all(&[0u8; 2])?; // reserved (2 bytes for alignment)
+        w.write_all(&[more_flags])?; // flags past the first eight
+        w.write_all(&[0u8])?; // reserved (for alignment)
+        w.write_all(&self.ext_len.to_le_bytes())?; // v14: extensions
         Ok(())
     }
 
@@ -161,10 +277,16 @@ impl SorexHeader {
 
         let mut buf = [0u8; 48]; // 52 - 4 (magic) = 48
         r.read_exact(&mut buf)?;
+        // v12 had no flags past the first eight, nor an extensions section
+        let more_flags = if buf[0] >= 14 { buf[46] } else { 0 };
+        let mut ext_len = [0u8; 4];
+        if buf[0] >= 14 {
+            r.read_exact(&mut ext_len)?;
+        }
 
         Ok(Self {
             version: buf[0],
-            flags: FormatFlags(buf[1]),
+            flags: FormatFlags(u16::from_le_bytes([buf[1], more_flags])),
             doc_count: u32::from_le_bytes([buf[2], buf[3], buf[4], buf[5]]),
             term_count: u32::from_le_bytes([buf[6], buf[7], buf[8], buf[9]]),
             vocab_len: u32::from_le_bytes([buf[10], buf[11], buf[12], buf[13]]),
@@ -176,7 +298,8 @@ impl SorexHeader {
             docs_len: u32::from_le_bytes([buf[34], buf[35], buf[36], buf[37]]),
             wasm_len: u32::from_le_bytes([buf[38], buf[39], buf[40], buf[41]]), // v7: embedded WASM
             dict_table_len: u32::from_le_bytes([buf[42], buf[43], buf[44], buf[45]]), // v7: dictionary tables
-            // buf[46..48] is reserved
+            // buf[47] is reserved
+            ext_len: u32::from_le_bytes(ext_len), // v14: extensions
         })
     }
 }
@@ -204,7 +327,7 @@ pub struct SorexFooter {
 /// v12 layout is optimized for streaming decode based on dependency analysis:
 /// - WASM first for `WebAssembly.compileStreaming()` async
 /// - Dependencies ordered: VOCAB before SA, DICT_TABLES before DOCS
-/// - LEV_DFA last (only needed for T3 fuzzy search)
+/// - LEV_DFA last (only needed for T3 fuzzy search), then EXTENSIONS (v14+)
 #[derive(Debug, Clone, Copy)]
 pub struct SectionOffsets {
     // Start and end offsets for each section
@@ -217,6 +340,7 @@ pub struct SectionOffsets {
     pub section_table: (usize, usize),
     pub skip_lists: (usize, usize),
     pub lev_dfa: (usize, usize),
+    pub extensions: (usize, usize),
     pub footer: (usize, usize),
 }
 
@@ -224,7 +348,7 @@ impl SectionOffsets {
     /// Compute section offsets from header lengths.
     ///
     /// v12 layout order (dependency-optimized):
-    /// 1. HEADER        [52B]     - Parse first to get section lengths
+    /// 1. HEADER        [56B]     - Parse first to get section lengths (52B before v14)
     /// 2. WASM          [wasm_len]    - Start async compile immediately
     /// 3. VOCABULARY    [vocab_len]   - Decode, needed by SUFFIX_ARRAY
     /// 4. DICT_TABLES   [dict_table_len] - Decode, needed by DOCS
@@ -234,9 +358,10 @@ impl SectionOffsets {
     /// 8. SECTION_TABLE [section_table_len] - For deep links
     /// 9. SKIP_LISTS    [skip_len]    - For fast postings access
     /// 10. LEV_DFA      [lev_dfa_len] - Only for T3 fuzzy search
-    /// 11. FOOTER       [8B]          - CRC32 validation
+    /// 11. EXTENSIONS   [ext_len]     - Tables the flags announce (v14+)
+    /// 12. FOOTER       [8B]          - CRC32 validation
     pub fn from_header(h: &SorexHeader) -> Self {
-        let mut pos = SorexHeader::SIZE;
+        let mut pos = h.size();
 
         // 1. WASM (async compile)
         let wasm_start = pos;
@@ -283,7 +408,12 @@ impl SectionOffsets {
         pos += h.lev_dfa_len as usize;
         let lev_end = pos;
 
-        // 10. FOOTER
+        // 10. EXTENSIONS (vocabulary trie, phonetic keys, stems, synonyms)
+        let ext_start = pos;
+        pos += h.ext_len as usize;
+        let ext_end = pos;
+
+        // 11. FOOTER
         let footer_start = pos;
         let footer_end = pos + SorexFooter::SIZE;
 
@@ -297,6 +427,7 @@ impl SectionOffsets {
             section_table: (section_start, section_end),
             skip_lists: (skip_start, skip_end),
             lev_dfa: (lev_start, lev_end),
+            extensions: (ext_start, ext_end),
             footer: (footer_start, footer_end),
         }
     }
@@ -37,14 +37,15 @@ use std::sync::Arc;
 
 use parking_lot::RwLock;
 
-use super::header::{FormatFlags, SectionOffsets, SorexHeader, VERSION};
-use super::postings::{decode_postings, PostingEntry, SkipList};
-use super::{decode_docs_binary, DocMeta, LoadedLayer};
+use super::header::{FormatFlags, SectionOffsets, SorexHeader, MIN_VERSION, VERSION};
+use super::postings::{decode_postings_section, PostingEntry, SkipList};
+use super::tokenizer_of;
+use super::{decode_docs_section, decode_extensions, DocMeta, LoadedLayer, StoredText};
 use super::{decode_section_table, decode_suffix_array, decode_varint, decode_vocabulary};
 use crate::util::dict_table::DictTables;
 
 /// Number of sections that need to be loaded (excluding WASM which is handled separately)
-const SECTION_COUNT: u8 = 8;
+const SECTION_COUNT: u8 = 9;
 
 /// Incremental loader that accepts sections as they arrive.
 ///
@@ -59,11 +60,14 @@ pub struct IncrementalLoader {
     vocabulary: Arc<RwLock<Option<Vec<String>>>>,
     dict_tables: Arc<RwLock<Option<DictTables>>>,
     postings: Arc<RwLock<Option<Vec<Vec<PostingEntry>>>>>,
+    positions: Arc<RwLock<Option<Vec<Vec<u32>>>>>,
     suffix_array: Arc<RwLock<Option<Vec<(u32, u32)>>>>,
     docs: Arc<RwLock<Option<Vec<DocMeta>>>>,
+    text: Arc<RwLock<Option<StoredText>>>,
     section_table: Arc<RwLock<Option<Vec<String>>>>,
     skip_lists: Arc<RwLock<Option<HashMap<usize, SkipList>>>>,
     lev_dfa_bytes: Arc<RwLock<Option<Vec<u8>>>>,
+    ext_bytes: Arc<RwLock<Option<Vec<u8>>>>,
 
     // Completion tracking (counts down from SECTION_COUNT)
     sections_pending: Arc<AtomicU8>,
@@ -83,11 +87,14 @@ impl IncrementalLoader {
             vocabulary: Arc::new(RwLock::new(None)),
             dict_tables: Arc::new(RwLock::new(None)),
             postings: Arc::new(RwLock::new(None)),
+            positions: Arc::new(RwLock::new(None)),
             suffix_array: Arc::new(RwLock::new(None)),
             docs: Arc::new(RwLock::new(None)),
+            text: Arc::new(RwLock::new(None)),
             section_table: Arc::new(RwLock::new(None)),
             skip_lists: Arc::new(RwLock::new(None)),
             lev_dfa_bytes: Arc::new(RwLock::new(None)),
+            ext_bytes: Arc::new(RwLock::new(None)),
             sections_pending: Arc::new(AtomicU8::new(SECTION_COUNT)),
         }
     }
@@ -96,25 +103,25 @@ impl IncrementalLoader {
     ///
     /// This must be called first before loading any sections.
     pub fn load_header(&mut self, bytes: &[u8]) -> io::Result<SectionOffsets> {
-        if bytes.len() < SorexHeader::SIZE {
+        if bytes.len() < SorexHeader::MIN_SIZE {
             return Err(io::Error::new(
                 io::ErrorKind::UnexpectedEof,
                 format!(
                     "Header too short: {} bytes (need {})",
                     bytes.len(),
-                    SorexHeader::SIZE
+                    SorexHeader::MIN_SIZE
                 ),
             ));
         }
 
         let header = SorexHeader::read(&mut io::Cursor::new(bytes))?;
 
-        if header.version != VERSION {
+        if !(MIN_VERSION..=VERSION).contains(&header.version) {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!(
-                    "Unsupported version: {} (expected {})",
-                    header.version, VERSION
+                    "Unsupported version: {} (expected {}-{})",
+                    header.version, MIN_VERSION, VERSION
                 ),
             ));
         }
@@ -182,28 +189,29 @@ impl IncrementalLoader {
     /// Decode postings in background thread. Non-blocking.
     ///
     /// This is typically the largest section (~30-50% of file size).
+    /// Word positions (if the header has `HAS_POSITIONS`) are decoded here too,
+    /// since they share the postings section.
     #[cfg(feature = "rayon")]
     pub fn load_postings(&self, bytes: Vec<u8>, term_count: u32) {
         let postings_lock = self.postings.clone();
+        let positions_lock = self.positions.clone();
         let pending = self.sections_pending.clone();
+        let has_positions = self
+            .header
+            .as_ref()
+            .is_some_and(|h| h.flags.has_positions());
 
         rayon::spawn(move || {
-            let mut postings = Vec::with_capacity(term_count as usize);
-            let mut pos = 0;
-
-            while pos < bytes.len() {
-                match decode_postings(&bytes[pos..]) {
-                    Ok((posting_list, consumed)) => {
-                        postings.push(posting_list);
-                        pos += consumed;
-                    }
+            let (postings, positions) =
+                match decode_postings_section(&bytes, term_count as usize, has_positions) {
+                    Ok(decoded) => decoded,
                     Err(e) => {
-                        eprintln!("Error decoding postings at offset {}: {}", pos, e);
-                        break;
+                        eprintln!("Error decoding postings: {}", e);
+                        (Vec::new(), Vec::new())
                     }
-                }
-            }
+                };
 
+            *positions_lock.write() = Some(positions);
             *postings_lock.write() = Some(postings);
             pending.fetch_sub(1, Ordering::SeqCst);
         });
@@ -235,14 +243,19 @@ impl IncrementalLoader {
     ///
     /// Note: Docs decode is independent of dict_tables at decode time
     /// (dict_tables are used at query time for field resolution).
+    /// Stored text (if the header has `HAS_TEXT`) shares the docs section
+    /// and is decoded here too.
     #[cfg(feature = "rayon")]
     pub fn load_docs(&self, bytes: Vec<u8>) {
         let docs_lock = self.docs.clone();
+        let text_lock = self.text.clone();
         let pending = self.sections_pending.clone();
+        let has_text = self.header.as_ref().is_some_and(|h| h.flags.has_text());
 
         rayon::spawn(move || {
-            match decode_docs_binary(&bytes) {
-                Ok(docs) => {
+            match decode_docs_section(&bytes, has_text) {
+                Ok((docs, text)) => {
+                    *text_lock.write() = text;
                     *docs_lock.write() = Some(docs);
                 }
                 Err(e) => {
@@ -334,6 +347,21 @@ impl IncrementalLoader {
         });
     }
 
+    /// Store extensions section bytes. Non-blocking.
+    ///
+    /// Note: the tables are decoded in `finalize`, which has the DFA bytes
+    /// older files keep them after.
+    #[cfg(feature = "rayon")]
+    pub fn load_extensions(&self, bytes: Vec<u8>) {
+        let ext_lock = self.ext_bytes.clone();
+        let pending = self.sections_pending.clone();
+
+        rayon::spawn(move || {
+            *ext_lock.write() = Some(bytes);
+            pending.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+
     /// Check if all sections are loaded (non-blocking).
     pub fn is_complete(&self) -> bool {
         self.sections_pending.load(Ordering::SeqCst) == 0
@@ -374,6 +402,8 @@ impl IncrementalLoader {
             .take()
             .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Postings not loaded"))?;
 
+        let positions = self.positions.write().take().unwrap_or_default();
+
         let section_table = self.section_table.write().take().ok_or_else(|| {
             io::Error::new(io::ErrorKind::InvalidData, "Section table not loaded")
         })?;
@@ -386,6 +416,11 @@ impl IncrementalLoader {
         let lev_dfa_bytes = self.lev_dfa_bytes.write().take().ok_or_else(|| {
             io::Error::new(io::ErrorKind::InvalidData, "Levenshtein DFA not loaded")
         })?;
+        let ext_bytes =
+            self.ext_bytes.write().take().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "Extensions not loaded")
+            })?;
+        let extensions = decode_extensions(&header, &ext_bytes)?;
 
         let docs = self
             .docs
@@ -415,12 +450,20 @@ impl IncrementalLoader {
             vocabulary,
             suffix_array,
             postings,
+            positions,
             section_table,
             skip_lists,
             lev_dfa_bytes,
+            vocab_trie: extensions.vocab_trie,
+            phonetic: extensions.phonetic,
+            stems: extensions.stems,
+            synonyms: extensions.synonyms,
+            tokenizer: tokenizer_of(header.flags),
+            transliterated: header.flags.has_transliteration(),
             docs,
             dict_tables,
             wasm_bytes: Vec::new(), // WASM is handled separately by JS
+            text: self.text.write().take(),
         })
     }
 }
@@ -505,6 +548,7 @@ mod tests {
             flags,
         );
         loader.load_lev_dfa(bytes[offsets.lev_dfa.0..offsets.lev_dfa.1].to_vec());
+        loader.load_extensions(bytes[offsets.extensions.0..offsets.extensions.1].to_vec());
 
         // Finalize
         let layer = loader.finalize().unwrap();
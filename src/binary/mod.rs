@@ -34,16 +34,18 @@
 //!
 //! ```text
 //! ┌────────────────────────────────────────────────────────────┐
-//! │ HEADER (52 bytes)                                          │
+//! │ HEADER (56 bytes)                                          │
 //! │   magic: [u8; 4] = "SORX"                                  │
-//! │   version: u8 = 12                                         │
-//! │   flags: u8                                                │
+//! │   version: u8 = 14                                         │
+//! │   flags: u8 (bits 0-7)                                     │
 //! │   doc_count: u32                                           │
 //! │   term_count: u32                                          │
 //! │   vocab_len: u32, sa_len: u32, postings_len: u32           │
 //! │   skip_len: u32, section_table_len: u32, lev_dfa_len: u32  │
 //! │   docs_len: u32, wasm_len: u32, dict_table_len: u32        │
-//! │   reserved: [u8; 2]                                        │
+//! │   more_flags: u8 (bits 8-15, v14+; reserved in v12)        │
+//! │   reserved: u8                                             │
+//! │   ext_len: u32 (v14+)                                      │
 //! ├────────────────────────────────────────────────────────────┤
 //! │ 1. WASM (async compile, ~200KB)                            │
 //! ├────────────────────────────────────────────────────────────┤
@@ -63,6 +65,12 @@
 //! ├────────────────────────────────────────────────────────────┤
 //! │ 9. LEV_DFA (precomputed automaton, only for T3 fuzzy)      │
 //! ├────────────────────────────────────────────────────────────┤
+//! │ 10. EXTENSIONS (v14+)                                      │
+//! │    vocabulary skip table if HAS_VOCAB_TRIE                 │
+//! │    + phonetic keys per term if HAS_PHONETIC                │
+//! │    + stem tables per language if HAS_STEMS                 │
+//! │    + synonym rules if HAS_SYNONYMS                         │
+//! ├────────────────────────────────────────────────────────────┤
 //! │ FOOTER (8 bytes): crc32 + magic "XROS"                     │
 //! └────────────────────────────────────────────────────────────┘
 //! ```
@@ -73,6 +81,7 @@ mod header;
 #[cfg(feature = "rayon")]
 mod incremental;
 mod postings;
+mod text;
 
 // Re-export from submodules for public API
 pub use encoding::{
@@ -82,15 +91,24 @@ pub use encoding::{
 pub use header::{
     FormatFlags, SectionOffsets, SorexFooter, SorexHeader, BLOCK_SIZE, FOOTER_MAGIC, MAGIC,
     MAX_DOC_COUNT, MAX_FILE_SIZE, MAX_POSTING_SIZE, MAX_SKIP_LEVELS, MAX_TERM_COUNT,
-    MAX_VARINT_BYTES, SKIP_INTERVAL, SKIP_LIST_THRESHOLD, VERSION,
+    MAX_VARINT_BYTES, MIN_VERSION, SKIP_INTERVAL, SKIP_LIST_THRESHOLD, VERSION,
 };
 #[cfg(feature = "rayon")]
 pub use incremental::IncrementalLoader;
-pub use postings::{decode_postings, encode_postings, PostingEntry, SkipEntry, SkipList};
+pub use postings::{
+    decode_positions, decode_postings, decode_postings_section, encode_positions, encode_postings,
+    PostingEntry, SkipEntry, SkipList,
+};
+pub use text::{encode_doc_texts, DocText, StoredText, TextSpan};
 
 use std::collections::HashMap;
 use std::io;
 
+use crate::fuzzy::phonetic::PhoneticIndex;
+use crate::fuzzy::trie::VocabTrie;
+use crate::index::stem::StemIndex;
+use crate::index::synonyms::Synonyms;
+use crate::index::tokenizer::Tokenizer;
 use crate::util::dict_table::DictTables;
 
 // ============================================================================
@@ -115,6 +133,8 @@ pub struct BinaryLayer {
     pub wasm_bytes: Vec<u8>,
     /// Dictionary tables (v7: Parquet-style compression for category, author, tags, href_prefix)
     pub dict_table_bytes: Vec<u8>,
+    /// Extension tables (v14: vocabulary trie, phonetic keys, stems, synonyms, each only if flagged)
+    pub ext_bytes: Vec<u8>,
 }
 
 impl BinaryLayer {
@@ -125,11 +145,16 @@ impl BinaryLayer {
     /// - Delta+varint postings (~45% better compression)
     /// - Separated streams for suffix array
     /// - WASM first for streaming compilation
+    ///
+    /// `positions`, when given, holds one word position per posting entry
+    /// (parallel to `postings`). They're appended to the postings section and
+    /// flagged with `HAS_POSITIONS` so the searcher can match phrases.
     #[allow(clippy::too_many_arguments)]
     pub fn build_v7(
         vocabulary: &[String],
         suffix_array: &[(u32, u32)],
         postings: &[Vec<PostingEntry>], // One posting list per term (in vocab order)
+        positions: Option<&[Vec<u32>]>, // One word position per posting entry
         section_table: &[String],       // Unique section_id strings
         doc_count: usize,
         lev_dfa_bytes: Vec<u8>,
@@ -150,6 +175,24 @@ impl BinaryLayer {
             encode_postings(posting_list, &mut postings_bytes);
         }
 
+        // Positions stream follows the posting lists (same term and entry order)
+        if let Some(positions) = positions {
+            if positions.len() != postings.len()
+                || positions
+                    .iter()
+                    .zip(postings)
+                    .any(|(p, e)| p.len() != e.len())
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Positions must have one entry per posting",
+                ));
+            }
+            for (posting_list, term_positions) in postings.iter().zip(positions) {
+                encode_positions(posting_list, term_positions, &mut postings_bytes);
+            }
+        }
+
         // Build skip lists for large posting lists
         let mut skip_bytes = Vec::new();
         let mut has_skip_lists = false;
@@ -167,11 +210,13 @@ impl BinaryLayer {
         let mut section_table_bytes = Vec::new();
         encode_section_table(section_table, &mut section_table_bytes);
 
-        let flags = if has_skip_lists {
-            FormatFlags::new().with_skip_lists()
-        } else {
-            FormatFlags::new()
-        };
+        let mut flags = FormatFlags::new();
+        if has_skip_lists {
+            flags = flags.with_skip_lists();
+        }
+        if positions.is_some() {
+            flags = flags.with_positions();
+        }
 
         let header = SorexHeader {
             version: VERSION,
@@ -187,6 +232,7 @@ impl BinaryLayer {
             docs_len: docs_bytes.len() as u32,
             wasm_len: wasm_bytes.len() as u32,
             dict_table_len: 0, // Caller sets this after build (see build/parallel.rs)
+            ext_len: 0,        // Grows with each set_* extension below
         };
 
         Ok(Self {
@@ -200,6 +246,7 @@ impl BinaryLayer {
             docs_bytes,
             wasm_bytes,
             dict_table_bytes: Vec::new(), // Empty for now, populated via build_v7_with_dicts
+            ext_bytes: Vec::new(),
         })
     }
 
@@ -220,6 +267,7 @@ impl BinaryLayer {
             vocabulary,
             suffix_array,
             postings,
+            None,
             section_table,
             doc_count,
             lev_dfa_bytes,
@@ -263,6 +311,81 @@ impl BinaryLayer {
         )
     }
 
+    /// Append stored document text to the docs section (one entry per doc,
+    /// in doc_id order) and flag it with `HAS_TEXT`.
+    ///
+    /// Like positions, the text rides inside an existing section rather
+    /// than getting one of its own.
+    pub fn set_text(&mut self, texts: &[DocText]) {
+        encode_doc_texts(texts, &mut self.docs_bytes);
+        self.header.docs_len = self.docs_bytes.len() as u32;
+        self.header.flags = self.header.flags.with_text();
+    }
+
+    /// Append the vocabulary skip table to the extensions section and flag
+    /// it with `HAS_VOCAB_TRIE`, so fuzzy search can skip whole runs of
+    /// terms instead of scanning the vocabulary.
+    pub fn set_vocab_trie(&mut self, trie: &VocabTrie) {
+        debug_assert!(
+            self.ext_bytes.is_empty(),
+            "the vocabulary trie is the first extension"
+        );
+        trie.encode(&mut self.ext_bytes);
+        self.header.ext_len = self.ext_bytes.len() as u32;
+        self.header.flags = self.header.flags.with_vocab_trie();
+    }
+
+    /// Append per-term phonetic keys to the extensions section and flag them
+    /// with `HAS_PHONETIC`, so searches can opt into matching terms that
+    /// sound like the query. Call after [`BinaryLayer::set_vocab_trie`].
+    pub fn set_phonetic(&mut self, index: &PhoneticIndex) {
+        debug_assert!(
+            !self.header.flags.has_stems() && !self.header.flags.has_synonyms(),
+            "the phonetic keys go before the stems and synonyms"
+        );
+        index.encode(&mut self.ext_bytes);
+        self.header.ext_len = self.ext_bytes.len() as u32;
+        self.header.flags = self.header.flags.with_phonetic();
+    }
+
+    /// Append the stem tables to the extensions section and flag them with
+    /// `HAS_STEMS`, so exact search also matches the query's other
+    /// inflections. Call after [`BinaryLayer::set_phonetic`].
+    pub fn set_stems(&mut self, index: &StemIndex) {
+        debug_assert!(
+            !self.header.flags.has_synonyms(),
+            "the stems go before the synonyms"
+        );
+        index.encode(&mut self.ext_bytes);
+        self.header.ext_len = self.ext_bytes.len() as u32;
+        self.header.flags = self.header.flags.with_stems();
+    }
+
+    /// Append the synonym table to the extensions section and flag it with
+    /// `HAS_SYNONYMS`, so searches also try each rule's other side. Call
+    /// after [`BinaryLayer::set_stems`].
+    pub fn set_synonyms(&mut self, synonyms: &Synonyms) {
+        synonyms.encode(&mut self.ext_bytes);
+        self.header.ext_len = self.ext_bytes.len() as u32;
+        self.header.flags = self.header.flags.with_synonyms();
+    }
+
+    /// Record the tokenizer the words were cut with, so queries are cut the
+    /// same way. Text is the default and leaves the flags alone; code sets
+    /// `CODE_TOKENS`.
+    pub fn set_tokenizer(&mut self, tokenizer: Tokenizer) {
+        if tokenizer == Tokenizer::Code {
+            self.header.flags = self.header.flags.with_code_tokens();
+        }
+    }
+
+    /// Record that words in Cyrillic, Greek and Devanagari were indexed
+    /// romanized too (`TRANSLITERATED`), so queries in those scripts are
+    /// romanized to match.
+    pub fn set_transliterated(&mut self) {
+        self.header.flags = self.header.flags.with_transliteration();
+    }
+
     /// Serialize to bytes (with CRC32 footer)
     ///
     /// v12 layout optimized for streaming decode (dependency-ordered):
@@ -276,7 +399,8 @@ impl BinaryLayer {
     /// 8. SECTION_TABLE- Decode (moderate), for deep links
     /// 9. SKIP_LISTS  - Decode, for fast postings access
     /// 10. LEV_DFA    - Memcpy, only for fuzzy search (T3)
-    /// 11. FOOTER     - CRC32 validation
+    /// 11. EXTENSIONS - Tables the flags announce (v14)
+    /// 12. FOOTER     - CRC32 validation
     pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
         let content_size = SorexHeader::SIZE
             + self.wasm_bytes.len()
@@ -287,7 +411,8 @@ impl BinaryLayer {
             + self.docs_bytes.len()
             + self.section_table_bytes.len()
             + self.skip_bytes.len()
-            + self.lev_dfa_bytes.len();
+            + self.lev_dfa_bytes.len()
+            + self.ext_bytes.len();
         let total_size = content_size + SorexFooter::SIZE;
 
         let mut buf = Vec::with_capacity(total_size);
@@ -302,6 +427,7 @@ impl BinaryLayer {
         buf.extend_from_slice(&self.section_table_bytes); // 7. SECTION_TABLE
         buf.extend_from_slice(&self.skip_bytes); // 8. SKIP_LISTS
         buf.extend_from_slice(&self.lev_dfa_bytes); // 9. LEV_DFA (only for T3)
+        buf.extend_from_slice(&self.ext_bytes); // 10. EXTENSIONS
 
         // Compute CRC32 over everything written so far
         let crc32 = SorexFooter::compute_crc32(&buf);
@@ -336,7 +462,7 @@ impl BinaryLayer {
         }
 
         // Minimum size: header + footer
-        let min_size = SorexHeader::SIZE + SorexFooter::SIZE;
+        let min_size = SorexHeader::MIN_SIZE + SorexFooter::SIZE;
         if bytes.len() < min_size {
             return Err(io::Error::new(
                 io::ErrorKind::UnexpectedEof,
@@ -367,13 +493,13 @@ impl BinaryLayer {
         let mut cursor = io::Cursor::new(bytes);
         let header = SorexHeader::read(&mut cursor)?;
 
-        // Validate version (v12 only)
-        if header.version != VERSION {
+        // Validate version (v12 to v14)
+        if !(MIN_VERSION..=VERSION).contains(&header.version) {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!(
-                    "Unsupported version: {} (expected {})",
-                    header.version, VERSION
+                    "Unsupported version: {} (expected {}-{})",
+                    header.version, MIN_VERSION, VERSION
                 ),
             ));
         }
@@ -437,6 +563,7 @@ impl BinaryLayer {
         let section_table_bytes = extract_section(offsets.section_table, "Section table")?;
         let skip_bytes = extract_section(offsets.skip_lists, "Skip lists")?;
         let lev_dfa_bytes = extract_section(offsets.lev_dfa, "Levenshtein DFA")?;
+        let ext_bytes = extract_section(offsets.extensions, "Extensions")?;
         // FOOTER is validated at the start of from_bytes()
 
         Ok(Self {
@@ -450,6 +577,7 @@ impl BinaryLayer {
             docs_bytes,
             wasm_bytes,
             dict_table_bytes,
+            ext_bytes,
         })
     }
 }
@@ -564,13 +692,133 @@ pub fn encode_docs_binary(docs: &[DocMetaInput]) -> Vec<u8> {
     buf
 }
 
+/// Decode a docs section, plus the stored text that follows the docs when
+/// the header has `HAS_TEXT`.
+pub(crate) fn decode_docs_section(
+    bytes: &[u8],
+    has_text: bool,
+) -> io::Result<(Vec<DocMeta>, Option<StoredText>)> {
+    let (docs, consumed) = decode_docs_entries(bytes)?;
+    if !has_text {
+        return Ok((docs, None));
+    }
+
+    let (text, text_len) = StoredText::decode(&bytes[consumed..])?;
+    if consumed + text_len != bytes.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Trailing bytes after stored text: {} of {} consumed",
+                consumed + text_len,
+                bytes.len()
+            ),
+        ));
+    }
+    if text.len() != docs.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Stored text covers {} docs, docs section has {}",
+                text.len(),
+                docs.len()
+            ),
+        ));
+    }
+    Ok((docs, Some(text)))
+}
+
+/// The tokenizer an index's words were cut with.
+pub(crate) fn tokenizer_of(flags: FormatFlags) -> Tokenizer {
+    if flags.has_code_tokens() {
+        Tokenizer::Code
+    } else {
+        Tokenizer::Text
+    }
+}
+
+/// The extension tables, each `None` unless the header flags it.
+#[derive(Default)]
+pub(crate) struct Extensions {
+    pub vocab_trie: Option<VocabTrie>,
+    pub phonetic: Option<PhoneticIndex>,
+    pub stems: Option<StemIndex>,
+    pub synonyms: Option<Synonyms>,
+}
+
+/// Decode the extension tables the header flags from the EXTENSIONS section.
+pub(crate) fn decode_extensions(header: &SorexHeader, ext_bytes: &[u8]) -> io::Result<Extensions> {
+    decode_extension_tables(ext_bytes, header.flags, header.term_count as usize)
+}
+
+/// Decode the extension tables `flags` announces, in order, from `bytes`:
+/// the vocabulary skip table (`HAS_VOCAB_TRIE`), the phonetic keys
+/// (`HAS_PHONETIC`), the stem tables (`HAS_STEMS`), then the synonym table
+/// (`HAS_SYNONYMS`).
+fn decode_extension_tables(
+    bytes: &[u8],
+    flags: FormatFlags,
+    term_count: usize,
+) -> io::Result<Extensions> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+    let check_len = |what: &str, len: usize| {
+        if len == term_count {
+            Ok(())
+        } else {
+            Err(invalid(format!(
+                "{} cover {} terms, vocabulary has {}",
+                what, len, term_count
+            )))
+        }
+    };
+    let mut tables = Extensions::default();
+    let mut pos = 0;
+
+    if flags.has_vocab_trie() {
+        let (trie, trie_len) = VocabTrie::decode(&bytes[pos..])?;
+        check_len("Vocabulary trie entries", trie.len())?;
+        pos += trie_len;
+        tables.vocab_trie = Some(trie);
+    }
+
+    if flags.has_phonetic() {
+        let (index, index_len) = PhoneticIndex::decode(&bytes[pos..])?;
+        check_len("Phonetic keys", index.len())?;
+        pos += index_len;
+        tables.phonetic = Some(index);
+    }
+
+    if flags.has_stems() {
+        let (index, index_len) = StemIndex::decode(&bytes[pos..])?;
+        check_len("Stem tables", index.len())?;
+        pos += index_len;
+        tables.stems = Some(index);
+    }
+
+    if flags.has_synonyms() {
+        let (table, table_len) = Synonyms::decode(&bytes[pos..])?;
+        pos += table_len;
+        tables.synonyms = Some(table);
+    }
+
+    if pos != bytes.len() {
+        return Err(invalid(format!(
+            "Trailing bytes after extension tables: {} of {} consumed",
+            pos,
+            bytes.len()
+        )));
+    }
+    Ok(tables)
+}
+
 /// Decode docs from binary format (supports v1 and v2 formats)
 ///
 /// - v1 (legacy): varint(count) + docs without author/tags
 /// - v2: magic(0xFE) + version(2) + varint(count) + docs with author/tags
-pub(crate) fn decode_docs_binary(bytes: &[u8]) -> io::Result<Vec<DocMeta>> {
+///
+/// Returns the docs and the number of bytes consumed.
+fn decode_docs_entries(bytes: &[u8]) -> io::Result<(Vec<DocMeta>, usize)> {
     if bytes.is_empty() {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), 0));
     }
 
     let mut offset = 0;
@@ -714,7 +962,7 @@ pub(crate) fn decode_docs_binary(bytes: &[u8]) -> io::Result<Vec<DocMeta>> {
         });
     }
 
-    Ok(docs)
+    Ok((docs, offset))
 }
 
 // ============================================================================
@@ -748,17 +996,35 @@ pub struct LoadedLayer {
     pub suffix_array: Vec<(u32, u32)>,
     /// Posting lists with section_id indices (v6)
     pub postings: Vec<Vec<PostingEntry>>,
+    /// Word position of each posting entry, parallel to `postings`.
+    /// Empty when the index was built without positions.
+    pub positions: Vec<Vec<u32>>,
     /// Section ID string table (v6)
     pub section_table: Vec<String>,
     pub skip_lists: HashMap<usize, SkipList>,
     /// Levenshtein DFA bytes (precomputed parametric automaton)
     pub lev_dfa_bytes: Vec<u8>,
+    /// Skip table for walking the vocabulary during fuzzy search
+    /// (`None` unless built with one)
+    pub vocab_trie: Option<VocabTrie>,
+    /// Phonetic key per vocabulary term (`None` unless built with them)
+    pub phonetic: Option<PhoneticIndex>,
+    /// Vocabulary terms grouped by stem (`None` unless built with a stemmer)
+    pub stems: Option<StemIndex>,
+    /// How the words were cut (`CODE_TOKENS` flag), which queries must match
+    pub tokenizer: Tokenizer,
+    /// Words were indexed romanized too (`TRANSLITERATED` flag)
+    pub transliterated: bool,
+    /// Synonym rules (`None` unless built with them)
+    pub synonyms: Option<Synonyms>,
     /// Document metadata (embedded in binary)
     pub docs: Vec<DocMeta>,
     /// Dictionary tables for Parquet-style compression (v7)
     pub dict_tables: DictTables,
     /// Embedded WASM binary (v7)
     pub wasm_bytes: Vec<u8>,
+    /// Stored document text for snippets (`None` unless built with text)
+    pub text: Option<StoredText>,
 }
 
 impl LoadedLayer {
@@ -778,23 +1044,12 @@ impl LoadedLayer {
         // Decode section table (v6)
         let (section_table, _) = decode_section_table(&layer.section_table_bytes)?;
 
-        // Decode postings (delta+varint encoding)
-        let mut postings = Vec::with_capacity(layer.header.term_count as usize);
-        let mut pos = 0;
-        let mut term_idx = 0;
-        while pos < layer.postings_bytes.len() {
-            let (posting_list, consumed) =
-                decode_postings(&layer.postings_bytes[pos..]).map_err(|e| {
-                    io::Error::new(
-                        e.kind(),
-                        format!("Error decoding term {} postings: {}", term_idx, e),
-                    )
-                })?;
-
-            postings.push(posting_list);
-            pos += consumed;
-            term_idx += 1;
-        }
+        // Decode postings (delta+varint encoding) and optional positions
+        let (postings, positions) = decode_postings_section(
+            &layer.postings_bytes,
+            layer.header.term_count as usize,
+            layer.header.flags.has_positions(),
+        )?;
 
         // Validate term count matches header
         if postings.len() != layer.header.term_count as usize {
@@ -829,8 +1084,11 @@ impl LoadedLayer {
             }
         }
 
-        // Decode docs
-        let docs = decode_docs_binary(&layer.docs_bytes)?;
+        // Decode docs and optional stored text
+        let (docs, text) = decode_docs_section(&layer.docs_bytes, layer.header.flags.has_text())?;
+
+        // Decode the vocabulary skip table, phonetic keys, stems and synonyms, if any
+        let extensions = decode_extensions(&layer.header, &layer.ext_bytes)?;
 
         // Decode dictionary tables (v7)
         let dict_tables = if !layer.dict_table_bytes.is_empty() {
@@ -845,12 +1103,20 @@ impl LoadedLayer {
             vocabulary,
             suffix_array,
             postings,
+            positions,
             section_table,
             skip_lists,
             lev_dfa_bytes: layer.lev_dfa_bytes,
+            vocab_trie: extensions.vocab_trie,
+            phonetic: extensions.phonetic,
+            stems: extensions.stems,
+            synonyms: extensions.synonyms,
+            tokenizer: tokenizer_of(layer.header.flags),
+            transliterated: layer.header.flags.has_transliteration(),
             docs,
             dict_tables,
             wasm_bytes: layer.wasm_bytes,
+            text,
         })
     }
 
@@ -896,12 +1162,25 @@ impl LoadedLayer {
 mod tests {
     use super::*;
     use crate::fuzzy::dfa::ParametricDFA;
+    use crate::index::language::Language;
 
     /// Helper to build Levenshtein DFA bytes (for tests)
     fn build_lev_dfa_bytes() -> Vec<u8> {
         ParametricDFA::build(true).to_bytes()
     }
 
+    /// What a v12 writer made of `bytes` (which must have no extensions
+    /// section): no `ext_len`, and the CRC to match.
+    fn downgrade_to_v12(mut bytes: Vec<u8>) -> Vec<u8> {
+        assert_eq!(bytes[52..56], [0; 4], "extensions section in use");
+        bytes.drain(52..56);
+        bytes[4] = 12;
+        let content_len = bytes.len() - SorexFooter::SIZE;
+        let crc32 = SorexFooter::compute_crc32(&bytes[..content_len]);
+        bytes[content_len..content_len + 4].copy_from_slice(&crc32.to_le_bytes());
+        bytes
+    }
+
     #[test]
     fn test_varint_roundtrip() {
         let values = [0, 1, 127, 128, 255, 256, 16383, 16384, u64::MAX];
@@ -1126,6 +1405,7 @@ mod tests {
             docs_len: 5000,
             wasm_len: 50000,      // v7: embedded WASM
             dict_table_len: 1024, // v7: dictionary tables
+            ext_len: 300,         // v14: extensions
         };
 
         let mut buf = Vec::new();
@@ -1141,9 +1421,45 @@ mod tests {
         assert_eq!(decoded.docs_len, header.docs_len);
         assert_eq!(decoded.wasm_len, header.wasm_len);
         assert_eq!(decoded.dict_table_len, header.dict_table_len);
+        assert_eq!(decoded.ext_len, header.ext_len);
         assert!(decoded.flags.has_skip_lists());
     }
 
+    #[test]
+    fn test_v12_files_still_load() {
+        let vocabulary = vec!["rust".to_string()];
+        let layer = BinaryLayer::build_v7(
+            &vocabulary,
+            &[(0, 0)],
+            &[Vec::new()],
+            None,
+            &[],
+            0,
+            build_lev_dfa_bytes(),
+            encode_docs_binary(&[]),
+            Vec::new(),
+        )
+        .unwrap();
+        // What a v12 writer made of the same index: a zero where the second
+        // flags byte now goes
+        let mut bytes = downgrade_to_v12(layer.to_bytes().unwrap());
+        let more_flags_at = 4 + 46;
+        assert_eq!(bytes[more_flags_at], 0);
+
+        let loaded = LoadedLayer::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.vocabulary, vocabulary);
+        assert!(!loaded.transliterated);
+
+        // v12 had nothing in that byte to read, even if it was set
+        bytes[more_flags_at] = (FormatFlags::TRANSLITERATED >> 8) as u8;
+        let header = SorexHeader::read(&mut io::Cursor::new(&bytes)).unwrap();
+        assert!(!header.flags.has_transliteration());
+
+        // Nor are versions past the current one read
+        bytes[4] = VERSION + 1;
+        assert!(BinaryLayer::from_bytes(&bytes).is_err());
+    }
+
     #[test]
     fn test_crc32_detects_corruption() {
         // Create valid layer
@@ -1273,6 +1589,7 @@ mod tests {
             &vocabulary,
             &suffix_array,
             &postings,
+            None,
             &[],
             1,
             lev_dfa_bytes,
@@ -1293,6 +1610,376 @@ mod tests {
         assert_eq!(loaded.wasm_bytes, wasm_bytes);
     }
 
+    #[test]
+    fn test_v7_positions_roundtrip() {
+        let vocabulary = vec!["memory".to_string(), "safety".to_string()];
+        let suffix_array = vec![(0, 0), (1, 0)];
+        let entry = |doc_id, score| PostingEntry {
+            doc_id,
+            section_idx: 0,
+            heading_level: 5,
+            score,
+        };
+        // Out of score order on purpose: positions must follow the entries
+        // through the encoder's (score desc, doc_id asc) sort.
+        let postings = vec![
+            vec![entry(1, 10), entry(0, 50), entry(0, 50)],
+            vec![entry(2, 5), entry(0, 20)],
+        ];
+        let positions = vec![vec![7, 3, 9], vec![0, 4]];
+
+        let layer = BinaryLayer::build_v7(
+            &vocabulary,
+            &suffix_array,
+            &postings,
+            Some(&positions),
+            &[],
+            3,
+            build_lev_dfa_bytes(),
+            encode_docs_binary(&[]),
+            Vec::new(),
+        )
+        .unwrap();
+        assert!(layer.header.flags.has_positions());
+
+        let loaded = LoadedLayer::from_bytes(&layer.to_bytes().unwrap()).unwrap();
+        let pairs: Vec<Vec<(u32, u32)>> = loaded
+            .postings
+            .iter()
+            .zip(&loaded.positions)
+            .map(|(entries, pos)| {
+                entries
+                    .iter()
+                    .map(|e| e.doc_id)
+                    .zip(pos.iter().copied())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(pairs[0], vec![(0, 3), (0, 9), (1, 7)]);
+        assert_eq!(pairs[1], vec![(0, 4), (2, 0)]);
+
+        // Without positions: no flag, nothing decoded
+        let layer = BinaryLayer::build_v7(
+            &vocabulary,
+            &suffix_array,
+            &postings,
+            None,
+            &[],
+            3,
+            build_lev_dfa_bytes(),
+            encode_docs_binary(&[]),
+            Vec::new(),
+        )
+        .unwrap();
+        assert!(!layer.header.flags.has_positions());
+        let loaded = LoadedLayer::from_bytes(&layer.to_bytes().unwrap()).unwrap();
+        assert!(loaded.positions.is_empty());
+    }
+
+    #[test]
+    fn test_text_roundtrip() {
+        let metas: Vec<DocMetaInput> = ["Rust", "Go"]
+            .iter()
+            .map(|title| DocMetaInput {
+                title: title.to_string(),
+                excerpt: String::new(),
+                href: format!("/{}", title),
+                doc_type: "post".to_string(),
+                section_id: None,
+                category: None,
+                author: None,
+                tags: vec![],
+            })
+            .collect();
+        let build = || {
+            BinaryLayer::build_v7(
+                &[],
+                &[],
+                &[],
+                None,
+                &[],
+                2,
+                build_lev_dfa_bytes(),
+                encode_docs_binary(&metas),
+                Vec::new(),
+            )
+            .unwrap()
+        };
+        let text = |body: &str| DocText {
+            text: body.to_string(),
+            spans: vec![TextSpan {
+                start: 0,
+                end: body.len() as u32,
+                section_idx: 0,
+                heading_level: 5,
+            }],
+        };
+
+        let mut layer = build();
+        assert!(!layer.header.flags.has_text());
+        let plain = LoadedLayer::from_bytes(&layer.to_bytes().unwrap()).unwrap();
+        assert!(plain.text.is_none());
+
+        let texts = vec![text("Rust is fast."), text("Go is simple.")];
+        layer.set_text(&texts);
+        assert!(layer.header.flags.has_text());
+        let loaded = LoadedLayer::from_bytes(&layer.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.docs.len(), 2);
+        assert_eq!(loaded.docs[1].title, "Go");
+        let stored = loaded.text.expect("text flag set");
+        assert_eq!(stored.get(0), Some(texts[0].clone()));
+        assert_eq!(stored.get(1), Some(texts[1].clone()));
+
+        // One text per document, or the index is rejected
+        let mut short = build();
+        short.set_text(&texts[..1]);
+        assert!(LoadedLayer::from_bytes(&short.to_bytes().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_vocab_trie_roundtrip() {
+        let vocabulary: Vec<String> = ["optimal", "optimize", "rust"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let postings = vec![Vec::new(); vocabulary.len()];
+        let build = |lev_dfa_bytes: Vec<u8>| {
+            BinaryLayer::build_v7(
+                &vocabulary,
+                &[],
+                &postings,
+                None,
+                &[],
+                0,
+                lev_dfa_bytes,
+                encode_docs_binary(&[]),
+                Vec::new(),
+            )
+            .unwrap()
+        };
+        let trie = VocabTrie::build(&vocabulary);
+
+        let mut layer = build(build_lev_dfa_bytes());
+        layer.set_vocab_trie(&trie);
+        assert!(layer.header.flags.has_vocab_trie());
+        let loaded = LoadedLayer::from_bytes(&layer.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.vocab_trie, Some(trie.clone()));
+        // The DFA comes back without the table glued on
+        assert_eq!(loaded.lev_dfa_bytes, build_lev_dfa_bytes());
+
+        // The table has its own section, so it doesn't need a DFA
+        let mut no_dfa = build(Vec::new());
+        no_dfa.set_vocab_trie(&trie);
+        let loaded = LoadedLayer::from_bytes(&no_dfa.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.vocab_trie, Some(trie.clone()));
+        assert!(loaded.lev_dfa_bytes.is_empty());
+
+        // A table for a different vocabulary is rejected
+        let mut wrong = build(build_lev_dfa_bytes());
+        wrong.set_vocab_trie(&VocabTrie::build(&vocabulary[..2]));
+        assert!(LoadedLayer::from_bytes(&wrong.to_bytes().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_phonetic_roundtrip() {
+        let vocabulary: Vec<String> = ["catherine", "kathryn", "rust"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let postings = vec![Vec::new(); vocabulary.len()];
+        let build = |lev_dfa_bytes: Vec<u8>| {
+            BinaryLayer::build_v7(
+                &vocabulary,
+                &[],
+                &postings,
+                None,
+                &[],
+                0,
+                lev_dfa_bytes,
+                encode_docs_binary(&[]),
+                Vec::new(),
+            )
+            .unwrap()
+        };
+        let trie = VocabTrie::build(&vocabulary);
+        let phonetic = PhoneticIndex::build(&vocabulary);
+
+        // On its own, and after the trie
+        let mut alone = build(build_lev_dfa_bytes());
+        alone.set_phonetic(&phonetic);
+        let loaded = LoadedLayer::from_bytes(&alone.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.phonetic, Some(phonetic.clone()));
+        assert_eq!(loaded.vocab_trie, None);
+        assert_eq!(loaded.lev_dfa_bytes, build_lev_dfa_bytes());
+
+        // Without a DFA too
+        let mut no_dfa = build(Vec::new());
+        no_dfa.set_phonetic(&phonetic);
+        let loaded = LoadedLayer::from_bytes(&no_dfa.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.phonetic, Some(phonetic.clone()));
+
+        let mut both = build(build_lev_dfa_bytes());
+        both.set_vocab_trie(&trie);
+        both.set_phonetic(&phonetic);
+        assert!(both.header.flags.has_phonetic());
+        let loaded = LoadedLayer::from_bytes(&both.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.vocab_trie, Some(trie));
+        assert_eq!(loaded.phonetic, Some(phonetic));
+        assert_eq!(loaded.lev_dfa_bytes, build_lev_dfa_bytes());
+
+        // Keys for a different vocabulary are rejected
+        let mut wrong = build(build_lev_dfa_bytes());
+        wrong.set_phonetic(&PhoneticIndex::build(&vocabulary[..1]));
+        assert!(LoadedLayer::from_bytes(&wrong.to_bytes().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_stems_roundtrip() {
+        let vocabulary: Vec<String> = ["connect", "connected", "kathryn", "rust"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let postings = vec![Vec::new(); vocabulary.len()];
+        let build = || {
+            BinaryLayer::build_v7(
+                &vocabulary,
+                &[],
+                &postings,
+                None,
+                &[],
+                0,
+                build_lev_dfa_bytes(),
+                encode_docs_binary(&[]),
+                Vec::new(),
+            )
+            .unwrap()
+        };
+        let stems = StemIndex::build(&vocabulary, &[Language::English, Language::French]);
+
+        // After every other extension
+        let mut all = build();
+        all.set_vocab_trie(&VocabTrie::build(&vocabulary));
+        all.set_phonetic(&PhoneticIndex::build(&vocabulary));
+        all.set_stems(&stems);
+        assert!(all.header.flags.has_stems());
+        let loaded = LoadedLayer::from_bytes(&all.to_bytes().unwrap()).unwrap();
+        assert!(loaded.vocab_trie.is_some() && loaded.phonetic.is_some());
+        assert_eq!(loaded.stems, Some(stems.clone()));
+        assert_eq!(loaded.lev_dfa_bytes, build_lev_dfa_bytes());
+
+        // Without a DFA too
+        let mut no_dfa = BinaryLayer::build_v7(
+            &vocabulary,
+            &[],
+            &postings,
+            None,
+            &[],
+            0,
+            Vec::new(),
+            encode_docs_binary(&[]),
+            Vec::new(),
+        )
+        .unwrap();
+        no_dfa.set_stems(&stems);
+        let loaded = LoadedLayer::from_bytes(&no_dfa.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.stems, Some(stems.clone()));
+        assert!(loaded.lev_dfa_bytes.is_empty());
+
+        // Stems for a different vocabulary are rejected
+        let mut wrong = build();
+        wrong.set_stems(&StemIndex::build(&vocabulary[..2], &[Language::English]));
+        assert!(LoadedLayer::from_bytes(&wrong.to_bytes().unwrap()).is_err());
+
+        // Synonyms go last, and don't need a DFA either
+        let synonyms = Synonyms::parse(&["k8s, kubernetes"]).unwrap();
+        all.set_synonyms(&synonyms);
+        let loaded = LoadedLayer::from_bytes(&all.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.stems, Some(stems.clone()));
+        assert_eq!(loaded.synonyms, Some(synonyms.clone()));
+        assert_eq!(loaded.lev_dfa_bytes, build_lev_dfa_bytes());
+
+        no_dfa.set_synonyms(&synonyms);
+        let loaded = LoadedLayer::from_bytes(&no_dfa.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.stems, Some(stems));
+        assert_eq!(loaded.synonyms, Some(synonyms));
+    }
+
+    #[test]
+    fn test_tokenizer_roundtrip() {
+        let vocabulary = vec!["vec::push".to_string()];
+        let postings = vec![Vec::new()];
+        for tokenizer in [Tokenizer::Text, Tokenizer::Code] {
+            let mut layer = BinaryLayer::build_v7(
+                &vocabulary,
+                &[],
+                &postings,
+                None,
+                &[],
+                0,
+                Vec::new(),
+                encode_docs_binary(&[]),
+                Vec::new(),
+            )
+            .unwrap();
+            layer.set_tokenizer(tokenizer);
+            let loaded = LoadedLayer::from_bytes(&layer.to_bytes().unwrap()).unwrap();
+            assert_eq!(loaded.tokenizer, tokenizer);
+        }
+    }
+
+    #[test]
+    fn test_transliterated_roundtrip() {
+        let vocabulary = vec!["moskva".to_string()];
+        let postings = vec![Vec::new()];
+        let mut layer = BinaryLayer::build_v7(
+            &vocabulary,
+            &[],
+            &postings,
+            None,
+            &[],
+            0,
+            Vec::new(),
+            encode_docs_binary(&[]),
+            Vec::new(),
+        )
+        .unwrap();
+        let loaded = LoadedLayer::from_bytes(&layer.to_bytes().unwrap()).unwrap();
+        assert!(!loaded.transliterated);
+
+        // The flag lives past the first flags byte, next to the others
+        layer.set_tokenizer(Tokenizer::Code);
+        layer.set_transliterated();
+        let bytes = layer.to_bytes().unwrap();
+        assert_eq!(bytes[5], FormatFlags::CODE_TOKENS as u8);
+        assert_eq!(bytes[50], (FormatFlags::TRANSLITERATED >> 8) as u8);
+        let loaded = LoadedLayer::from_bytes(&bytes).unwrap();
+        assert!(loaded.transliterated);
+        assert_eq!(loaded.tokenizer, Tokenizer::Code);
+    }
+
+    #[test]
+    fn test_v7_positions_must_match_postings() {
+        let vocabulary = vec!["memory".to_string()];
+        let postings = vec![vec![PostingEntry {
+            doc_id: 0,
+            section_idx: 0,
+            heading_level: 0,
+            score: 100,
+        }]];
+        let result = BinaryLayer::build_v7(
+            &vocabulary,
+            &[(0, 0)],
+            &postings,
+            Some(&[vec![1, 2]]),
+            &[],
+            1,
+            build_lev_dfa_bytes(),
+            encode_docs_binary(&[]),
+            Vec::new(),
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_v7_wasm_offset_calculation() {
         // Test that WASM bytes are at the correct offset
@@ -1349,6 +2036,7 @@ mod tests {
             &vocabulary,
             &suffix_array,
             &postings,
+            None,
             &[],
             3,
             lev_dfa_bytes,
@@ -75,8 +75,7 @@ pub fn encode_postings(entries: &[PostingEntry], buf: &mut Vec<u8>) {
     }
 
     // Sort by score descending (primary), then doc_id ascending (secondary for stability)
-    let mut sorted: Vec<&PostingEntry> = entries.iter().collect();
-    sorted.sort_by(|a, b| b.score.cmp(&a.score).then(a.doc_id.cmp(&b.doc_id)));
+    let sorted: Vec<&PostingEntry> = encoding_order(entries).map(|i| &entries[i]).collect();
 
     // Find max score for delta encoding
     let max_score = sorted.first().map(|e| e.score).unwrap_or(0);
@@ -98,6 +97,17 @@ pub fn encode_postings(entries: &[PostingEntry], buf: &mut Vec<u8>) {
     }
 }
 
+/// Order in which `encode_postings` writes entries: score descending, then
+/// doc_id ascending. The sort is stable, so equal entries keep input order.
+fn encoding_order(entries: &[PostingEntry]) -> impl Iterator<Item = usize> {
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (&entries[a], &entries[b]);
+        b.score.cmp(&a.score).then(a.doc_id.cmp(&b.doc_id))
+    });
+    order.into_iter()
+}
+
 /// Decode posting list with delta+varint compression
 ///
 /// Fully materializes PostingEntry vectors for fast in-memory search.
@@ -170,6 +180,105 @@ pub fn decode_postings(bytes: &[u8]) -> io::Result<(Vec<PostingEntry>, usize)> {
     Ok((entries, pos))
 }
 
+// ============================================================================
+// POSITIONS (optional stream after the posting lists)
+// ============================================================================
+//
+// Phrase search needs to know *where* in a document each occurrence sits.
+// Positions are word ordinals (0 = first word of the document text, stop
+// words counted), one per posting entry. They live in their own stream after
+// all posting lists rather than interleaved, so readers that don't care about
+// positions skip them wholesale, and the doc_id/score streams keep compressing
+// the way they always did.
+//
+// Entries are in score order, not position order, so there's nothing useful
+// to delta against. Plain varints it is.
+
+/// Encode one term's positions, in the same order `encode_postings` writes
+/// the entries. `positions[i]` belongs to `entries[i]`.
+pub fn encode_positions(entries: &[PostingEntry], positions: &[u32], buf: &mut Vec<u8>) {
+    debug_assert_eq!(entries.len(), positions.len());
+    for i in encoding_order(entries) {
+        encode_varint(positions[i] as u64, buf);
+    }
+}
+
+/// Decode `count` positions written by `encode_positions`.
+///
+/// The result lines up with the entries returned by `decode_postings`.
+pub fn decode_positions(bytes: &[u8], count: usize) -> io::Result<(Vec<u32>, usize)> {
+    let mut positions = Vec::with_capacity(count);
+    let mut pos = 0;
+    for _ in 0..count {
+        let (position, consumed) = decode_varint(&bytes[pos..])?;
+        if position > u32::MAX as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Word position out of range: {}", position),
+            ));
+        }
+        positions.push(position as u32);
+        pos += consumed;
+    }
+    Ok((positions, pos))
+}
+
+/// Decode a whole postings section.
+///
+/// Without positions this reads posting lists until the bytes run out (the
+/// caller checks the count against the header). With positions it reads
+/// exactly `term_count` lists, then one position per entry, and returns the
+/// positions parallel to the postings.
+#[allow(clippy::type_complexity)]
+pub fn decode_postings_section(
+    bytes: &[u8],
+    term_count: usize,
+    has_positions: bool,
+) -> io::Result<(Vec<Vec<PostingEntry>>, Vec<Vec<u32>>)> {
+    let mut postings = Vec::with_capacity(term_count);
+    let mut pos = 0;
+    while pos < bytes.len() && (!has_positions || postings.len() < term_count) {
+        let (posting_list, consumed) = decode_postings(&bytes[pos..]).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Error decoding term {} postings: {}", postings.len(), e),
+            )
+        })?;
+        postings.push(posting_list);
+        pos += consumed;
+    }
+
+    if !has_positions {
+        return Ok((postings, Vec::new()));
+    }
+
+    let mut positions = Vec::with_capacity(postings.len());
+    for (term_idx, posting_list) in postings.iter().enumerate() {
+        let (term_positions, consumed) = decode_positions(&bytes[pos..], posting_list.len())
+            .map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("Error decoding term {} positions: {}", term_idx, e),
+                )
+            })?;
+        positions.push(term_positions);
+        pos += consumed;
+    }
+
+    if pos != bytes.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Trailing bytes after positions: {} of {} consumed",
+                pos,
+                bytes.len()
+            ),
+        ));
+    }
+
+    Ok((postings, positions))
+}
+
 // ============================================================================
 // SKIP LIST
 // ============================================================================
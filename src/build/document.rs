@@ -28,6 +28,10 @@ pub struct Document {
     /// Tags/labels for categorization
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Language code or name ("en", "de-AT", "german"). Picks the stop words
+    /// dropped and the stemmer run; unknown languages count as none.
+    #[serde(default)]
+    pub lang: Option<String>,
     pub text: String,
     pub field_boundaries: Vec<FieldBoundary>,
 }
@@ -48,6 +52,7 @@ mod tests {
             category: None,
             author: None,
             tags: vec![],
+            lang: None,
             text: "test content".to_string(),
             field_boundaries: vec![],
         }
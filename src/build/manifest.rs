@@ -8,6 +8,7 @@
 //! indexes (`include: {"category": "engineering"}`). The filtering happens at
 //! build time, not query time, so you pay the cost once.
 
+use crate::fuzzy::dfa::DEFAULT_K;
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -17,6 +18,45 @@ pub struct InputManifest {
     pub documents: Vec<String>,
     #[serde(default)]
     pub indexes: HashMap<String, IndexDefinition>,
+    /// Store each document's text in the index so results can show snippets
+    #[serde(default, rename = "storeText")]
+    pub store_text: bool,
+    /// Store phonetic keys so searches can match names spelled by ear
+    #[serde(default)]
+    pub phonetic: bool,
+    /// Language to stem words in ("english", "de", ...), so a search for one
+    /// inflection finds the others. Documents with a `lang` are stemmed in
+    /// their own language instead
+    #[serde(default)]
+    pub stemmer: Option<String>,
+    /// How text is cut into words: "text" (the default) or "code", which
+    /// keeps identifiers like `std::vec::Vec` and `C++` whole and splits
+    /// them into their parts too
+    #[serde(default)]
+    pub tokenizer: Option<String>,
+    /// Language to split compound words in ("german", "nl", ...), so a search
+    /// for "versicherung" finds "Krankenversicherungsbeitrag"
+    #[serde(default)]
+    pub decompound: Option<String>,
+    /// Word list (one per line, relative to the input directory) added to
+    /// the decompounder's dictionary of corpus words
+    #[serde(default, rename = "decompoundDictionary")]
+    pub decompound_dictionary: Option<String>,
+    /// Also index Cyrillic, Greek and Devanagari words romanized, so "moskva"
+    /// finds "Москва" and the other way round
+    #[serde(default)]
+    pub transliterate: bool,
+    /// Synonym rules ("k8s, kubernetes", "auth => authentication"), so a
+    /// search for one phrase finds the others
+    #[serde(default)]
+    pub synonyms: Vec<String>,
+    /// Typo tolerance: maximum edit distance for fuzzy matches (1-3)
+    #[serde(default = "default_max_edit_distance", rename = "maxEditDistance")]
+    pub max_edit_distance: u8,
+}
+
+fn default_max_edit_distance() -> u8 {
+    DEFAULT_K
 }
 
 #[derive(Deserialize, Clone, Debug)]
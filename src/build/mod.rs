@@ -18,6 +18,11 @@ pub mod parallel;
 use std::fs;
 use std::path::Path;
 
+use crate::fuzzy::dfa::MAX_K;
+use crate::index::language::Language;
+use crate::index::synonyms::Synonyms;
+use crate::index::tokenizer::Tokenizer;
+
 #[cfg(feature = "parallel")]
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 
@@ -30,6 +35,27 @@ pub use parallel::*;
 pub struct NormalizedIndexDefinition {
     pub include: IncludeFilter,
     pub fields: Option<Vec<String>>,
+    /// Embed document text for snippets (`storeText` in the manifest)
+    pub store_text: bool,
+    /// Store phonetic keys for the phonetic tier (`phonetic` in the manifest)
+    pub phonetic: bool,
+    /// Stemmer for documents without a `lang` (`stemmer` in the manifest);
+    /// documents with one use their own
+    pub stemmer: Option<Language>,
+    /// How text is cut into words (`tokenizer` in the manifest)
+    pub tokenizer: Tokenizer,
+    /// Language whose compound words are split into parts (`decompound` in
+    /// the manifest)
+    pub decompound: Option<Language>,
+    /// Extra dictionary words for the decompounder (`decompoundDictionary`)
+    pub decompound_words: Vec<String>,
+    /// Index Cyrillic, Greek and Devanagari words romanized too
+    /// (`transliterate` in the manifest)
+    pub transliterate: bool,
+    /// Synonym rules expanded at query time (`synonyms` in the manifest)
+    pub synonyms: Synonyms,
+    /// Edit distance (k) the index's Levenshtein DFA is built for
+    pub max_edit_distance: u8,
 }
 
 /// Create a progress style for the main progress bars
@@ -62,6 +88,77 @@ pub fn run_build(
         .map_err(|e| format!("Failed to read manifest: {}", e))?;
     let manifest: InputManifest = serde_json::from_str(&manifest_content)
         .map_err(|e| format!("Invalid manifest JSON: {}", e))?;
+    if !(1..=MAX_K).contains(&manifest.max_edit_distance) {
+        return Err(format!(
+            "Invalid manifest: maxEditDistance must be between 1 and {}, got {}",
+            MAX_K, manifest.max_edit_distance
+        ));
+    }
+    let stemmer = match manifest.stemmer.as_deref() {
+        None => None,
+        Some(name) => Some(
+            Language::parse(name)
+                .filter(|lang| lang.has_stemmer())
+                .ok_or_else(|| {
+                    let stemmed: Vec<&str> = Language::ALL
+                        .into_iter()
+                        .filter(|lang| lang.has_stemmer())
+                        .map(Language::name)
+                        .collect();
+                    format!(
+                        "Invalid manifest: no stemmer for {:?} (try {})",
+                        name,
+                        stemmed.join(", ")
+                    )
+                })?,
+        ),
+    };
+    let tokenizer = match manifest.tokenizer.as_deref() {
+        None => Tokenizer::default(),
+        Some(name) => Tokenizer::parse(name).ok_or_else(|| {
+            format!(
+                "Invalid manifest: unknown tokenizer {:?} (try text, code)",
+                name
+            )
+        })?,
+    };
+    let decompound = match manifest.decompound.as_deref() {
+        None => None,
+        Some(name) => Some(
+            Language::parse(name)
+                .filter(|lang| lang.has_decompounder())
+                .ok_or_else(|| {
+                    let compounding: Vec<&str> = Language::ALL
+                        .into_iter()
+                        .filter(|lang| lang.has_decompounder())
+                        .map(Language::name)
+                        .collect();
+                    format!(
+                        "Invalid manifest: no decompounder for {:?} (try {})",
+                        name,
+                        compounding.join(", ")
+                    )
+                })?,
+        ),
+    };
+    let decompound_words = match manifest.decompound_dictionary.as_deref() {
+        None => Vec::new(),
+        Some(_) if decompound.is_none() => {
+            return Err(
+                "Invalid manifest: decompoundDictionary needs decompound set to a language"
+                    .to_string(),
+            );
+        }
+        Some(path) => fs::read_to_string(input_path.join(path))
+            .map_err(|e| format!("Failed to read decompoundDictionary {:?}: {}", path, e))?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect(),
+    };
+    let synonyms =
+        Synonyms::parse(&manifest.synonyms).map_err(|e| format!("Invalid manifest: {}", e))?;
 
     // 2. Load documents in parallel with progress bar
     #[cfg(feature = "parallel")]
@@ -94,6 +191,15 @@ pub fn run_build(
         NormalizedIndexDefinition {
             include: IncludeFilter::All,
             fields: None,
+            store_text: manifest.store_text,
+            phonetic: manifest.phonetic,
+            stemmer,
+            tokenizer,
+            decompound,
+            decompound_words,
+            transliterate: manifest.transliterate,
+            synonyms,
+            max_edit_distance: manifest.max_edit_distance,
         },
     )];
 
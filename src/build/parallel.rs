@@ -13,6 +13,7 @@
 //! is enabled.
 
 use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -21,9 +22,18 @@ use std::sync::Arc;
 #[cfg(feature = "parallel")]
 use indicatif::ProgressBar;
 
-use crate::binary::{encode_docs_binary, BinaryLayer, DocMetaInput, PostingEntry};
+use crate::binary::{
+    encode_docs_binary, BinaryLayer, DocMetaInput, DocText, PostingEntry, TextSpan,
+};
 use crate::fuzzy::dfa::ParametricDFA;
-use crate::index::fst::build_fst_index;
+use crate::fuzzy::phonetic::PhoneticIndex;
+use crate::fuzzy::trie::VocabTrie;
+use crate::index::decompound::{Decompounder, COMPOUND_PART_SCORE};
+use crate::index::fst::build_fst_index_with_languages;
+use crate::index::language::Language;
+use crate::index::stem::StemIndex;
+use crate::index::translit::romanized_spans;
+use crate::index::{word_spans_with, word_starts};
 use crate::runtime::deno::{
     ScoringContext, ScoringDocContext, ScoringEvaluator, ScoringMatchContext,
 };
@@ -149,6 +159,17 @@ pub fn load_documents_with_progress(
     load_documents(input_dir, manifest)
 }
 
+/// One serialized Levenshtein DFA per edit distance the indexes ask for.
+fn build_lev_dfas(index_defs: &[(String, NormalizedIndexDefinition)]) -> HashMap<u8, Arc<Vec<u8>>> {
+    let mut dfas = HashMap::new();
+    for (_, def) in index_defs {
+        dfas.entry(def.max_edit_distance).or_insert_with(|| {
+            Arc::new(ParametricDFA::build_for_distance(def.max_edit_distance, true).to_bytes())
+        });
+    }
+    dfas
+}
+
 /// Build multiple indexes in parallel with shared Levenshtein DFA.
 ///
 /// Uses Rayon to construct each index in parallel, then writes binary format.
@@ -158,9 +179,8 @@ pub fn build_indexes_parallel(
     ranking_path: Option<&str>,
     ranking_batch_size: Option<usize>,
 ) -> Vec<BuiltIndex> {
-    // Build Levenshtein DFA once (expensive) and share via Arc
-    let lev_dfa = ParametricDFA::build(true);
-    let lev_dfa_bytes = Arc::new(lev_dfa.to_bytes());
+    // Build each Levenshtein DFA once (expensive) and share via Arc
+    let lev_dfas = build_lev_dfas(index_defs);
 
     // Always embed WASM when feature is enabled
     #[cfg(feature = "embed-wasm")]
@@ -178,7 +198,7 @@ pub fn build_indexes_parallel(
                 name,
                 def,
                 documents,
-                Arc::clone(&lev_dfa_bytes),
+                Arc::clone(&lev_dfas[&def.max_edit_distance]),
                 #[cfg(feature = "embed-wasm")]
                 Arc::clone(&wasm_bytes),
                 ranking_path.as_deref(),
@@ -197,10 +217,9 @@ pub fn build_indexes_with_progress(
     ranking_batch_size: Option<usize>,
     progress: &ProgressBar,
 ) -> Vec<BuiltIndex> {
-    // Build Levenshtein DFA once (expensive) and share via Arc
+    // Build each Levenshtein DFA once (expensive) and share via Arc
     progress.set_message("building Levenshtein DFA...");
-    let lev_dfa = ParametricDFA::build(true);
-    let lev_dfa_bytes = Arc::new(lev_dfa.to_bytes());
+    let lev_dfas = build_lev_dfas(index_defs);
 
     // Always embed WASM when feature is enabled
     #[cfg(feature = "embed-wasm")]
@@ -221,7 +240,7 @@ pub fn build_indexes_with_progress(
                 name,
                 def,
                 documents,
-                Arc::clone(&lev_dfa_bytes),
+                Arc::clone(&lev_dfas[&def.max_edit_distance]),
                 #[cfg(feature = "embed-wasm")]
                 Arc::clone(&wasm_bytes),
                 ranking_path.as_deref(),
@@ -252,6 +271,39 @@ pub fn build_indexes_with_progress(
     build_indexes_parallel(documents, index_defs, ranking_path, ranking_batch_size)
 }
 
+/// Document text and section spans for the stored-text stream.
+///
+/// Spans come from the (already field-filtered) boundaries, so a snippet's
+/// section lookup sees the same sections the postings do.
+fn doc_texts(
+    docs: &[&Document],
+    boundaries: &[FieldBoundary],
+    section_idx_map: &HashMap<&str, u32>,
+) -> Vec<DocText> {
+    let mut texts: Vec<DocText> = docs
+        .iter()
+        .map(|doc| DocText {
+            text: doc.text.clone(),
+            spans: Vec::new(),
+        })
+        .collect();
+
+    for boundary in boundaries {
+        let section_idx = boundary
+            .section_id
+            .as_deref()
+            .and_then(|id| section_idx_map.get(id).copied())
+            .unwrap_or(0);
+        texts[boundary.doc_id].spans.push(TextSpan {
+            start: boundary.start as u32,
+            end: boundary.end as u32,
+            section_idx,
+            heading_level: boundary.heading_level,
+        });
+    }
+    texts
+}
+
 fn build_single_index(
     name: &str,
     def: &NormalizedIndexDefinition,
@@ -309,8 +361,37 @@ fn build_single_index(
         }
     }
 
-    // 4. Build index using existing verified code
-    let fst_index = build_fst_index(search_docs.clone(), texts, all_boundaries.clone());
+    // 4. Build index using existing verified code, dropping each document's
+    // own stop words
+    let languages: Vec<Option<Language>> = filtered_docs
+        .iter()
+        .map(|d| d.lang.as_deref().and_then(Language::parse))
+        .collect();
+
+    // Compound parts, for documents in the decompounder's language (or
+    // without one). Its dictionary is the words those documents use, plus
+    // the manifest's word list
+    let decompounder = def.decompound.and_then(|language| {
+        let corpus_words = texts
+            .iter()
+            .zip(&languages)
+            .filter(|(_, lang)| lang.is_none_or(|lang| lang == language))
+            .flat_map(|(text, _)| word_spans_with(text, def.tokenizer))
+            .map(|(word, _, _)| word);
+        Decompounder::new(
+            language,
+            corpus_words.chain(def.decompound_words.iter().cloned()),
+        )
+    });
+    let fst_index = build_fst_index_with_languages(
+        search_docs.clone(),
+        texts,
+        all_boundaries.clone(),
+        &languages,
+        def.tokenizer,
+        decompounder.as_ref(),
+        def.transliterate,
+    );
 
     // 5. Convert to binary format
     let vocabulary = fst_index.vocabulary.clone();
@@ -432,6 +513,13 @@ fn build_single_index(
                                     0
                                 };
 
+                                // Compound parts rank below the word itself
+                                let score = if p.compound_part {
+                                    (score as f64 * COMPOUND_PART_SCORE) as u32
+                                } else {
+                                    score
+                                };
+
                                 PostingEntry {
                                     doc_id: p.doc_id as u32,
                                     section_idx,
@@ -446,6 +534,50 @@ fn build_single_index(
             .collect()
     };
 
+    // Word position of each posting (parallel to `postings`) for phrase search.
+    // Offsets are byte offsets into the doc text; positions are word ordinals.
+    let doc_word_starts: Vec<Vec<usize>> = filtered_docs
+        .iter()
+        .map(|d| {
+            let mut starts = word_starts(&d.text, def.tokenizer);
+            // Devanagari words only exist romanized, so they add positions
+            if def.transliterate {
+                starts.extend(
+                    romanized_spans(&d.text)
+                        .into_iter()
+                        .map(|(_, start, _)| start),
+                );
+                starts.sort_unstable();
+                starts.dedup();
+            }
+            starts
+        })
+        .collect();
+    let positions: Vec<Vec<u32>> = vocabulary
+        .iter()
+        .map(|term| {
+            fst_index
+                .inverted_index
+                .terms
+                .get(term)
+                .map(|pl| {
+                    pl.postings
+                        .iter()
+                        .map(|p| {
+                            // A compound part takes its word's position
+                            let starts = &doc_word_starts[p.doc_id];
+                            let pos = match starts.binary_search(&p.offset) {
+                                Ok(pos) => pos,
+                                Err(next) => next.saturating_sub(1),
+                            };
+                            pos as u32
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+        .collect();
+
     // Encode docs as binary with section_id support
     let docs_input: Vec<DocMetaInput> = search_docs
         .iter()
@@ -478,6 +610,7 @@ fn build_single_index(
         &vocabulary,
         &suffix_array,
         &postings,
+        Some(&positions),
         &section_table,
         search_docs.len(),
         (*lev_dfa_bytes).clone(),
@@ -490,6 +623,51 @@ fn build_single_index(
     layer.header.dict_table_len = dict_table_bytes.len() as u32;
     layer.dict_table_bytes = dict_table_bytes;
 
+    // Skip table so fuzzy search walks the vocabulary instead of scanning it
+    layer.set_vocab_trie(&VocabTrie::build(&vocabulary));
+
+    // Phonetic keys for names spelled by ear (opt-in: few indexes need them)
+    if def.phonetic {
+        layer.set_phonetic(&PhoneticIndex::build(&vocabulary));
+    }
+
+    // Terms sharing a stem, so exact search matches every inflection. Terms
+    // are stemmed in the language of each document using them, or the
+    // manifest's stemmer for documents without one
+    let mut stemmed_terms: BTreeMap<Language, Vec<u32>> = BTreeMap::new();
+    for (term_idx, entries) in postings.iter().enumerate() {
+        for entry in entries {
+            let language = languages[entry.doc_id as usize].or(def.stemmer);
+            if let Some(language) = language.filter(|l| l.has_stemmer()) {
+                let terms = stemmed_terms.entry(language).or_default();
+                if terms.last() != Some(&(term_idx as u32)) {
+                    terms.push(term_idx as u32);
+                }
+            }
+        }
+    }
+    if !stemmed_terms.is_empty() {
+        layer.set_stems(&StemIndex::build_per_language(&vocabulary, &stemmed_terms));
+    }
+    if !def.synonyms.is_empty() {
+        layer.set_synonyms(&def.synonyms);
+    }
+
+    // Queries against this index must be cut the way its words were
+    layer.set_tokenizer(def.tokenizer);
+    if def.transliterate {
+        layer.set_transliterated();
+    }
+
+    // Stored text for snippets (opt-in: it's the bulk of the index)
+    if def.store_text {
+        layer.set_text(&doc_texts(
+            &filtered_docs,
+            &all_boundaries,
+            &section_idx_map,
+        ));
+    }
+
     let bytes = layer.to_bytes().expect("failed to serialize binary layer");
 
     BuiltIndex {
@@ -504,6 +682,9 @@ fn build_single_index(
 mod tests {
     use super::*;
     use crate::build::{IncludeFilter, NormalizedIndexDefinition};
+    use crate::fuzzy::dfa::DEFAULT_K;
+    use crate::index::synonyms::Synonyms;
+    use crate::index::tokenizer::Tokenizer;
 
     fn make_doc(id: usize, slug: &str, category: Option<&str>) -> Document {
         Document {
@@ -516,6 +697,7 @@ mod tests {
             category: category.map(|s| s.to_string()),
             author: None,
             tags: vec![],
+            lang: None,
             text: format!("{} content", slug),
             field_boundaries: vec![],
         }
@@ -531,6 +713,15 @@ mod tests {
         let def = NormalizedIndexDefinition {
             include: IncludeFilter::All,
             fields: None,
+            store_text: false,
+            phonetic: false,
+            stemmer: None,
+            tokenizer: Tokenizer::Text,
+            decompound: None,
+            decompound_words: Vec::new(),
+            transliterate: false,
+            synonyms: Synonyms::default(),
+            max_edit_distance: DEFAULT_K,
         };
 
         let filtered: Vec<&Document> = docs.iter().filter(|d| def.include.matches(d)).collect();
@@ -550,6 +741,15 @@ mod tests {
         let def = NormalizedIndexDefinition {
             include: IncludeFilter::Filter(filters),
             fields: None,
+            store_text: false,
+            phonetic: false,
+            stemmer: None,
+            tokenizer: Tokenizer::Text,
+            decompound: None,
+            decompound_words: Vec::new(),
+            transliterate: false,
+            synonyms: Synonyms::default(),
+            max_edit_distance: DEFAULT_K,
         };
 
         let filtered: Vec<&Document> = docs.iter().filter(|d| def.include.matches(d)).collect();
@@ -565,6 +765,15 @@ mod tests {
         let def = NormalizedIndexDefinition {
             include: IncludeFilter::All,
             fields: None,
+            store_text: false,
+            phonetic: false,
+            stemmer: None,
+            tokenizer: Tokenizer::Text,
+            decompound: None,
+            decompound_words: Vec::new(),
+            transliterate: false,
+            synonyms: Synonyms::default(),
+            max_edit_distance: DEFAULT_K,
         };
 
         let filtered: Vec<&Document> = docs.iter().filter(|d| def.include.matches(d)).collect();
@@ -362,6 +362,7 @@ pub fn technique_badge(tech: &str) -> String {
         "SKIP" => YELLOW(),
         "DEDUP" => CYAN(),
         "DFA" => BRIGHT_MAGENTA(),
+        "EXT" => BRIGHT_CYAN(),
         "BIN" => BRIGHT_BLUE(),
         "RAW" => GRAY(),
         "DICT" => BRIGHT_GREEN(),
@@ -69,7 +69,7 @@ pub enum Commands {
         /// Path to .sorex file
         file: String,
 
-        /// Search query
+        /// Search query (supports `OR`, `-term`, `"exact"`, and parentheses)
         query: String,
 
         /// Maximum number of results to return
@@ -8,6 +8,12 @@
 //! for every query. Only the character class computation depends on the actual
 //! query string.
 //!
+//! The automaton is built for a maximum edit distance k between 1 and
+//! [`MAX_K`]. k=2 is the default and covers most typos; k=3 is for corpora
+//! full of long technical terms, where "asynchronously" picks up three typos
+//! without trying very hard. Character classes grow as 2^(k+1) and states
+//! grow faster, so k=3 is an opt-in per index rather than the default.
+//!
 //! Based on Schulz-Mihov (2002) "Fast String Correction with Levenshtein Automata."
 //!
 //! # Binary Format
@@ -15,26 +21,35 @@
 //! ```text
 //! Header (8 bytes):
 //!   num_states: u16
-//!   max_distance: u8
+//!   max_distance: u8 (k, 1-3)
 //!   flags: u8 (bit 0 = transpositions enabled)
 //!   reserved: u32
 //!
 //! Accept array (num_states bytes):
 //!   For each state: distance if accepting (0-k), or 0xFF if not accepting
 //!
-//! Transitions array (num_states * 8 * 2 bytes):
-//!   For each state, 8 transitions (one per char class)
+//! Transitions array (num_states * 2^(k+1) * 2 bytes):
+//!   For each state, 2^(k+1) transitions (one per char class)
 //!   Each transition is u16: next state, or 0xFFFF for dead state
 //! ```
 
-use crate::util::simd::levenshtein_within_str;
+use crate::util::simd::{levenshtein_prefix_within_str, levenshtein_within_str};
 use std::collections::{HashMap, VecDeque};
 
-/// Maximum edit distance (k=2 is standard for search)
-pub const MAX_K: u8 = 2;
+/// Largest edit distance the automaton can be built for
+pub const MAX_K: u8 = 3;
+
+/// Edit distance used unless an index or query asks for another (k=2 is
+/// standard for search)
+pub const DEFAULT_K: u8 = 2;
 
-/// Number of character classes: 2^(k+1) = 8 for k=2
-pub const NUM_CHAR_CLASSES: usize = 8;
+/// Number of character classes for the default k: 2^(k+1) = 8 for k=2
+pub const NUM_CHAR_CLASSES: usize = num_char_classes(DEFAULT_K);
+
+/// Number of character classes for edit distance k: 2^(k+1)
+pub const fn num_char_classes(k: u8) -> usize {
+    1 << (k as usize + 1)
+}
 
 /// Dead state marker
 pub const DEAD_STATE: u16 = 0xFFFF;
@@ -83,22 +98,22 @@ impl ParametricState {
 
     /// Minimum edit distance to accepting state (if query consumed)
     #[allow(dead_code)]
-    fn min_distance(&self, query_len_remaining: i8) -> Option<u8> {
+    fn min_distance(&self, query_len_remaining: i8, k: u8) -> Option<u8> {
         self.positions
             .iter()
             .filter(|p| p.offset >= query_len_remaining)
             .map(|p| p.edits + (p.offset - query_len_remaining) as u8)
-            .filter(|&d| d <= MAX_K)
+            .filter(|&d| d <= k)
             .min()
     }
 
-    /// Compute next state given character class
+    /// Compute next state given character class, for edit distance k
     /// char_class bits: bit i = 1 if input matches query[base + i]
-    fn next(&self, char_class: usize, with_transpositions: bool) -> ParametricState {
+    fn next(&self, char_class: usize, k: u8, with_transpositions: bool) -> ParametricState {
         let mut next_positions = Vec::new();
 
         for &pos in &self.positions {
-            if pos.edits > MAX_K {
+            if pos.edits > k {
                 continue;
             }
 
@@ -111,7 +126,7 @@ impl ParametricState {
             }
 
             // Substitution: consume one from each, add one edit
-            if pos.edits < MAX_K {
+            if pos.edits < k {
                 next_positions.push(NfaPos {
                     offset: pos.offset + 1,
                     edits: pos.edits + 1,
@@ -122,7 +137,7 @@ impl ParametricState {
             // This is handled by epsilon transitions during state computation
 
             // Insertion (into query): consume input without advancing query
-            if pos.edits < MAX_K {
+            if pos.edits < k {
                 next_positions.push(NfaPos {
                     offset: pos.offset,
                     edits: pos.edits + 1,
@@ -130,10 +145,10 @@ impl ParametricState {
             }
 
             // Transposition: swap adjacent characters
-            if with_transpositions && pos.edits < MAX_K && pos.offset >= 0 {
+            if with_transpositions && pos.edits < k && pos.offset >= 0 {
                 // Check if this char matches query[base + offset + 1]
-                let next_bit = ((pos.offset + 1) as usize).min(MAX_K as usize);
-                if next_bit <= MAX_K as usize && char_class & (1 << next_bit) != 0 {
+                let next_bit = ((pos.offset + 1) as usize).min(k as usize);
+                if char_class & (1 << next_bit) != 0 {
                     // We matched the "next" character, could be transposition
                     next_positions.push(NfaPos {
                         offset: pos.offset, // Stay at same offset, will need to match current next
@@ -143,21 +158,15 @@ impl ParametricState {
             }
         }
 
-        // Add deletion transitions (epsilon moves)
+        // Add deletion transitions (epsilon moves): skip up to the remaining
+        // edit budget's worth of query chars
         let mut with_deletions = next_positions.clone();
         for &pos in &next_positions {
-            if pos.edits < MAX_K {
-                // Can delete from query (skip query char)
+            for skipped in 1..=k.saturating_sub(pos.edits) {
                 with_deletions.push(NfaPos {
-                    offset: pos.offset + 1,
-                    edits: pos.edits + 1,
+                    offset: pos.offset + skipped as i8,
+                    edits: pos.edits + skipped,
                 });
-                if pos.edits + 1 < MAX_K {
-                    with_deletions.push(NfaPos {
-                        offset: pos.offset + 2,
-                        edits: pos.edits + 2,
-                    });
-                }
             }
         }
 
@@ -182,7 +191,7 @@ impl ParametricState {
     }
 }
 
-/// The compiled DFA: ~70 states, ~1.2KB serialized.
+/// The compiled DFA: ~70 states and ~1.2KB serialized for k=2.
 ///
 /// Build this once at startup, then use `QueryMatcher` for each search query.
 #[derive(Debug, Clone)]
@@ -193,21 +202,29 @@ pub struct ParametricDFA {
     pub transitions: Vec<u16>,
     /// Number of states
     pub num_states: u16,
+    /// Maximum edit distance (k) the automaton was built for
+    pub max_distance: u8,
     /// Whether transpositions are enabled
     pub with_transpositions: bool,
 }
 
 impl ParametricDFA {
-    /// Build the parametric DFA for edit distance k=2
+    /// Build the parametric DFA for the default edit distance (k=2)
     pub fn build(with_transpositions: bool) -> Self {
+        Self::build_for_distance(DEFAULT_K, with_transpositions)
+    }
+
+    /// Build the parametric DFA for edit distance k (clamped to 1..=MAX_K)
+    pub fn build_for_distance(k: u8, with_transpositions: bool) -> Self {
+        let k = k.clamp(1, MAX_K);
         let mut states: Vec<ParametricState> = Vec::new();
         let mut state_map: HashMap<ParametricState, u16> = HashMap::new();
         let mut transitions: Vec<u16> = Vec::new();
         let mut accept: Vec<u8> = Vec::new();
         let mut queue: VecDeque<u16> = VecDeque::new();
 
-        // Initial state: can be at positions 0, 1, 2 with increasing edits
-        let initial_positions: Vec<NfaPos> = (0..=MAX_K)
+        // Initial state: can be at positions 0..=k with increasing edits
+        let initial_positions: Vec<NfaPos> = (0..=k)
             .map(|i| NfaPos {
                 offset: i as i8,
                 edits: i,
@@ -231,13 +248,13 @@ impl ParametricDFA {
                 .filter(|p| p.offset >= 0)
                 .map(|p| p.edits)
                 .min()
-                .filter(|&d| d <= MAX_K)
+                .filter(|&d| d <= k)
                 .unwrap_or(NOT_ACCEPTING);
             accept.push(accept_dist);
 
             // Compute transitions for all character classes
-            for char_class in 0..NUM_CHAR_CLASSES {
-                let next = state.next(char_class, with_transpositions);
+            for char_class in 0..num_char_classes(k) {
+                let next = state.next(char_class, k, with_transpositions);
                 let (normalized, _) = next.normalize();
 
                 let next_id = if normalized.is_empty() {
@@ -260,6 +277,7 @@ impl ParametricDFA {
             accept,
             transitions,
             num_states: states.len() as u16,
+            max_distance: k,
             with_transpositions,
         }
     }
@@ -270,7 +288,7 @@ impl ParametricDFA {
 
         // Header (8 bytes)
         bytes.extend_from_slice(&self.num_states.to_le_bytes());
-        bytes.push(MAX_K);
+        bytes.push(self.max_distance);
         bytes.push(if self.with_transpositions { 1 } else { 0 });
         bytes.extend_from_slice(&[0u8; 4]); // Reserved
 
@@ -295,7 +313,7 @@ impl ParametricDFA {
         let max_k = bytes[2];
         let with_transpositions = bytes[3] & 1 != 0;
 
-        if max_k != MAX_K {
+        if !(1..=MAX_K).contains(&max_k) {
             return Err("Unsupported max distance");
         }
 
@@ -306,11 +324,12 @@ impl ParametricDFA {
         let accept = bytes[8..accept_end].to_vec();
 
         let transitions_bytes = &bytes[accept_end..];
-        let expected_transitions = num_states as usize * NUM_CHAR_CLASSES;
+        let expected_transitions = num_states as usize * num_char_classes(max_k);
         if transitions_bytes.len() < expected_transitions * 2 {
             return Err("Transitions array too short");
         }
 
+        #[allow(clippy::chunks_exact_to_as_chunks)]
         let transitions: Vec<u16> = transitions_bytes
             .chunks_exact(2)
             .take(expected_transitions)
@@ -321,6 +340,7 @@ impl ParametricDFA {
             accept,
             transitions,
             num_states,
+            max_distance: max_k,
             with_transpositions,
         })
     }
@@ -334,18 +354,29 @@ impl ParametricDFA {
 /// Query-specific matcher: wraps the DFA for a particular search term.
 ///
 /// The DFA is shared across all queries; this struct holds the query-specific
-/// state (character classes).
+/// state (character classes) and the edit distance this query allows.
 pub struct QueryMatcher<'a> {
     #[allow(dead_code)]
     dfa: &'a ParametricDFA,
     query_chars: Vec<char>,
+    max_distance: u8,
 }
 
 impl<'a> QueryMatcher<'a> {
+    /// Match within the distance the DFA was built for.
     pub fn new(dfa: &'a ParametricDFA, query: &str) -> Self {
+        Self::with_distance(dfa, query, dfa.max_distance)
+    }
+
+    /// Match within `max_distance` (at most [`MAX_K`]) instead of the DFA's k.
+    ///
+    /// A query can ask for less fuzziness than its index was built with, or
+    /// more, up to `MAX_K`.
+    pub fn with_distance(dfa: &'a ParametricDFA, query: &str, max_distance: u8) -> Self {
         Self {
             dfa,
             query_chars: query.chars().collect(),
+            max_distance: max_distance.min(MAX_K),
         }
     }
 
@@ -355,7 +386,7 @@ impl<'a> QueryMatcher<'a> {
     #[inline]
     fn char_class(&self, c: char, offset: usize) -> usize {
         let mut class = 0usize;
-        for i in 0..=MAX_K as usize {
+        for i in 0..=self.max_distance as usize {
             if offset + i < self.query_chars.len() && self.query_chars[offset + i] == c {
                 class |= 1 << i;
             }
@@ -374,7 +405,18 @@ impl<'a> QueryMatcher<'a> {
         // Note: query_chars is already available, but levenshtein_within_str
         // handles ASCII detection and SIMD dispatch internally
         let query: String = self.query_chars.iter().collect();
-        levenshtein_within_str(&query, term, MAX_K)
+        levenshtein_within_str(&query, term, self.max_distance)
+    }
+
+    /// Is the query within edit distance k of some prefix of this term?
+    ///
+    /// Prefix mode for half-typed words: "optimz" is one edit from
+    /// "optim", so it matches "optimization" here even though the whole
+    /// word is far more than k edits away. Returns the distance to the
+    /// closest prefix.
+    pub fn matches_prefix(&self, term: &str) -> Option<u8> {
+        let query: String = self.query_chars.iter().collect();
+        levenshtein_prefix_within_str(&query, term, self.max_distance)
     }
 }
 
@@ -468,4 +510,84 @@ mod tests {
         assert!(result.is_some(), "programming should match progamming");
         assert!(result.unwrap() <= 2);
     }
+
+    #[test]
+    fn test_matches_prefix() {
+        let dfa = ParametricDFA::build(true);
+        let matcher = QueryMatcher::new(&dfa, "optimz");
+
+        assert_eq!(matcher.matches("optimization"), None);
+        assert_eq!(matcher.matches_prefix("optimization"), Some(1));
+        assert_eq!(matcher.matches_prefix("optimize"), Some(1));
+        assert_eq!(matcher.matches_prefix("options"), Some(2));
+        assert_eq!(matcher.matches_prefix("rust"), None);
+
+        // Exact prefixes are distance 0
+        let matcher = QueryMatcher::new(&dfa, "optim");
+        assert_eq!(matcher.matches_prefix("optimization"), Some(0));
+    }
+
+    #[test]
+    fn test_build_for_distance() {
+        for k in 1..=MAX_K {
+            let dfa = ParametricDFA::build_for_distance(k, true);
+            assert_eq!(dfa.max_distance, k);
+            assert_eq!(
+                dfa.transitions.len(),
+                dfa.num_states as usize * num_char_classes(k)
+            );
+            assert!(dfa.accept.iter().all(|&d| d == NOT_ACCEPTING || d <= k));
+        }
+        assert_eq!(ParametricDFA::build(true).max_distance, DEFAULT_K);
+        assert_eq!(
+            ParametricDFA::build_for_distance(9, true).max_distance,
+            MAX_K
+        );
+        assert!(
+            ParametricDFA::build_for_distance(3, true).num_states
+                > ParametricDFA::build_for_distance(2, true).num_states
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_honors_max_distance() {
+        let dfa = ParametricDFA::build_for_distance(3, true);
+        let restored = ParametricDFA::from_bytes(&dfa.to_bytes()).unwrap();
+        assert_eq!(restored.max_distance, 3);
+        assert_eq!(restored.transitions, dfa.transitions);
+        assert_eq!(restored.serialized_size(), dfa.serialized_size());
+
+        let mut bytes = dfa.to_bytes();
+        for bad in [0, MAX_K + 1] {
+            bytes[2] = bad;
+            assert!(ParametricDFA::from_bytes(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn test_three_typos_need_k3() {
+        let k2 = ParametricDFA::build(true);
+        let k3 = ParametricDFA::build_for_distance(3, true);
+        let typo = "asyncronusli"; // "asynchronously": no h, no o, i for y
+
+        assert_eq!(QueryMatcher::new(&k2, typo).matches("asynchronously"), None);
+        assert_eq!(
+            QueryMatcher::new(&k3, typo).matches("asynchronously"),
+            Some(3)
+        );
+
+        // Queries can ask for more or less than the index was built with
+        assert_eq!(
+            QueryMatcher::with_distance(&k2, typo, 3).matches("asynchronously"),
+            Some(3)
+        );
+        assert_eq!(
+            QueryMatcher::with_distance(&k3, "helo", 0).matches("hello"),
+            None
+        );
+        assert_eq!(
+            QueryMatcher::with_distance(&k3, "helo", 1).matches("hello"),
+            Some(1)
+        );
+    }
 }
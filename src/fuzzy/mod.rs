@@ -5,8 +5,14 @@
 //!
 //! Two implementations here: a simple bounded Levenshtein for one-off comparisons,
 //! and a parametric DFA for bulk matching against many terms (the FST case).
+//! `policy` decides how much of that typo budget each query term gets,
+//! `trie` walks the sorted vocabulary so only plausible terms get checked, and
+//! `phonetic` catches the misspellings too far off to count as typos.
 
-mod levenshtein;
 pub mod dfa;
+mod levenshtein;
+pub mod phonetic;
+pub mod policy;
+pub mod trie;
 
 pub use levenshtein::*;
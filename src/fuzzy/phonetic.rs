@@ -0,0 +1,318 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Phonetic keys: matching names by how they sound.
+//!
+//! People spell names they've only heard. "Tumalacharla" for "Tummalacherla"
+//! is already two edits, "Kathryn" for "Catherine" is four, and the fuzzy
+//! tier's budget runs out long before the guesses stop. What the guesses
+//! share is their sound: collapse each word to a key of its consonant
+//! sounds and they meet.
+//!
+//! The key is Lawrence Philips' original Metaphone, untruncated, so long
+//! names keep every consonant: "tummalacherla" and "tumalacharla" both
+//! become `TMLXRL`. Vowels after the first letter are dropped, doubled
+//! letters collapse, and letter groups that sound alike (`ph`/`f`, `ck`/`k`,
+//! `sh`/`ti` before a vowel) share a code.
+//!
+//! Metaphone only knows English spelling, so only terms made of `a`-`z`
+//! get a key, and only from [`MIN_WORD_LEN`] letters: a three-letter word's
+//! key matches half the vocabulary.
+//!
+//! # Binary Format
+//!
+//! Appended to the LEV_DFA section (after the vocabulary trie, if any),
+//! flagged by `FormatFlags::HAS_PHONETIC`:
+//!
+//! ```text
+//! varint(term_count)
+//! For each term: varint(key_len), key bytes (ASCII; length 0 = no key)
+//! ```
+
+use crate::binary::{decode_varint, encode_varint};
+use std::collections::HashMap;
+use std::io;
+
+/// Shortest word that gets a phonetic key.
+pub const MIN_WORD_LEN: usize = 4;
+
+/// Metaphone key of `word`, or `None` if it isn't an ASCII word of at least
+/// [`MIN_WORD_LEN`] letters.
+///
+/// Case-insensitive: "Tummalacherla" and "tumalacharla" both give `TMLXRL`.
+pub fn phonetic_key(word: &str) -> Option<String> {
+    if word.len() < MIN_WORD_LEN || !word.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    let w: Vec<u8> = word.bytes().map(|b| b.to_ascii_uppercase()).collect();
+    let at = |i: usize| w.get(i).copied().unwrap_or(0);
+    let is_vowel = |c: u8| matches!(c, b'A' | b'E' | b'I' | b'O' | b'U');
+    let front_vowel = |c: u8| matches!(c, b'E' | b'I' | b'Y');
+
+    // Silent or shifted first letters
+    let start = match (w[0], w[1]) {
+        (b'K', b'N') | (b'G', b'N') | (b'P', b'N') | (b'A', b'E') | (b'W', b'R') => 1,
+        _ => 0,
+    };
+
+    let mut key = String::new();
+    for i in start..w.len() {
+        let c = w[i];
+        let (prev, next, after) = (if i > 0 { at(i - 1) } else { 0 }, at(i + 1), at(i + 2));
+        // Doubled letters sound once, except "cc" ("accident")
+        if c == prev && c != b'C' {
+            continue;
+        }
+
+        match c {
+            b'A' | b'E' | b'I' | b'O' | b'U' => {
+                if i == start {
+                    key.push(c as char);
+                }
+            }
+            // Silent in a final "mb" ("plumb")
+            b'B' => {
+                if !(prev == b'M' && next == 0) {
+                    key.push('B');
+                }
+            }
+            b'C' => {
+                if next == b'I' && after == b'A' || next == b'H' {
+                    // "sch" is hard ("school")
+                    key.push_str(if prev == b'S' && next == b'H' {
+                        "K"
+                    } else {
+                        "X"
+                    });
+                } else if front_vowel(next) {
+                    // "sci", "sce", "scy": the s already says it
+                    if prev != b'S' {
+                        key.push('S');
+                    }
+                } else {
+                    key.push('K');
+                }
+            }
+            b'D' => key.push(if next == b'G' && front_vowel(after) {
+                'J'
+            } else {
+                'T'
+            }),
+            b'G' => {
+                let silent = (next == b'H' && after != 0 && !is_vowel(after))
+                    || (next == b'N' && (after == 0 || &w[i + 1..] == b"NED"))
+                    || (prev == b'D' && front_vowel(next));
+                if !silent {
+                    key.push(if front_vowel(next) && prev != b'G' {
+                        'J'
+                    } else {
+                        'K'
+                    });
+                }
+            }
+            // Sounded before a vowel unless it only modifies the letter before
+            b'H' => {
+                let modifies = matches!(prev, b'C' | b'S' | b'P' | b'T' | b'G');
+                if is_vowel(next) && !modifies {
+                    key.push('H');
+                }
+            }
+            b'K' => {
+                if prev != b'C' {
+                    key.push('K');
+                }
+            }
+            b'P' => key.push(if next == b'H' { 'F' } else { 'P' }),
+            b'Q' => key.push('K'),
+            b'S' => {
+                let sh = next == b'H' || (next == b'I' && matches!(after, b'O' | b'A'));
+                key.push(if sh { 'X' } else { 'S' });
+            }
+            b'T' => {
+                if next == b'I' && matches!(after, b'O' | b'A') {
+                    key.push('X');
+                } else if next == b'H' {
+                    key.push('0');
+                } else if !(next == b'C' && after == b'H') {
+                    key.push('T');
+                }
+            }
+            b'V' => key.push('F'),
+            b'W' | b'Y' => {
+                if is_vowel(next) {
+                    key.push(c as char);
+                }
+            }
+            b'X' => key.push_str(if i == 0 { "S" } else { "KS" }),
+            b'Z' => key.push('S'),
+            // F, J, L, M, N, R sound as written
+            _ => key.push(c as char),
+        }
+    }
+
+    (!key.is_empty()).then_some(key)
+}
+
+/// Phonetic key of every vocabulary term, and the terms behind each key.
+///
+/// Only valid for the vocabulary it was built from, in the same order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhoneticIndex {
+    /// Key per term (empty for terms without one)
+    keys: Vec<String>,
+    /// Terms sharing each key, in vocabulary order
+    by_key: HashMap<String, Vec<u32>>,
+}
+
+impl PhoneticIndex {
+    /// Compute the key of every term in `vocabulary`.
+    pub fn build(vocabulary: &[String]) -> Self {
+        Self::from_keys(
+            vocabulary
+                .iter()
+                .map(|term| phonetic_key(term).unwrap_or_default())
+                .collect(),
+        )
+    }
+
+    fn from_keys(keys: Vec<String>) -> Self {
+        let mut by_key: HashMap<String, Vec<u32>> = HashMap::new();
+        for (term_idx, key) in keys.iter().enumerate() {
+            if !key.is_empty() {
+                by_key.entry(key.clone()).or_default().push(term_idx as u32);
+            }
+        }
+        Self { keys, by_key }
+    }
+
+    /// Number of terms covered.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Vocabulary terms that sound like `word`, in vocabulary order.
+    pub fn matches(&self, word: &str) -> &[u32] {
+        phonetic_key(word)
+            .and_then(|key| self.by_key.get(&key))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Append the binary encoding to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        encode_varint(self.keys.len() as u64, out);
+        for key in &self.keys {
+            encode_varint(key.len() as u64, out);
+            out.extend_from_slice(key.as_bytes());
+        }
+    }
+
+    /// Decode from the start of `bytes`, returning the index and bytes consumed.
+    pub fn decode(bytes: &[u8]) -> io::Result<(Self, usize)> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        let (count, mut pos) = decode_varint(bytes)?;
+        // Every term takes at least one byte
+        if count > bytes.len() as u64 {
+            return Err(invalid(format!(
+                "Phonetic index claims {} terms in {} bytes",
+                count,
+                bytes.len()
+            )));
+        }
+
+        let mut keys = Vec::with_capacity(count as usize);
+        for i in 0..count as usize {
+            let (len, consumed) = decode_varint(&bytes[pos..])?;
+            pos += consumed;
+            let key = usize::try_from(len)
+                .ok()
+                .and_then(|len| bytes.get(pos..pos.checked_add(len)?))
+                .filter(|key| key.iter().all(|b| b.is_ascii_alphanumeric()))
+                .ok_or_else(|| invalid(format!("Invalid phonetic key for term {}", i)))?;
+            pos += key.len();
+            keys.push(String::from_utf8_lossy(key).into_owned());
+        }
+
+        Ok((Self::from_keys(keys), pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(word: &str) -> String {
+        phonetic_key(word).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_keys() {
+        let cases = [
+            ("tummalacherla", "TMLXRL"),
+            ("Tumalacharla", "TMLXRL"),
+            ("knight", "NT"),
+            ("phillips", "FLPS"),
+            ("philips", "FLPS"),
+            ("school", "SKL"),
+            ("science", "SNS"),
+            ("thompson", "0MPSN"),
+            ("nation", "NXN"),
+            ("judge", "JJ"),
+            ("xavier", "SFR"),
+            ("alexander", "ALKSNTR"),
+            ("wright", "RT"),
+            ("plumb", "PLM"),
+        ];
+        for (word, expected) in cases {
+            assert_eq!(key(word), expected, "key of {:?}", word);
+        }
+    }
+
+    #[test]
+    fn test_spellings_that_sound_alike_share_keys() {
+        for (a, b) in [
+            ("tummalacherla", "tumalacharla"),
+            ("stephen", "steven"),
+            ("catherine", "kathryn"),
+            ("philip", "filip"),
+            ("smith", "smyth"),
+        ] {
+            assert_eq!(key(a), key(b), "{:?} vs {:?}", a, b);
+        }
+        assert_ne!(key("catherine"), key("caroline"));
+    }
+
+    #[test]
+    fn test_no_key_for_short_or_non_ascii_words() {
+        assert_eq!(phonetic_key("sam"), None);
+        assert_eq!(phonetic_key("harīṣh"), None);
+        assert_eq!(phonetic_key("rust2024"), None);
+        assert_eq!(phonetic_key(""), None);
+    }
+
+    #[test]
+    fn test_index_roundtrip() {
+        let vocabulary: Vec<String> = ["café", "filip", "kathryn", "philip", "rust"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let index = PhoneticIndex::build(&vocabulary);
+        assert_eq!(index.len(), vocabulary.len());
+        assert_eq!(index.matches("phillip"), &[1, 3]);
+        assert_eq!(index.matches("catherine"), &[2]);
+        assert!(index.matches("cafe").is_empty());
+
+        let mut bytes = Vec::new();
+        index.encode(&mut bytes);
+        bytes.push(0xAB); // trailing data belongs to someone else
+        let (decoded, consumed) = PhoneticIndex::decode(&bytes).unwrap();
+        assert_eq!(decoded, index);
+        assert_eq!(consumed, bytes.len() - 1);
+
+        assert!(PhoneticIndex::decode(&bytes[..consumed - 1]).is_err());
+    }
+}
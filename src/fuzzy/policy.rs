@@ -0,0 +1,103 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! How many typos a query term gets, by length.
+//!
+//! One edit budget for every term is wrong at both ends. Two edits turn "cat"
+//! into "car", "hat", "cut", "at", "coat" and most of the three-letter words
+//! in the vocabulary, while "asynchronously" with three typos is still
+//! unmistakable. Short terms need less slack and long terms can take more.
+//!
+//! The policy picks k per term; the index's DFA (or the query's
+//! `max_edit_distance`) caps it.
+
+use super::dfa::MAX_K;
+
+/// Per-term fuzziness: how a term's length maps to its edit budget.
+///
+/// # Example
+///
+/// ```
+/// use sorex::Fuzziness;
+///
+/// let policy = Fuzziness::default();
+/// assert_eq!(policy.distance_for("cat", 2), 0);
+/// assert_eq!(policy.distance_for("rust", 2), 1);
+/// assert_eq!(policy.distance_for("programming", 2), 2);
+/// assert_eq!(policy.distance_for("asynchronously", 3), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fuzziness {
+    /// Every term gets the full budget, whatever its length.
+    Uniform,
+    /// A term of at least `min_chars[i]` characters gets `i + 1` edits.
+    ///
+    /// Thresholds should be ascending; terms shorter than `min_chars[0]`
+    /// must match exactly.
+    ByLength([usize; MAX_K as usize]),
+}
+
+impl Fuzziness {
+    /// k=0 below 4 chars, k=1 for 4-5, k=2 for 6-11, k=3 from 12.
+    ///
+    /// Swapping two letters costs two edits, so k=2 starts early enough for
+    /// "pyhton" to still find "python".
+    pub const ADAPTIVE: Self = Self::ByLength([4, 6, 12]);
+
+    /// Edit distance allowed for `term`, never more than `max_distance`.
+    pub fn distance_for(self, term: &str, max_distance: u8) -> u8 {
+        let k = match self {
+            Self::Uniform => max_distance,
+            Self::ByLength(min_chars) => {
+                let len = term.chars().count();
+                min_chars.iter().take_while(|&&min| len >= min).count() as u8
+            }
+        };
+        k.min(max_distance)
+    }
+}
+
+impl Default for Fuzziness {
+    fn default() -> Self {
+        Self::ADAPTIVE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adaptive_thresholds() {
+        let policy = Fuzziness::ADAPTIVE;
+        assert_eq!(policy.distance_for("", 3), 0);
+        assert_eq!(policy.distance_for("cat", 3), 0);
+        assert_eq!(policy.distance_for("rust", 3), 1);
+        assert_eq!(policy.distance_for("parse", 3), 1);
+        assert_eq!(policy.distance_for("python", 3), 2);
+        assert_eq!(policy.distance_for("optimization", 3), 3);
+    }
+
+    #[test]
+    fn test_capped_by_max_distance() {
+        assert_eq!(Fuzziness::ADAPTIVE.distance_for("optimization", 2), 2);
+        assert_eq!(Fuzziness::ADAPTIVE.distance_for("rust", 0), 0);
+        assert_eq!(Fuzziness::Uniform.distance_for("cat", 2), 2);
+        assert_eq!(Fuzziness::Uniform.distance_for("cat", 1), 1);
+    }
+
+    #[test]
+    fn test_counts_chars_not_bytes() {
+        // Two bytes per char: "дом" is 6 bytes but only 3 chars
+        assert_eq!(Fuzziness::ADAPTIVE.distance_for("дом", 2), 0);
+        assert_eq!(Fuzziness::ADAPTIVE.distance_for("дома", 2), 1);
+    }
+
+    #[test]
+    fn test_custom_table() {
+        let lenient = Fuzziness::ByLength([1, 3, usize::MAX]);
+        assert_eq!(lenient.distance_for("a", 3), 1);
+        assert_eq!(lenient.distance_for("cat", 3), 2);
+        assert_eq!(lenient.distance_for("asynchronously", 3), 2);
+    }
+}
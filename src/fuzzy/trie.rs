@@ -0,0 +1,342 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Vocabulary trie: fuzzy search without scanning every term.
+//!
+//! Checking each vocabulary term against the query is fine at a few thousand
+//! terms and hopeless at a few hundred thousand. But a sorted vocabulary is
+//! already a trie in disguise: terms sharing a prefix sit next to each other.
+//! Walk it in order, carrying one Levenshtein DP row per character of the
+//! current term, and two things fall out:
+//!
+//! - Consecutive terms share their prefix's rows, so "optimise" after
+//!   "optimisation" only computes one new row.
+//! - Once a prefix's row has no cell within k, no term starting with it can
+//!   match, so the whole run of terms under that prefix is skipped.
+//!
+//! The skip needs to know where a prefix's run ends without looking at the
+//! terms in it. That's what gets persisted: for each term, how many chars it
+//! shares with the previous term (`lcp`), and the next term that shares
+//! fewer (`next_smaller`). Following `next_smaller` hops over a whole subtree
+//! at a time, so the walk touches only terms whose prefixes are still alive.
+//!
+//! # Binary Format
+//!
+//! Appended to the LEV_DFA section, flagged by `FormatFlags::HAS_VOCAB_TRIE`:
+//!
+//! ```text
+//! varint(term_count)
+//! For each term: varint(lcp), varint(next_smaller - term_idx)
+//! ```
+
+use crate::binary::{decode_varint, encode_varint};
+use std::io;
+
+/// Skip table that lets fuzzy search walk the sorted vocabulary as a trie.
+///
+/// Only valid for the vocabulary it was built from, in the same order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VocabTrie {
+    /// Chars each term shares with the one before it (0 for the first)
+    lcp: Vec<u32>,
+    /// First later term whose `lcp` is smaller than this term's (`len` if none)
+    next_smaller: Vec<u32>,
+}
+
+impl VocabTrie {
+    /// Build the skip table for a vocabulary.
+    ///
+    /// Any order is correct, but only a sorted vocabulary clusters shared
+    /// prefixes into runs worth skipping.
+    pub fn build(vocabulary: &[String]) -> Self {
+        let mut lcp = Vec::with_capacity(vocabulary.len());
+        let mut prev = "";
+        for term in vocabulary {
+            let shared = prev
+                .chars()
+                .zip(term.chars())
+                .take_while(|(a, b)| a == b)
+                .count();
+            lcp.push(shared as u32);
+            prev = term;
+        }
+
+        // Monotonic stack: each term waits until a smaller lcp shows up
+        let len = lcp.len();
+        let mut next_smaller = vec![len as u32; len];
+        let mut stack: Vec<usize> = Vec::new();
+        for (i, &shared) in lcp.iter().enumerate() {
+            while let Some(&top) = stack.last() {
+                if shared >= lcp[top] {
+                    break;
+                }
+                next_smaller[top] = i as u32;
+                stack.pop();
+            }
+            stack.push(i);
+        }
+
+        Self { lcp, next_smaller }
+    }
+
+    /// Number of terms covered.
+    pub fn len(&self) -> usize {
+        self.lcp.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lcp.is_empty()
+    }
+
+    /// Append the binary encoding to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        encode_varint(self.lcp.len() as u64, out);
+        for (i, (&shared, &next)) in self.lcp.iter().zip(&self.next_smaller).enumerate() {
+            encode_varint(shared as u64, out);
+            encode_varint(next as u64 - i as u64, out);
+        }
+    }
+
+    /// Decode from the start of `bytes`, returning the trie and bytes consumed.
+    pub fn decode(bytes: &[u8]) -> io::Result<(Self, usize)> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        let (count, mut pos) = decode_varint(bytes)?;
+        // Every term takes at least two bytes
+        if count > (bytes.len() / 2) as u64 {
+            return Err(invalid(format!(
+                "Vocabulary trie claims {} terms in {} bytes",
+                count,
+                bytes.len()
+            )));
+        }
+        let len = count as usize;
+
+        let mut lcp = Vec::with_capacity(len);
+        let mut next_smaller = Vec::with_capacity(len);
+        for i in 0..len {
+            let (shared, consumed) = decode_varint(&bytes[pos..])?;
+            pos += consumed;
+            let (delta, consumed) = decode_varint(&bytes[pos..])?;
+            pos += consumed;
+
+            let next = (i as u64).saturating_add(delta);
+            if shared > u32::MAX as u64 || delta == 0 || next > len as u64 {
+                return Err(invalid(format!("Invalid vocabulary trie entry {}", i)));
+            }
+            lcp.push(shared as u32);
+            next_smaller.push(next as u32);
+        }
+
+        Ok((Self { lcp, next_smaller }, pos))
+    }
+
+    /// Terms within `max_distance` edits of `query`, as `(term_idx, distance)`
+    /// in vocabulary order.
+    ///
+    /// With `prefix`, a term matches when some non-empty prefix of it is
+    /// within `max_distance` (the distance reported is the closest prefix's),
+    /// the same as `QueryMatcher::matches_prefix`.
+    pub fn fuzzy_matches(
+        &self,
+        vocabulary: &[String],
+        query: &str,
+        max_distance: u8,
+        prefix: bool,
+    ) -> Vec<(usize, u8)> {
+        let query: Vec<char> = query.chars().collect();
+        let m = query.len();
+        let len = self.len().min(vocabulary.len());
+
+        // rows[d]: distance from the current term's first d chars to each
+        // query prefix. closest[d]: best rows[1..=d][m], for prefix mode.
+        let mut rows: Vec<Vec<u8>> = vec![(0..=m).map(|j| j.min(255) as u8).collect()];
+        let mut closest: Vec<u8> = vec![u8::MAX];
+        let mut matches = Vec::new();
+
+        let mut i = 0;
+        while i < len {
+            let shared = (self.lcp[i] as usize).min(rows.len() - 1);
+            rows.truncate(shared + 1);
+            closest.truncate(shared + 1);
+
+            let mut dead_at = None;
+            for c in vocabulary[i].chars().skip(shared) {
+                let row = next_row(&rows[rows.len() - 1], c, &query);
+                if row.iter().all(|&d| d > max_distance) {
+                    dead_at = Some(rows.len());
+                    break;
+                }
+                closest.push(closest[closest.len() - 1].min(row[m]));
+                rows.push(row);
+            }
+
+            let depth = rows.len() - 1;
+            let distance = if prefix {
+                closest[depth]
+            } else {
+                rows[depth][m]
+            };
+            match dead_at {
+                None => {
+                    if distance <= max_distance {
+                        matches.push((i, distance));
+                    }
+                    i += 1;
+                }
+                // Nothing under this prefix gets closer; in prefix mode
+                // they all match at the distance already reached
+                Some(dead) => {
+                    let end = self.subtree_end(i, dead, len);
+                    if prefix && distance <= max_distance {
+                        matches.extend((i..end).map(|j| (j, distance)));
+                    }
+                    i = end;
+                }
+            }
+        }
+
+        matches
+    }
+
+    /// First term after `i` that doesn't share `i`'s first `depth` chars.
+    fn subtree_end(&self, i: usize, depth: usize, len: usize) -> usize {
+        let mut j = i + 1;
+        while j < len && self.lcp[j] as usize >= depth {
+            // Everything before next_smaller[j] shares at least lcp[j] chars
+            j = self.next_smaller[j] as usize;
+        }
+        j.min(len)
+    }
+}
+
+/// One Levenshtein DP step: extend the term prefix by `c`.
+#[inline]
+fn next_row(prev: &[u8], c: char, query: &[char]) -> Vec<u8> {
+    let mut row = Vec::with_capacity(prev.len());
+    row.push(prev[0].saturating_add(1));
+    for (j, &q) in query.iter().enumerate() {
+        let cost = u8::from(q != c);
+        let cell = (prev[j + 1].saturating_add(1))
+            .min(row[j].saturating_add(1))
+            .min(prev[j].saturating_add(cost));
+        row.push(cell);
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::simd::{levenshtein_prefix_within_str, levenshtein_within_str};
+
+    fn vocab(words: &[&str]) -> Vec<String> {
+        let mut words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        words.sort();
+        words.dedup();
+        words
+    }
+
+    /// The linear scan the trie replaces.
+    fn scan(vocabulary: &[String], query: &str, k: u8, prefix: bool) -> Vec<(usize, u8)> {
+        vocabulary
+            .iter()
+            .enumerate()
+            .filter_map(|(i, term)| {
+                let d = if prefix {
+                    levenshtein_prefix_within_str(query, term, k)
+                } else {
+                    levenshtein_within_str(query, term, k)
+                };
+                d.map(|d| (i, d))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build_lcp_and_skips() {
+        let words = vocab(&["opt", "optimal", "optimize", "option", "rust"]);
+        let trie = VocabTrie::build(&words);
+        assert_eq!(trie.lcp, vec![0, 3, 5, 4, 0]);
+        assert_eq!(trie.next_smaller, vec![5, 4, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_encode_roundtrip() {
+        let words = vocab(&["apple", "application", "apply", "banana", "band"]);
+        let trie = VocabTrie::build(&words);
+        let mut bytes = Vec::new();
+        trie.encode(&mut bytes);
+        bytes.push(0xAB); // trailing data belongs to someone else
+
+        let (decoded, consumed) = VocabTrie::decode(&bytes).unwrap();
+        assert_eq!(decoded, trie);
+        assert_eq!(consumed, bytes.len() - 1);
+
+        assert!(VocabTrie::decode(&bytes[..consumed - 1]).is_err());
+        // A skip pointer that doesn't move forward would loop forever
+        let mut stuck = Vec::new();
+        encode_varint(1, &mut stuck);
+        encode_varint(0, &mut stuck);
+        encode_varint(0, &mut stuck);
+        assert!(VocabTrie::decode(&stuck).is_err());
+    }
+
+    #[test]
+    fn test_matches_linear_scan() {
+        let words = vocab(&[
+            "a",
+            "async",
+            "asynchronous",
+            "asynchronously",
+            "await",
+            "cafe",
+            "café",
+            "cat",
+            "category",
+            "optimal",
+            "optimization",
+            "optimize",
+            "optimizer",
+            "option",
+            "options",
+            "python",
+            "rust",
+            "rustic",
+            "rusty",
+            "trust",
+            "дом",
+            "дома",
+        ]);
+        let trie = VocabTrie::build(&words);
+        let queries = [
+            "",
+            "a",
+            "ca",
+            "cat",
+            "cafe",
+            "optimz",
+            "optimise",
+            "pyhton",
+            "rust",
+            "rsut",
+            "asyncronusli",
+            "дом",
+            "zzz",
+        ];
+        for k in 0..=3 {
+            for query in queries {
+                for prefix in [false, true] {
+                    assert_eq!(
+                        trie.fuzzy_matches(&words, query, k, prefix),
+                        scan(&words, query, k, prefix),
+                        "query {:?}, k={}, prefix={}",
+                        query,
+                        k,
+                        prefix
+                    );
+                }
+            }
+        }
+    }
+}
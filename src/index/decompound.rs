@@ -0,0 +1,270 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decompounding: "Krankenversicherungsbeitrag" contains "Versicherung".
+//!
+//! German, Dutch and the Scandinavian languages write compounds as one word,
+//! so the word someone searches for is often buried inside a longer one that
+//! no query equals. Without help only the suffix array finds it, as a Tier 2
+//! infix, if at all.
+//!
+//! A [`Decompounder`] splits a word into dictionary words, allowing the
+//! linking letters ("Fugen") each language puts between parts:
+//!
+//! ```text
+//! krankenversicherungsbeitrag → kranken | versicherung | s | beitrag
+//! ```
+//!
+//! The split uses the fewest parts it can, then each part is split again, so
+//! a compound inside a compound ("krankenversicherung") yields its parts too.
+//! Every part is indexed at its own offset inside the word, as an extra
+//! posting scored [`COMPOUND_PART_SCORE`] of a whole word: "versicherung"
+//! finds the compound as an exact match, below documents using the word on
+//! its own.
+//!
+//! The dictionary is the words the corpus uses, plus any word list the
+//! manifest adds. Parts are at least [`MIN_PART_CHARS`] long and never stop
+//! words, so "beitrag" doesn't turn into "bei" + "trag"; past that, a
+//! dictionary of real words is what keeps the splits honest.
+//!
+//! # References
+//!
+//! - **Dictionary decompounding**: Lucene's `DictionaryCompoundWordTokenFilter`
+//!   emits every dictionary word found inside a token.
+//! - **Linking elements**: Koehn & Knight, "Empirical Methods for Compound
+//!   Splitting" (EACL 2003) split German compounds into known words with
+//!   filler letters, preferring fewer parts.
+
+use super::inverted::is_stop_word_in;
+use super::language::Language;
+use crate::util::normalize::fold_char;
+use std::collections::HashSet;
+
+/// Score of a compound part's posting, relative to the whole word's.
+pub const COMPOUND_PART_SCORE: f64 = 0.8;
+
+/// Shortest part worth splitting off, in chars.
+pub const MIN_PART_CHARS: usize = 3;
+
+/// Splits compound words into dictionary words, for one language.
+#[derive(Debug, Clone)]
+pub struct Decompounder {
+    language: Language,
+    words: HashSet<String>,
+}
+
+impl Decompounder {
+    /// Build a decompounder for `language` from dictionary words (folded
+    /// here). Stop words and words under [`MIN_PART_CHARS`] are left out.
+    ///
+    /// Returns `None` for languages that don't write compounds as one word
+    /// (see [`Language::has_decompounder`]).
+    pub fn new(language: Language, words: impl IntoIterator<Item = String>) -> Option<Self> {
+        if !language.has_decompounder() {
+            return None;
+        }
+        let words = words
+            .into_iter()
+            .map(|word| fold_word(&word).0)
+            .filter(|word| word.chars().count() >= MIN_PART_CHARS)
+            .filter(|word| !is_stop_word_in(word, Some(language)))
+            .collect();
+        Some(Self { language, words })
+    }
+
+    /// The language whose compounds this splits.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// The parts of `word` as `(folded part, start, end)`, with byte ranges
+    /// in `word` as written. Empty unless it splits into dictionary words.
+    ///
+    /// ```
+    /// use sorex::{Decompounder, Language};
+    ///
+    /// let words = ["kranken", "versicherung", "beitrag"].map(String::from);
+    /// let german = Decompounder::new(Language::German, words).unwrap();
+    /// let parts: Vec<String> = german
+    ///     .parts("Krankenversicherungsbeitrag")
+    ///     .into_iter()
+    ///     .map(|(part, _, _)| part)
+    ///     .collect();
+    /// assert_eq!(parts, ["kranken", "versicherung", "beitrag"]);
+    /// ```
+    pub fn parts(&self, word: &str) -> Vec<(String, usize, usize)> {
+        let (folded, origin) = fold_word(word);
+        let mut ranges = Vec::new();
+        self.split_into(&folded, 0, folded.len(), &mut ranges);
+
+        // Map folded byte ranges back to the word as written
+        let written = |at: usize| origin.get(at).copied().unwrap_or(word.len());
+        ranges
+            .into_iter()
+            .map(|(start, end)| (folded[start..end].to_string(), written(start), written(end)))
+            .collect()
+    }
+
+    /// Push the parts of `folded[start..end]`, and their parts, in order.
+    fn split_into(&self, folded: &str, start: usize, end: usize, out: &mut Vec<(usize, usize)>) {
+        for (part_start, part_end) in self.split(&folded[start..end]) {
+            let (part_start, part_end) = (start + part_start, start + part_end);
+            out.push((part_start, part_end));
+            self.split_into(folded, part_start, part_end, out);
+        }
+    }
+
+    /// Split a folded word into two or more dictionary words, using as few
+    /// as possible and preferring a longer first part. Byte ranges exclude
+    /// the linking letters between parts. Empty if there's no such split.
+    fn split(&self, word: &str) -> Vec<(usize, usize)> {
+        let bounds: Vec<usize> = word
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(word.len()))
+            .collect();
+        let chars = bounds.len() - 1;
+        if chars < 2 * MIN_PART_CHARS {
+            return Vec::new();
+        }
+        let linkers = linking_elements(self.language);
+
+        // best[i]: fewest parts covering word[bounds[i]..], and the end (char
+        // index) of the first of them plus where the next one starts
+        let mut best: Vec<Option<(usize, usize, usize)>> = vec![None; chars + 1];
+        best[chars] = Some((0, chars, chars));
+        for i in (0..chars).rev() {
+            for j in (i + MIN_PART_CHARS..=chars).rev() {
+                if !self.words.contains(&word[bounds[i]..bounds[j]]) {
+                    continue;
+                }
+                // The part ends the word, or is followed by a linker (or
+                // nothing) and more parts
+                let rest = &word[bounds[j]..];
+                let nexts = std::iter::once(j).chain(
+                    linkers
+                        .iter()
+                        .filter(|linker| rest.starts_with(*linker))
+                        .map(|linker| j + linker.chars().count()),
+                );
+                for next in nexts {
+                    let Some((count, _, _)) = best[next] else {
+                        continue;
+                    };
+                    if next == chars && j < chars {
+                        continue; // a dangling linker isn't a part
+                    }
+                    if i == 0 && next == chars {
+                        continue; // the whole word isn't a split
+                    }
+                    if best[i].is_none_or(|(fewest, _, _)| count + 1 < fewest) {
+                        best[i] = Some((count + 1, j, next));
+                    }
+                }
+            }
+        }
+
+        let mut parts = Vec::new();
+        let mut i = 0;
+        while i < chars {
+            let Some((_, end, next)) = best[i] else {
+                return Vec::new();
+            };
+            parts.push((bounds[i], bounds[end]));
+            i = next;
+        }
+        parts
+    }
+}
+
+/// Letters a compound may put between its parts ("Versicherung-s-beitrag").
+fn linking_elements(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::German => &["s", "es", "n", "en", "er", "e", "ens"],
+        Language::Dutch => &["s", "e", "en"],
+        Language::Swedish => &["s", "a", "o", "u", "e"],
+        Language::Norwegian | Language::Danish => &["s", "e"],
+        _ => &[],
+    }
+}
+
+/// Fold a word like the indexer does, with the written byte offset of every
+/// folded byte.
+fn fold_word(word: &str) -> (String, Vec<usize>) {
+    let mut folded = String::with_capacity(word.len());
+    let mut origin = Vec::with_capacity(word.len());
+    for (at, c) in word.char_indices() {
+        fold_char(c, &mut folded);
+        origin.resize(folded.len(), at);
+    }
+    (folded, origin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decompounder(language: Language, words: &[&str]) -> Decompounder {
+        Decompounder::new(language, words.iter().map(|w| w.to_string())).unwrap()
+    }
+
+    fn parts(decompounder: &Decompounder, word: &str) -> Vec<String> {
+        decompounder
+            .parts(word)
+            .into_iter()
+            .map(|(part, _, _)| part)
+            .collect()
+    }
+
+    #[test]
+    fn test_splits_with_linking_letters() {
+        let german = decompounder(
+            Language::German,
+            &["kranken", "versicherung", "beitrag", "arbeit", "zeit"],
+        );
+        assert_eq!(
+            parts(&german, "Krankenversicherungsbeitrag"),
+            ["kranken", "versicherung", "beitrag"]
+        );
+        assert_eq!(parts(&german, "Arbeitszeit"), ["arbeit", "zeit"]);
+        // No split into dictionary words, no parts
+        assert!(parts(&german, "Versicherungsmakler").is_empty());
+        assert!(parts(&german, "Versicherung").is_empty());
+    }
+
+    #[test]
+    fn test_compounds_inside_compounds_split_too() {
+        let german = decompounder(
+            Language::German,
+            &["krankenversicherung", "kranken", "versicherung", "beitrag"],
+        );
+        assert_eq!(
+            parts(&german, "krankenversicherungsbeitrag"),
+            ["krankenversicherung", "kranken", "versicherung", "beitrag"]
+        );
+    }
+
+    #[test]
+    fn test_offsets_are_in_the_written_word() {
+        let german = decompounder(Language::German, &["strasse", "bahn"]);
+        let word = "Straßenbahn";
+        let ranges: Vec<&str> = german
+            .parts(word)
+            .into_iter()
+            .map(|(_, start, end)| &word[start..end])
+            .collect();
+        assert_eq!(ranges, ["Straße", "bahn"]);
+    }
+
+    #[test]
+    fn test_languages() {
+        let dutch = decompounder(Language::Dutch, &["ziekte", "verzekering"]);
+        assert_eq!(
+            parts(&dutch, "ziekteverzekering"),
+            ["ziekte", "verzekering"]
+        );
+        let swedish = decompounder(Language::Swedish, &["sjuk", "försäkring"]);
+        assert_eq!(parts(&swedish, "sjukförsäkring"), ["sjuk", "forsakring"]);
+        assert!(Decompounder::new(Language::English, Vec::new()).is_none());
+    }
+}
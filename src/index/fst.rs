@@ -18,8 +18,11 @@
 //! For huge vocabularies (>10k terms), consider `fst` crate. For typical blogs,
 //! this is faster and simpler.
 
-use super::inverted::build_inverted_index_parallel;
+use super::decompound::Decompounder;
+use super::inverted::build_inverted_index_with_languages;
+use super::language::Language;
 use super::sais::build_vocab_suffix_array_sais;
+use super::tokenizer::Tokenizer;
 use crate::types::{FieldBoundary, InvertedIndex, SearchDoc, VocabSuffixEntry};
 
 /// Index with vocabulary for efficient search.
@@ -55,9 +58,40 @@ pub fn build_fst_index(
     docs: Vec<SearchDoc>,
     texts: Vec<String>,
     field_boundaries: Vec<FieldBoundary>,
+) -> FstIndex {
+    build_fst_index_with_languages(
+        docs,
+        texts,
+        field_boundaries,
+        &[],
+        Tokenizer::Text,
+        None,
+        false,
+    )
+}
+
+/// [`build_fst_index`] with a language per document, which picks the stop
+/// words it drops, the tokenizer that cuts the words, the decompounder that
+/// splits compounds and whether words are also indexed romanized (see
+/// [`build_inverted_index_with_languages`]).
+pub fn build_fst_index_with_languages(
+    docs: Vec<SearchDoc>,
+    texts: Vec<String>,
+    field_boundaries: Vec<FieldBoundary>,
+    languages: &[Option<Language>],
+    tokenizer: Tokenizer,
+    decompounder: Option<&Decompounder>,
+    transliterate: bool,
 ) -> FstIndex {
     // Step 1: Build inverted index in parallel
-    let inverted_index = build_inverted_index_parallel(&texts, &field_boundaries);
+    let inverted_index = build_inverted_index_with_languages(
+        &texts,
+        &field_boundaries,
+        languages,
+        tokenizer,
+        decompounder,
+        transliterate,
+    );
 
     // Step 2: Extract vocabulary (sorted for binary search and suffix array)
     let mut vocabulary: Vec<String> = inverted_index.terms.keys().cloned().collect();
@@ -34,12 +34,25 @@ use std::collections::HashMap;
 
 /// Word boundary detection: checks if character is a word separator.
 fn is_word_boundary(c: char) -> bool {
-    !c.is_alphanumeric()
+    !c.is_alphanumeric() && !is_thai(c)
 }
 
 use std::collections::HashSet;
 use std::sync::LazyLock;
 
+use super::language::Language;
+use super::decompound::{Decompounder, COMPOUND_PART_SCORE};
+use super::segment::{is_thai, segment};
+use super::tokenizer::{code_spans, Tokenizer};
+use super::translit::romanized_spans;
+
+/// Stop words from data/stop_words.json, per language and all together.
+struct StopWords {
+    /// Every language's stop words
+    all: HashSet<String>,
+    by_language: HashMap<Language, HashSet<String>>,
+}
+
 /// Multilingual stop words loaded from data/stop_words.json.
 ///
 /// These words are:
@@ -51,45 +64,70 @@ use std::sync::LazyLock;
 /// English, Spanish, French, German, Portuguese, Italian, Dutch, Russian,
 /// Polish, Swedish, Norwegian, Danish, Finnish, Turkish, Indonesian,
 /// Arabic, Hindi, Chinese, Japanese, Korean (romanized forms).
-static STOP_WORDS: LazyLock<HashSet<String>> = LazyLock::new(|| {
+static STOP_WORDS: LazyLock<StopWords> = LazyLock::new(|| {
     let json_str = include_str!("../../data/stop_words.json");
     parse_stop_words_json(json_str)
 });
 
-/// Parse stop words from JSON, flattening all language arrays into a single set.
+/// Parse stop words from JSON, one set per language plus their union.
 /// Normalizes words to match how input text is normalized (strips diacritics).
-fn parse_stop_words_json(json_str: &str) -> HashSet<String> {
-    let mut stop_words = HashSet::new();
+///
+/// Keys name the language, optionally with a script after an underscore
+/// ("russian_cyrillic", "russian_translit"); both land in the same set.
+fn parse_stop_words_json(json_str: &str) -> StopWords {
+    let mut stop_words = StopWords {
+        all: HashSet::new(),
+        by_language: HashMap::new(),
+    };
 
     // Simple JSON parsing without external dependency
     // The JSON structure is: { "lang": ["word1", "word2", ...], ... }
     let mut in_array = false;
     let mut current_word = String::new();
     let mut in_string = false;
+    // Last string outside an array: the key of the array that follows
+    let mut key = String::new();
+    let mut language = None;
 
     for ch in json_str.chars() {
         match ch {
-            '[' => in_array = true,
-            ']' => in_array = false,
+            '[' if !in_string => {
+                in_array = true;
+                language = key.split('_').next().and_then(Language::parse);
+            }
+            ']' if !in_string => in_array = false,
             '"' if in_array => {
                 if in_string {
-                    // End of string, normalize and add word
-                    if !current_word.is_empty() {
-                        // Normalize to strip diacritics (e.g., "tú" → "tu", "está" → "esta")
-                        let normalized = normalize(&current_word);
-                        if !normalized.is_empty() {
-                            stop_words.insert(normalized);
+                    // End of string: add the word normalized to strip
+                    // diacritics (e.g., "tú" → "tu", "está" → "esta"), and
+                    // as written for non-normalized lookups
+                    let normalized = normalize(&current_word);
+                    for word in [normalized, std::mem::take(&mut current_word)] {
+                        if word.is_empty() {
+                            continue;
                         }
-                        // Also insert original for non-normalized lookups
-                        stop_words.insert(current_word.clone());
-                        current_word.clear();
+                        if let Some(language) = language {
+                            stop_words
+                                .by_language
+                                .entry(language)
+                                .or_default()
+                                .insert(word.clone());
+                        }
+                        stop_words.all.insert(word);
                     }
                 }
                 in_string = !in_string;
             }
-            _ if in_string => {
+            '"' => {
+                if !in_string {
+                    key.clear();
+                }
+                in_string = !in_string;
+            }
+            _ if in_string && in_array => {
                 current_word.push(ch);
             }
+            _ if in_string => key.push(ch),
             _ => {}
         }
     }
@@ -97,7 +135,7 @@ fn parse_stop_words_json(json_str: &str) -> HashSet<String> {
     stop_words
 }
 
-/// Check if a word is a stop word.
+/// Check if a word is a stop word in any language.
 ///
 /// Stop words are filtered during index construction to:
 /// 1. Reduce index size
@@ -105,12 +143,29 @@ fn parse_stop_words_json(json_str: &str) -> HashSet<String> {
 /// 3. Improve search relevance
 #[inline]
 pub fn is_stop_word(word: &str) -> bool {
-    STOP_WORDS.contains(word)
+    STOP_WORDS.all.contains(word)
+}
+
+/// Check if a word is a stop word in `language`, or in any language when
+/// it's `None`.
+///
+/// Documents with a language only drop that language's stop words, so an
+/// English document keeps "die" and a German one keeps "land".
+#[inline]
+pub fn is_stop_word_in(word: &str, language: Option<Language>) -> bool {
+    match language {
+        None => is_stop_word(word),
+        Some(language) => STOP_WORDS
+            .by_language
+            .get(&language)
+            .is_some_and(|words| words.contains(word)),
+    }
 }
 
 /// Tokenize text into (word, offset) pairs.
 ///
-/// Returns normalized words with their byte offsets in the original text.
+/// Returns normalized words with their byte offsets in the original text,
+/// minus `language`'s stop words (every language's without one).
 /// Only returns words at word boundaries (start of text or after non-alphanumeric).
 ///
 /// # Lean Specification
@@ -119,7 +174,51 @@ pub fn is_stop_word(word: &str) -> bool {
 /// ```lean
 /// (h_boundary : offset = 0 ∨ ¬ (texts[doc_id]).get! (offset - 1) |>.isAlphaNum)
 /// ```
-fn tokenize(text: &str) -> Vec<(String, usize)> {
+fn tokenize(
+    text: &str,
+    language: Option<Language>,
+    tokenizer: Tokenizer,
+) -> Vec<(String, usize)> {
+    word_spans_with(text, tokenizer)
+        .into_iter()
+        .filter(|(word, _, _)| !is_stop_word_in(word, language))
+        .map(|(word, start, _)| (word, start))
+        .collect()
+}
+
+/// Byte offsets of every word in `text`, stop words included.
+///
+/// A posting's word position is its offset's index in this list. Stop words
+/// are never indexed but still take a slot, so "state of the art" keeps its
+/// gaps and a phrase query can skip over them without matching "state art".
+/// A code token shares its first part's start, so it shares its position too.
+#[allow(dead_code)] // Build-side only; unused in WASM builds
+pub fn word_starts(text: &str, tokenizer: Tokenizer) -> Vec<usize> {
+    let mut starts: Vec<usize> = word_spans_with(text, tokenizer)
+        .into_iter()
+        .map(|(_, start, _)| start)
+        .collect();
+    starts.dedup();
+    starts
+}
+
+/// [`word_spans`] cut by `tokenizer`: the code tokenizer also keeps whole
+/// identifiers (see [`tokenizer`](super::tokenizer)).
+pub fn word_spans_with(text: &str, tokenizer: Tokenizer) -> Vec<(String, usize, usize)> {
+    match tokenizer {
+        Tokenizer::Text => word_spans(text),
+        Tokenizer::Code => code_spans(text),
+    }
+}
+
+/// Split text into `(normalized word, start, end)` byte ranges, stop words
+/// included.
+///
+/// This is the indexer's word splitter, so snippets use it too: a word found
+/// in stored text is a word the index saw. Chinese, Japanese, Korean and Thai
+/// come out as overlapping bigrams (see [`segment`](super::segment)), so
+/// their ranges overlap, but starts still only go up.
+pub fn word_spans(text: &str) -> Vec<(String, usize, usize)> {
     let mut tokens = Vec::new();
     let chars: Vec<char> = text.chars().collect();
     let mut i = 0;
@@ -146,13 +245,14 @@ fn tokenize(text: &str) -> Vec<(String, usize)> {
             i += 1;
         }
 
-        // Extract and normalize word
+        // Extract the word, cut runs of unspaced scripts into bigrams, and
+        // normalize each piece
         let word: String = chars[word_char_start..i].iter().collect();
-        let normalized = normalize(&word);
-
-        // Skip empty words and stop words
-        if !normalized.is_empty() && !is_stop_word(&normalized) {
-            tokens.push((normalized, word_start));
+        for (start, end) in segment(&word) {
+            let normalized = normalize(&word[start..end]);
+            if !normalized.is_empty() {
+                tokens.push((normalized, word_start + start, word_start + end));
+            }
         }
     }
 
@@ -184,7 +284,7 @@ pub fn build_inverted_index(texts: &[String], field_boundaries: &[FieldBoundary]
     // Process each document
     for (doc_id, text) in texts.iter().enumerate() {
         let text_len = text.len();
-        let tokens = tokenize(text);
+        let tokens = tokenize(text, None, Tokenizer::Text);
 
         for (word, offset) in tokens {
             // Determine field type, section_id, and heading_level at this position
@@ -201,6 +301,7 @@ pub fn build_inverted_index(texts: &[String], field_boundaries: &[FieldBoundary]
                 section_id,
                 heading_level,
                 score,
+                compound_part: false,
             };
 
             terms.entry(word).or_default().push(posting);
@@ -267,35 +368,43 @@ fn get_field_info_for_inverted(
 /// 2. **Reduce phase**: Merge per-document posting maps into global index
 ///
 /// For small corpora (<100 docs), use `build_inverted_index` instead.
-#[cfg(feature = "parallel")]
 pub fn build_inverted_index_parallel(
     texts: &[String],
     field_boundaries: &[FieldBoundary],
+) -> InvertedIndex {
+    build_inverted_index_with_languages(texts, field_boundaries, &[], Tokenizer::Text, None, false)
+}
+
+/// [`build_inverted_index_parallel`] with a language per document, which
+/// picks the stop words it drops. Documents past the end of `languages`, or
+/// with `None`, drop every language's stop words. `tokenizer` cuts the words,
+/// `decompounder` adds the parts of compounds in documents in its language
+/// (or without one), and `transliterate` adds the romanized form of words in
+/// Cyrillic, Greek and Devanagari.
+#[cfg(feature = "parallel")]
+pub fn build_inverted_index_with_languages(
+    texts: &[String],
+    field_boundaries: &[FieldBoundary],
+    languages: &[Option<Language>],
+    tokenizer: Tokenizer,
+    decompounder: Option<&Decompounder>,
+    transliterate: bool,
 ) -> InvertedIndex {
     // MAP PHASE: Parallel tokenization with precomputed scores
     let per_doc_terms: Vec<HashMap<String, Vec<Posting>>> = texts
         .par_iter()
         .enumerate()
         .map(|(doc_id, text)| {
-            let text_len = text.len();
-            let mut doc_terms: HashMap<String, Vec<Posting>> = HashMap::new();
-            for (word, offset) in tokenize(text) {
-                let (field_type, section_id, heading_level) =
-                    get_field_info_for_inverted(doc_id, offset, field_boundaries);
-
-                // INVARIANT: SCORE_PRECOMPUTED - compute score at index time
-                let score = final_score(&field_type, offset, text_len);
-
-                doc_terms.entry(word).or_default().push(Posting {
-                    doc_id,
-                    offset,
-                    field_type,
-                    section_id,
-                    heading_level,
-                    score,
-                });
-            }
-            doc_terms
+            let language = languages.get(doc_id).copied().flatten();
+            doc_terms(
+                doc_id,
+                text,
+                language,
+                field_boundaries,
+                tokenizer,
+                decompounder,
+                transliterate,
+            )
         })
         .collect();
 
@@ -344,35 +453,86 @@ pub fn build_inverted_index_parallel(
     }
 }
 
+/// One document's postings, by term: its words, their romanized forms, then
+/// the parts of its compounds at [`COMPOUND_PART_SCORE`] of a word's score.
+fn doc_terms(
+    doc_id: usize,
+    text: &str,
+    language: Option<Language>,
+    field_boundaries: &[FieldBoundary],
+    tokenizer: Tokenizer,
+    decompounder: Option<&Decompounder>,
+    transliterate: bool,
+) -> HashMap<String, Vec<Posting>> {
+    let text_len = text.len();
+    let mut doc_terms: HashMap<String, Vec<Posting>> = HashMap::new();
+    let mut add = |word: String, offset: usize, compound_part: bool| {
+        let (field_type, section_id, heading_level) =
+            get_field_info_for_inverted(doc_id, offset, field_boundaries);
+
+        // INVARIANT: SCORE_PRECOMPUTED - compute score at index time
+        let mut score = final_score(&field_type, offset, text_len);
+        if compound_part {
+            score *= COMPOUND_PART_SCORE;
+        }
+
+        doc_terms.entry(word).or_default().push(Posting {
+            doc_id,
+            offset,
+            field_type,
+            section_id,
+            heading_level,
+            score,
+            compound_part,
+        });
+    };
+
+    for (word, offset) in tokenize(text, language, tokenizer) {
+        add(word, offset, false);
+    }
+    if transliterate {
+        for (word, offset, _) in romanized_spans(text) {
+            if !is_stop_word_in(&word, language) {
+                add(word, offset, false);
+            }
+        }
+    }
+    let decompounder =
+        decompounder.filter(|d| language.is_none_or(|language| language == d.language()));
+    if let Some(decompounder) = decompounder {
+        for (_, start, end) in word_spans_with(text, tokenizer) {
+            for (part, part_start, _) in decompounder.parts(&text[start..end]) {
+                add(part, start + part_start, true);
+            }
+        }
+    }
+    doc_terms
+}
+
 /// Sequential version for non-parallel builds (WASM).
 #[cfg(not(feature = "parallel"))]
-pub fn build_inverted_index_parallel(
+pub fn build_inverted_index_with_languages(
     texts: &[String],
     field_boundaries: &[FieldBoundary],
+    languages: &[Option<Language>],
+    tokenizer: Tokenizer,
+    decompounder: Option<&Decompounder>,
+    transliterate: bool,
 ) -> InvertedIndex {
     let per_doc_terms: Vec<HashMap<String, Vec<Posting>>> = texts
         .iter()
         .enumerate()
         .map(|(doc_id, text)| {
-            let text_len = text.len();
-            let mut doc_terms: HashMap<String, Vec<Posting>> = HashMap::new();
-            for (word, offset) in tokenize(text) {
-                let (field_type, section_id, heading_level) =
-                    get_field_info_for_inverted(doc_id, offset, field_boundaries);
-
-                // INVARIANT: SCORE_PRECOMPUTED - compute score at index time
-                let score = final_score(&field_type, offset, text_len);
-
-                doc_terms.entry(word).or_default().push(Posting {
-                    doc_id,
-                    offset,
-                    field_type,
-                    section_id,
-                    heading_level,
-                    score,
-                });
-            }
-            doc_terms
+            let language = languages.get(doc_id).copied().flatten();
+            doc_terms(
+                doc_id,
+                text,
+                language,
+                field_boundaries,
+                tokenizer,
+                decompounder,
+                transliterate,
+            )
         })
         .collect();
 
@@ -590,7 +750,7 @@ mod tests {
 
     #[test]
     fn test_tokenize_simple() {
-        let tokens = tokenize("hello world");
+        let tokens = tokenize("hello world", None, Tokenizer::Text);
         assert_eq!(tokens.len(), 2);
         assert_eq!(tokens[0].0, "hello");
         assert_eq!(tokens[0].1, 0);
@@ -600,7 +760,7 @@ mod tests {
 
     #[test]
     fn test_tokenize_with_punctuation() {
-        let tokens = tokenize("hello, world!");
+        let tokens = tokenize("hello, world!", None, Tokenizer::Text);
         assert_eq!(tokens.len(), 2);
         assert_eq!(tokens[0].0, "hello");
         assert_eq!(tokens[1].0, "world");
@@ -608,11 +768,82 @@ mod tests {
 
     #[test]
     fn test_tokenize_normalizes() {
-        let tokens = tokenize("Hello WORLD");
+        let tokens = tokenize("Hello WORLD", None, Tokenizer::Text);
         assert_eq!(tokens[0].0, "hello");
         assert_eq!(tokens[1].0, "world");
     }
 
+    #[test]
+    fn test_word_starts_count_stop_words() {
+        let text = "state of the art";
+        assert_eq!(word_starts(text, Tokenizer::Text), vec![0, 6, 9, 13]);
+        // tokenize drops "of" and "the", but offsets still land on word_starts
+        let starts = word_starts(text, Tokenizer::Text);
+        for (_, offset) in tokenize(text, None, Tokenizer::Text) {
+            assert!(starts.binary_search(&offset).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_stop_words_per_language() {
+        // "die" is a German article but an English word; "also" the reverse
+        assert!(is_stop_word("die") && is_stop_word("also"));
+        assert!(!is_stop_word_in("die", Some(Language::English)));
+        assert!(is_stop_word_in("die", Some(Language::German)));
+        assert!(is_stop_word_in("also", Some(Language::English)));
+        assert!(!is_stop_word_in("also", Some(Language::German)));
+        assert!(is_stop_word_in("die", None));
+        // Both scripts of a language share its set
+        assert!(is_stop_word_in("na", Some(Language::Russian)));
+        assert!(is_stop_word_in("на", Some(Language::Russian)));
+
+        let texts = vec![
+            "die hard also".to_string(),
+            "die katze also".to_string(),
+            "die sonne also".to_string(),
+        ];
+        let languages = [Some(Language::English), Some(Language::German)];
+        let index = build_inverted_index_with_languages(&texts, &[], &languages, Tokenizer::Text, None, false);
+        let docs = |term: &str| -> Vec<usize> {
+            index.terms.get(term).map_or_else(Vec::new, |pl| {
+                pl.postings.iter().map(|p| p.doc_id).collect()
+            })
+        };
+        assert_eq!(docs("die"), vec![0]);
+        assert_eq!(docs("also"), vec![1]);
+        assert_eq!(docs("sonne"), vec![2]);
+    }
+
+    #[test]
+    fn test_compound_parts_get_postings() {
+        let texts = vec![
+            "Der Krankenversicherungsbeitrag steigt".to_string(),
+            "Die Versicherung zahlt".to_string(),
+        ];
+        let words = ["kranken", "versicherung", "beitrag"].map(String::from);
+        let german = Decompounder::new(Language::German, words).unwrap();
+        let languages = [Some(Language::German), Some(Language::German)];
+        let index = build_inverted_index_with_languages(
+            &texts,
+            &[],
+            &languages,
+            Tokenizer::Text,
+            Some(&german),
+            false,
+        );
+
+        // The part is posted at its offset inside the compound, below the
+        // document using the word on its own
+        let postings = &index.terms["versicherung"].postings;
+        assert_eq!(postings.len(), 2);
+        assert_eq!((postings[0].doc_id, postings[0].compound_part), (1, false));
+        assert_eq!((postings[1].doc_id, postings[1].compound_part), (0, true));
+        assert_eq!(postings[1].offset, "Der Kranken".len());
+        assert!(postings[1].score < postings[0].score);
+        // The compound itself is still a term
+        assert!(index.terms.contains_key("krankenversicherungsbeitrag"));
+    }
+
     #[test]
     fn test_build_inverted_index_simple() {
         let texts = vec!["hello world".to_string(), "world peace".to_string()];
@@ -0,0 +1,171 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Document languages: which stop words to drop and which stemmer to run.
+//!
+//! A document's `lang` picks its analysis. "die" is a German article and an
+//! English verb: German documents drop it as a stop word, English ones index
+//! it. Documents without a language fall back to every language's stop words
+//! at once, which is what every document got before languages existed.
+//!
+//! The languages are the ones `data/stop_words.json` has lists for. Four of
+//! them also have a stemmer (see [`stem`](super::stem)), and the five that
+//! write compounds as one word a decompounder (see
+//! [`decompound`](super::decompound)).
+
+/// A language sorex has stop words for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+    Portuguese,
+    Italian,
+    Dutch,
+    Russian,
+    Polish,
+    Swedish,
+    Norwegian,
+    Danish,
+    Finnish,
+    Turkish,
+    Indonesian,
+    Arabic,
+    Hindi,
+    Chinese,
+    Japanese,
+    Korean,
+}
+
+impl Language {
+    /// Every known language.
+    pub const ALL: [Language; 20] = [
+        Language::English,
+        Language::German,
+        Language::French,
+        Language::Spanish,
+        Language::Portuguese,
+        Language::Italian,
+        Language::Dutch,
+        Language::Russian,
+        Language::Polish,
+        Language::Swedish,
+        Language::Norwegian,
+        Language::Danish,
+        Language::Finnish,
+        Language::Turkish,
+        Language::Indonesian,
+        Language::Arabic,
+        Language::Hindi,
+        Language::Chinese,
+        Language::Japanese,
+        Language::Korean,
+    ];
+
+    /// ISO 639-1 code ("en", "de", "fr", ...).
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::Portuguese => "pt",
+            Language::Italian => "it",
+            Language::Dutch => "nl",
+            Language::Russian => "ru",
+            Language::Polish => "pl",
+            Language::Swedish => "sv",
+            Language::Norwegian => "no",
+            Language::Danish => "da",
+            Language::Finnish => "fi",
+            Language::Turkish => "tr",
+            Language::Indonesian => "id",
+            Language::Arabic => "ar",
+            Language::Hindi => "hi",
+            Language::Chinese => "zh",
+            Language::Japanese => "ja",
+            Language::Korean => "ko",
+        }
+    }
+
+    /// English name, lowercase ("english").
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "english",
+            Language::German => "german",
+            Language::French => "french",
+            Language::Spanish => "spanish",
+            Language::Portuguese => "portuguese",
+            Language::Italian => "italian",
+            Language::Dutch => "dutch",
+            Language::Russian => "russian",
+            Language::Polish => "polish",
+            Language::Swedish => "swedish",
+            Language::Norwegian => "norwegian",
+            Language::Danish => "danish",
+            Language::Finnish => "finnish",
+            Language::Turkish => "turkish",
+            Language::Indonesian => "indonesian",
+            Language::Arabic => "arabic",
+            Language::Hindi => "hindi",
+            Language::Chinese => "chinese",
+            Language::Japanese => "japanese",
+            Language::Korean => "korean",
+        }
+    }
+
+    /// Parse an ISO 639-1 code or English name, ignoring case. Region
+    /// subtags are ignored too, so "en-US" is English. Bokmål and Nynorsk
+    /// ("nb", "nn") are Norwegian.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        let primary = value.split(['-', '_']).next().unwrap_or_default();
+        if matches!(primary, "nb" | "nn") {
+            return Some(Language::Norwegian);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|lang| lang.code() == primary || lang.name() == value)
+    }
+
+    /// True if [`stem`](super::stem::stem) has a stemmer for this language.
+    pub fn has_stemmer(self) -> bool {
+        matches!(
+            self,
+            Language::English | Language::German | Language::French | Language::Spanish
+        )
+    }
+
+    /// True if this language writes compounds as one word, so it has a
+    /// [`Decompounder`](super::decompound::Decompounder).
+    pub fn has_decompounder(self) -> bool {
+        matches!(
+            self,
+            Language::German
+                | Language::Dutch
+                | Language::Swedish
+                | Language::Norwegian
+                | Language::Danish
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Language::parse("en"), Some(Language::English));
+        assert_eq!(Language::parse("German"), Some(Language::German));
+        assert_eq!(Language::parse("fr-CA"), Some(Language::French));
+        assert_eq!(Language::parse("es_MX"), Some(Language::Spanish));
+        assert_eq!(Language::parse("nb-NO"), Some(Language::Norwegian));
+        assert_eq!(Language::parse("klingon"), None);
+        for language in Language::ALL {
+            assert_eq!(Language::parse(language.code()), Some(language));
+            assert_eq!(Language::parse(language.name()), Some(language));
+        }
+    }
+}
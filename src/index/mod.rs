@@ -11,8 +11,15 @@
 mod suffix_array;
 mod sais;
 mod inverted;
+pub mod decompound;
 pub mod fst;
 pub mod hybrid;
+pub mod language;
+pub mod segment;
+pub mod stem;
+pub mod synonyms;
+pub mod tokenizer;
+pub mod translit;
 
 pub use suffix_array::*;
 pub use sais::*;
@@ -0,0 +1,234 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Script-aware segmentation for languages written without spaces.
+//!
+//! Chinese, Japanese and Thai run words together, and Korean puts whole
+//! phrases between spaces. Splitting on non-letters leaves a sentence as one
+//! "word" that no query ever equals, so exact search never fires and only the
+//! suffix array finds anything.
+//!
+//! Instead of a dictionary, runs of these scripts are cut into overlapping
+//! bigrams, the same trick as Lucene's CJK analyzer: "東京都庁" indexes as
+//! "東京", "京都", "都庁". A query is cut the same way, so "東京都" becomes
+//! "東京" AND "京都" and finds the document by exact term lookup. A run of
+//! one unit is kept whole, so "猫" between two spaces is still a term.
+//!
+//! - **Han, Kana, Hangul**: one unit per character.
+//! - **Thai**: one unit per character cluster, a consonant with the vowels
+//!   and tone marks written around it ("ที่" is one cluster, not three
+//!   characters). Thai has leading vowels written before the consonant they
+//!   follow in speech ("เก" is e+k), so a cluster starts at a leading vowel
+//!   or at a consonant that doesn't follow one, and marks never start one.
+//!
+//! Cluster boundaries only look at the character before, so a word cuts the
+//! same on its own as inside a sentence, which is what lets a query's
+//! bigrams line up with the document's.
+//!
+//! Everything else (Latin, Cyrillic, digits, ...) passes through as one unit.
+//!
+//! # References
+//!
+//! - **CJK bigrams**: Lucene's `CJKBigramFilter` indexes overlapping character
+//!   pairs for Han, Hiragana, Katakana and Hangul.
+//! - **Thai clusters**: Theeramunkong et al., "Character Cluster Based Thai
+//!   Information Retrieval" (IRAL 2000) define the inseparable clusters used
+//!   here, loosely.
+
+/// How a character is segmented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    /// Han, Kana, Hangul: one unit per character
+    Cjk,
+    /// Thai: one unit per character cluster
+    Thai,
+    /// Everything else: the whole run is one unit
+    Other,
+}
+
+fn script(c: char) -> Script {
+    if is_cjk(c) {
+        Script::Cjk
+    } else if is_thai(c) {
+        Script::Thai
+    } else {
+        Script::Other
+    }
+}
+
+/// Han ideographs, Hiragana, Katakana and Hangul.
+pub fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}' |   // Hangul Jamo
+        '\u{3005}'..='\u{3007}' |   // 々 〆 〇
+        '\u{3040}'..='\u{30FF}' |   // Hiragana, Katakana
+        '\u{3130}'..='\u{318F}' |   // Hangul Compatibility Jamo
+        '\u{31F0}'..='\u{31FF}' |   // Katakana Phonetic Extensions
+        '\u{3400}'..='\u{4DBF}' |   // CJK Extension A
+        '\u{4E00}'..='\u{9FFF}' |   // CJK Unified Ideographs
+        '\u{A960}'..='\u{A97F}' |   // Hangul Jamo Extended-A
+        '\u{AC00}'..='\u{D7FF}' |   // Hangul Syllables, Jamo Extended-B
+        '\u{F900}'..='\u{FAFF}' |   // CJK Compatibility Ideographs
+        '\u{FF66}'..='\u{FFDC}' |   // Halfwidth Katakana and Hangul
+        '\u{20000}'..='\u{3FFFF}'   // CJK Extensions B and up
+    )
+}
+
+/// Thai letters, vowels and tone marks (not digits or punctuation).
+///
+/// Tone marks aren't alphanumeric, so the word splitter has to ask this too:
+/// otherwise "ที่" would break at its tone mark.
+pub fn is_thai(c: char) -> bool {
+    matches!(c, '\u{0E01}'..='\u{0E3A}' | '\u{0E40}'..='\u{0E4E}')
+}
+
+fn is_thai_consonant(c: char) -> bool {
+    matches!(c, '\u{0E01}'..='\u{0E2E}')
+}
+
+/// เ แ โ ใ ไ: written before the consonant they're pronounced after.
+fn is_thai_leading_vowel(c: char) -> bool {
+    matches!(c, '\u{0E40}'..='\u{0E44}')
+}
+
+/// Does a new Thai cluster start at `c`?
+fn starts_cluster(prev: Option<char>, c: char) -> bool {
+    if is_thai_leading_vowel(c) {
+        return true;
+    }
+    is_thai_consonant(c) && !prev.is_some_and(is_thai_leading_vowel)
+}
+
+/// Split one word (a run of word characters) into the byte ranges the index
+/// stores as terms.
+///
+/// Latin and friends come back whole. Runs of Han, Kana, Hangul or Thai come
+/// back as overlapping bigrams of their units:
+///
+/// ```
+/// use sorex::segment;
+///
+/// assert_eq!(segment("rust"), vec![(0, 4)]);
+/// // "東京都": "東京", "京都"
+/// assert_eq!(segment("東京都"), vec![(0, 6), (3, 9)]);
+/// ```
+pub fn segment(word: &str) -> Vec<(usize, usize)> {
+    if word.is_ascii() {
+        return vec![(0, word.len())];
+    }
+
+    let mut spans = Vec::new();
+    // Units of the current run, and the run's script
+    let mut units: Vec<(usize, usize)> = Vec::new();
+    let mut run = None;
+    let mut prev = None;
+
+    for (i, c) in word.char_indices() {
+        let end = i + c.len_utf8();
+        let script = script(c);
+        if run != Some(script) {
+            flush(&mut units, run, &mut spans);
+            run = Some(script);
+        }
+        let starts_unit = match script {
+            Script::Cjk => true,
+            Script::Thai => units.is_empty() || starts_cluster(prev, c),
+            Script::Other => units.is_empty(),
+        };
+        match units.last_mut() {
+            Some(unit) if !starts_unit => unit.1 = end,
+            _ => units.push((i, end)),
+        }
+        prev = Some(c);
+    }
+    flush(&mut units, run, &mut spans);
+    spans
+}
+
+/// Emit a finished run's units: whole, or as overlapping bigrams.
+fn flush(units: &mut Vec<(usize, usize)>, run: Option<Script>, spans: &mut Vec<(usize, usize)>) {
+    match run {
+        Some(Script::Cjk | Script::Thai) if units.len() > 1 => {
+            spans.extend(units.windows(2).map(|pair| (pair[0].0, pair[1].1)));
+        }
+        _ => spans.append(units),
+    }
+    units.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::word_spans;
+    use crate::search::tiered::TierSearcher;
+    use crate::testing::{build_layer, load_searcher};
+
+    fn words(word: &str) -> Vec<&str> {
+        segment(word)
+            .into_iter()
+            .map(|(s, e)| &word[s..e])
+            .collect()
+    }
+
+    #[test]
+    fn test_cjk_bigrams() {
+        assert_eq!(words("東京都庁"), vec!["東京", "京都", "都庁"]);
+        assert_eq!(
+            words("検索エンジン"),
+            vec!["検索", "索エ", "エン", "ンジ", "ジン"]
+        );
+        assert_eq!(words("한국어"), vec!["한국", "국어"]);
+        assert_eq!(words("猫"), vec!["猫"]);
+    }
+
+    #[test]
+    fn test_mixed_scripts_split_at_the_change() {
+        assert_eq!(words("rust语言"), vec!["rust", "语言"]);
+        assert_eq!(words("iPhone手机壳"), vec!["iPhone", "手机", "机壳"]);
+        assert_eq!(words("café"), vec!["café"]);
+    }
+
+    #[test]
+    fn test_thai_clusters() {
+        // กิน = กิ + น; ข้าว = ข้า + ว
+        assert_eq!(words("กินข้าว"), vec!["กิน", "นข้า", "ข้าว"]);
+        // The leading vowel เ stays with its consonant
+        assert_eq!(words("เรา"), vec!["เรา"]);
+        // A tone mark never starts a cluster
+        assert_eq!(words("ที่"), vec!["ที่"]);
+    }
+
+    #[test]
+    fn test_a_word_cuts_the_same_inside_a_sentence() {
+        let sentence = "ฉันกินข้าวที่บ้าน";
+        let sentence_words = words(sentence);
+        for word in ["กินข้าว", "บ้าน", "ข้าวที่"] {
+            for bigram in words(word) {
+                assert!(sentence_words.contains(&bigram), "{bigram} from {word}");
+            }
+        }
+    }
+
+    /// A searcher over `texts`, one doc each, with word positions.
+    fn searcher(texts: &[&str]) -> TierSearcher {
+        let docs: Vec<(&str, u8)> = texts.iter().map(|&t| (t, 5)).collect();
+        load_searcher(build_layer(&docs, true, &[], word_spans).0)
+    }
+
+    #[test]
+    fn test_unspaced_scripts_match_by_bigram() {
+        let searcher = searcher(&["东京都厅的展望台", "我去了京都，然后去了东京"]);
+        let ids = |query: &str| -> Vec<usize> {
+            let mut ids: Vec<usize> = searcher
+                .search(query, 10)
+                .iter()
+                .map(|r| r.doc_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+        // Both docs have "东京" and "京都", only one side by side
+        assert_eq!(ids("东京都"), vec![0, 1]);
+        assert_eq!(ids("\"东京都\""), vec![0]);
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! English: the Porter2 ("English") Snowball stemmer.
+
+use super::{longest_suffix, region_after, replace_suffix};
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u' | b'y')
+}
+
+/// Irregular words and their stems, checked before any rule.
+const EXCEPTIONS: &[(&str, &str)] = &[
+    ("skis", "ski"),
+    ("skies", "sky"),
+    ("dying", "die"),
+    ("lying", "lie"),
+    ("tying", "tie"),
+    ("idly", "idl"),
+    ("gently", "gentl"),
+    ("ugly", "ugli"),
+    ("early", "earli"),
+    ("only", "onli"),
+    ("singly", "singl"),
+    ("sky", "sky"),
+    ("news", "news"),
+    ("howe", "howe"),
+    ("atlas", "atlas"),
+    ("cosmos", "cosmos"),
+    ("bias", "bias"),
+    ("andes", "andes"),
+];
+
+/// Words left alone once a plural `s` is gone.
+const INVARIANT: &[&str] = &[
+    "inning", "outing", "canning", "herring", "earring", "proceed", "exceed", "succeed",
+];
+
+/// Step 2 endings (in R1) and their replacements.
+const STEP2: &[(&str, &str)] = &[
+    ("tional", "tion"),
+    ("enci", "ence"),
+    ("anci", "ance"),
+    ("abli", "able"),
+    ("entli", "ent"),
+    ("izer", "ize"),
+    ("ization", "ize"),
+    ("ational", "ate"),
+    ("ation", "ate"),
+    ("ator", "ate"),
+    ("alism", "al"),
+    ("aliti", "al"),
+    ("alli", "al"),
+    ("fulness", "ful"),
+    ("ousli", "ous"),
+    ("ousness", "ous"),
+    ("iveness", "ive"),
+    ("iviti", "ive"),
+    ("biliti", "ble"),
+    ("bli", "ble"),
+    ("ogi", "og"),
+    ("fulli", "ful"),
+    ("lessli", "less"),
+    ("li", ""),
+];
+
+/// Step 3 endings (in R1) and their replacements.
+const STEP3: &[(&str, &str)] = &[
+    ("tional", "tion"),
+    ("ational", "ate"),
+    ("alize", "al"),
+    ("icate", "ic"),
+    ("iciti", "ic"),
+    ("ical", "ic"),
+    ("ful", ""),
+    ("ness", ""),
+    ("ative", ""),
+];
+
+/// Step 4 endings, deleted in R2.
+const STEP4: &[&str] = &[
+    "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ism", "ate",
+    "iti", "ous", "ive", "ize", "ion",
+];
+
+pub(super) fn stem(w: &mut Vec<u8>) {
+    if w.len() <= 2 {
+        return;
+    }
+    if let Some((_, to)) = EXCEPTIONS.iter().find(|(from, _)| w == from.as_bytes()) {
+        *w = to.as_bytes().to_vec();
+        return;
+    }
+
+    // A y that starts the word or follows a vowel is a consonant
+    if w[0] == b'y' {
+        w[0] = b'Y';
+    }
+    for i in 1..w.len() {
+        if w[i] == b'y' && is_vowel(w[i - 1]) {
+            w[i] = b'Y';
+        }
+    }
+
+    let r1 = ["gener", "commun", "arsen"]
+        .iter()
+        .find(|p| w.starts_with(p.as_bytes()))
+        .map_or_else(|| region_after(w, 0, is_vowel), |p| p.len());
+    let r2 = region_after(w, r1, is_vowel);
+
+    step1a(w);
+    if INVARIANT.iter().any(|word| w == word.as_bytes()) {
+        return;
+    }
+    step1b(w, r1);
+    step1c(w);
+    replace_in_r1(w, r1, r2, STEP2);
+    replace_in_r1(w, r1, r2, STEP3);
+    step4(w, r2);
+    step5(w, r1, r2);
+}
+
+/// Plurals: "caresses" → "caress", "ponies" → "poni", "cats" → "cat".
+fn step1a(w: &mut Vec<u8>) {
+    match longest_suffix(w, &["sses", "ied", "ies", "us", "ss", "s"]) {
+        Some("sses") => w.truncate(w.len() - 2),
+        // "cries" → "cri", but "ties" → "tie"
+        Some("ied" | "ies") => w.truncate(w.len() - if w.len() > 4 { 2 } else { 1 }),
+        // Only if a vowel comes before the letter before the s: "gas" stays
+        Some("s") if w[..w.len() - 2].iter().any(|&c| is_vowel(c)) => w.truncate(w.len() - 1),
+        _ => {}
+    }
+}
+
+/// Past tenses and gerunds: "hoped" → "hope", "running" → "run".
+fn step1b(w: &mut Vec<u8>, r1: usize) {
+    let Some(suffix) = longest_suffix(w, &["eed", "eedly", "ed", "edly", "ing", "ingly"]) else {
+        return;
+    };
+    let stem_len = w.len() - suffix.len();
+    if suffix.starts_with("eed") {
+        if stem_len >= r1 {
+            replace_suffix(w, suffix.len(), "ee");
+        }
+        return;
+    }
+    if !w[..stem_len].iter().any(|&c| is_vowel(c)) {
+        return;
+    }
+    w.truncate(stem_len);
+    if w.ends_with(b"at") || w.ends_with(b"bl") || w.ends_with(b"iz") {
+        w.push(b'e');
+    } else if ends_double(w) {
+        w.pop();
+    } else if r1 >= w.len() && ends_short_syllable(w) {
+        w.push(b'e');
+    }
+}
+
+/// A final y after a consonant: "happy" → "happi", but "by" stays.
+fn step1c(w: &mut [u8]) {
+    let n = w.len();
+    if n > 2 && matches!(w[n - 1], b'y' | b'Y') && !is_vowel(w[n - 2]) {
+        w[n - 1] = b'i';
+    }
+}
+
+/// Steps 2 and 3: swap a derivational ending in R1 for its base.
+fn replace_in_r1(w: &mut Vec<u8>, r1: usize, r2: usize, rules: &[(&str, &str)]) {
+    let suffixes: Vec<&str> = rules.iter().map(|(s, _)| *s).collect();
+    let Some(suffix) = longest_suffix(w, &suffixes) else {
+        return;
+    };
+    let stem_len = w.len() - suffix.len();
+    let replacement = rules.iter().find(|(s, _)| *s == suffix).map_or("", |r| r.1);
+    let allowed = stem_len >= r1
+        && match suffix {
+            "ogi" => w[..stem_len].ends_with(b"l"),
+            "li" => stem_len > 0 && b"cdeghkmnrt".contains(&w[stem_len - 1]),
+            "ative" => stem_len >= r2,
+            _ => true,
+        };
+    if allowed {
+        replace_suffix(w, suffix.len(), replacement);
+    }
+}
+
+/// Suffixes in R2: "adjustment" → "adjust".
+fn step4(w: &mut Vec<u8>, r2: usize) {
+    let Some(suffix) = longest_suffix(w, STEP4) else {
+        return;
+    };
+    let stem_len = w.len() - suffix.len();
+    let allowed = stem_len >= r2
+        && (suffix != "ion" || stem_len > 0 && matches!(w[stem_len - 1], b's' | b't'));
+    if allowed {
+        w.truncate(stem_len);
+    }
+}
+
+/// A final e or doubled l: "hope" stays, "probate" → "probat", "controll" →
+/// "control".
+fn step5(w: &mut Vec<u8>, r1: usize, r2: usize) {
+    let stem_len = w.len() - 1;
+    if w.ends_with(b"e")
+        && (stem_len >= r2 || stem_len >= r1 && !ends_short_syllable(&w[..stem_len]))
+        || w.ends_with(b"ll") && stem_len >= r2
+    {
+        w.truncate(stem_len);
+    }
+}
+
+fn ends_double(w: &[u8]) -> bool {
+    let n = w.len();
+    n >= 2 && w[n - 1] == w[n - 2] && b"bdfgmnprt".contains(&w[n - 1])
+}
+
+/// A vowel, then a consonant other than w, x or Y, after a consonant ("hop")
+/// or at the start of a two-letter word ("at").
+fn ends_short_syllable(w: &[u8]) -> bool {
+    match w.len() {
+        0 | 1 => false,
+        2 => is_vowel(w[0]) && !is_vowel(w[1]),
+        n => {
+            !is_vowel(w[n - 3])
+                && is_vowel(w[n - 2])
+                && !is_vowel(w[n - 1])
+                && !matches!(w[n - 1], b'w' | b'x' | b'Y')
+        }
+    }
+}
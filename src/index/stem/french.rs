@@ -0,0 +1,279 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! French: the Snowball French stemmer, on folded words ("é" reads as "e",
+//! so "ité" is "ite" and a past participle's "é" is a plain final "e").
+
+use super::{longest_suffix, region_after, replace_suffix};
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u' | b'y')
+}
+
+/// Verb endings starting with i, deleted after a consonant in RV.
+const I_VERB_ENDINGS: &[&str] = &[
+    "imes", "it", "ites", "i", "ie", "ies", "ir", "ira", "irai", "iraIent", "irais", "irait",
+    "iras", "irent", "irez", "iriez", "irions", "irons", "iront", "is", "issaIent", "issais",
+    "issait", "issant", "issante", "issantes", "issants", "isse", "issent", "isses", "issez",
+    "issiez", "issions", "issons",
+];
+
+/// Other verb endings, deleted in RV.
+const E_VERB_ENDINGS: &[&str] = &[
+    "e", "ee", "ees", "es", "erent", "er", "era", "erai", "eraIent", "erais", "erait", "eras",
+    "erez", "eriez", "erions", "erons", "eront", "ez", "iez",
+];
+
+/// Verb endings starting with a, deleted in RV along with an e before them.
+const A_VERB_ENDINGS: &[&str] = &[
+    "ames", "at", "ates", "a", "ai", "aIent", "ais", "ait", "ant", "ante", "antes", "ants", "as",
+    "asse", "assent", "asses", "assiez", "assions",
+];
+
+/// Step 1 suffixes deleted in R2 with no follow-up.
+const PLAIN: &[&str] = &[
+    "ance", "iqUe", "isme", "able", "iste", "eux", "ances", "iqUes", "ismes", "ables", "istes",
+];
+
+/// Step 1 suffixes with their own rules.
+const SPECIAL: &[&str] = &[
+    "atrice",
+    "ateur",
+    "ation",
+    "atrices",
+    "ateurs",
+    "ations",
+    "logie",
+    "logies",
+    "usion",
+    "ution",
+    "usions",
+    "utions",
+    "ence",
+    "ences",
+    "ement",
+    "ements",
+    "ite",
+    "ites",
+    "if",
+    "ive",
+    "ifs",
+    "ives",
+    "eaux",
+    "aux",
+    "euse",
+    "euses",
+    "issement",
+    "issements",
+    "amment",
+    "emment",
+    "ment",
+    "ments",
+];
+
+/// What step 1 did, which decides the steps after it.
+#[derive(PartialEq, Eq)]
+enum Step1 {
+    /// No suffix removed
+    Nothing,
+    /// A suffix removed
+    Removed,
+    /// An adverb's "ment" removed: what's left may still be a verb
+    Adverb,
+}
+
+/// RV: after the third letter if the word starts with two vowels or one of
+/// "par", "col", "tap"; otherwise after the first vowel past the first letter.
+fn rv(w: &[u8]) -> usize {
+    let n = w.len();
+    if n >= 2 && is_vowel(w[0]) && is_vowel(w[1])
+        || [b"par", b"col", b"tap"].iter().any(|p| w.starts_with(*p))
+    {
+        return n.min(3);
+    }
+    (1..n).find(|&i| is_vowel(w[i])).map_or(n, |i| i + 1)
+}
+
+pub(super) fn stem(w: &mut Vec<u8>) {
+    // Vowels acting as consonants: u or i between vowels, y next to a
+    // vowel, u after q
+    for i in 0..w.len() {
+        let prev = i.checked_sub(1).map(|p| w[p]);
+        let next = w.get(i + 1).copied();
+        let prev_vowel = prev.is_some_and(is_vowel);
+        let next_vowel = next.is_some_and(is_vowel);
+        let consonant = match w[i] {
+            b'u' | b'i' => prev_vowel && next_vowel || w[i] == b'u' && prev == Some(b'q'),
+            b'y' => prev_vowel || next_vowel,
+            _ => false,
+        };
+        if consonant {
+            w[i] = w[i].to_ascii_uppercase();
+        }
+    }
+
+    let rv = rv(w);
+    let r1 = region_after(w, 0, is_vowel);
+    let r2 = region_after(w, r1, is_vowel);
+
+    let before = w.clone();
+    let step1 = standard_suffix(w, rv, r1, r2);
+    let verb_removed = step1 != Step1::Removed && (i_verb_suffix(w, rv) || verb_suffix(w, rv, r2));
+
+    if step1 == Step1::Nothing && !verb_removed {
+        residual_suffix(w, rv, r2);
+    } else if *w != before {
+        // A consonant y at the end is a vowel again
+        if w.last() == Some(&b'Y') {
+            w.pop();
+            w.push(b'i');
+        }
+    }
+
+    // Undouble: "anciennes" → "ancien"
+    if longest_suffix(w, &["enn", "onn", "ett", "ell", "eill"]).is_some() {
+        w.pop();
+    }
+}
+
+/// Step 1: derivational suffixes.
+fn standard_suffix(w: &mut Vec<u8>, rv: usize, r1: usize, r2: usize) -> Step1 {
+    let all: Vec<&str> = PLAIN.iter().chain(SPECIAL).copied().collect();
+    let Some(suffix) = longest_suffix(w, &all) else {
+        return Step1::Nothing;
+    };
+    let stem_len = w.len() - suffix.len();
+    let (in_rv, in_r1, in_r2) = (stem_len >= rv, stem_len >= r1, stem_len >= r2);
+    // Delete `before` if it ends the word within R2, else replace it with
+    // `or` (if any). Returns whether `before` was there.
+    let follow = |w: &mut Vec<u8>, before: &str, or: Option<&str>| {
+        if !w.ends_with(before.as_bytes()) {
+            return false;
+        }
+        if w.len() - before.len() >= r2 {
+            w.truncate(w.len() - before.len());
+        } else if let Some(or) = or {
+            replace_suffix(w, before.len(), or);
+        }
+        true
+    };
+
+    match suffix {
+        "atrice" | "ateur" | "ation" | "atrices" | "ateurs" | "ations" if in_r2 => {
+            w.truncate(stem_len);
+            follow(w, "ic", Some("iqU"));
+        }
+        "logie" | "logies" if in_r2 => replace_suffix(w, suffix.len(), "log"),
+        "usion" | "ution" | "usions" | "utions" if in_r2 => replace_suffix(w, suffix.len(), "u"),
+        "ence" | "ences" if in_r2 => replace_suffix(w, suffix.len(), "ent"),
+        "ement" | "ements" if in_rv => {
+            w.truncate(stem_len);
+            if w.ends_with(b"iv") && w.len() - 2 >= r2 {
+                w.truncate(w.len() - 2);
+                follow(w, "at", None);
+            } else if w.ends_with(b"eus") {
+                if w.len() - 3 >= r2 {
+                    w.truncate(w.len() - 3);
+                } else if w.len() - 3 >= r1 {
+                    replace_suffix(w, 3, "eux");
+                }
+            } else if !(follow(w, "abl", None) || follow(w, "iqU", None))
+                && (w.ends_with(b"ier") || w.ends_with(b"Ier"))
+                && w.len() - 3 >= rv
+            {
+                replace_suffix(w, 3, "i");
+            }
+        }
+        "ite" | "ites" if in_r2 => {
+            w.truncate(stem_len);
+            let _ = follow(w, "abil", Some("abl"))
+                || follow(w, "ic", Some("iqU"))
+                || follow(w, "iv", None);
+        }
+        "if" | "ive" | "ifs" | "ives" if in_r2 => {
+            w.truncate(stem_len);
+            if w.ends_with(b"at") && w.len() - 2 >= r2 {
+                w.truncate(w.len() - 2);
+                follow(w, "ic", Some("iqU"));
+            }
+        }
+        "eaux" => w.truncate(w.len() - 1),
+        "aux" if in_r1 => replace_suffix(w, 3, "al"),
+        "euse" | "euses" if in_r2 => w.truncate(stem_len),
+        "euse" | "euses" if in_r1 => replace_suffix(w, suffix.len(), "eux"),
+        "issement" | "issements" if in_r1 && !is_vowel(w[stem_len - 1]) => w.truncate(stem_len),
+        "amment" if in_rv => {
+            replace_suffix(w, suffix.len(), "ant");
+            return Step1::Adverb;
+        }
+        "emment" if in_rv => {
+            replace_suffix(w, suffix.len(), "ent");
+            return Step1::Adverb;
+        }
+        "ment" | "ments" if in_rv && stem_len > 0 && is_vowel(w[stem_len - 1]) => {
+            w.truncate(stem_len);
+            return Step1::Adverb;
+        }
+        _ if in_r2 && PLAIN.contains(&suffix) => w.truncate(stem_len),
+        _ => return Step1::Nothing,
+    }
+    Step1::Removed
+}
+
+/// Step 2a: verb endings starting with i ("finissons" → "fin").
+fn i_verb_suffix(w: &mut Vec<u8>, rv: usize) -> bool {
+    let Some(suffix) = longest_suffix(w, I_VERB_ENDINGS) else {
+        return false;
+    };
+    let stem_len = w.len() - suffix.len();
+    let allowed = stem_len > rv && !is_vowel(w[stem_len - 1]);
+    if allowed {
+        w.truncate(stem_len);
+    }
+    allowed
+}
+
+/// Step 2b: other verb endings ("continuer" → "continu").
+fn verb_suffix(w: &mut Vec<u8>, rv: usize, r2: usize) -> bool {
+    let all: Vec<&str> = ["ions"]
+        .iter()
+        .chain(E_VERB_ENDINGS)
+        .chain(A_VERB_ENDINGS)
+        .copied()
+        .collect();
+    let Some(suffix) = longest_suffix(w, &all) else {
+        return false;
+    };
+    let stem_len = w.len() - suffix.len();
+    if stem_len < rv || suffix == "ions" && stem_len < r2 {
+        return false;
+    }
+    w.truncate(stem_len);
+    if A_VERB_ENDINGS.contains(&suffix) && w.ends_with(b"e") && w.len() > rv {
+        w.pop();
+    }
+    true
+}
+
+/// Step 4: a final s or e, and "ion"/"ier" in RV.
+fn residual_suffix(w: &mut Vec<u8>, rv: usize, r2: usize) {
+    if w.ends_with(b"s") && w.len() >= 2 && !b"aious".contains(&w[w.len() - 2]) {
+        w.pop();
+    }
+    let Some(suffix) = longest_suffix(w, &["ion", "ier", "iere", "Ier", "Iere", "e"]) else {
+        return;
+    };
+    let stem_len = w.len() - suffix.len();
+    if stem_len < rv {
+        return;
+    }
+    match suffix {
+        "ion" => {
+            if stem_len >= r2 && stem_len > rv && matches!(w[stem_len - 1], b's' | b't') {
+                w.truncate(stem_len);
+            }
+        }
+        "e" => w.truncate(stem_len),
+        _ => replace_suffix(w, suffix.len(), "i"),
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! German: the Snowball German stemmer, on folded words (umlauts already
+//! read as their base vowel, "ß" as "ss").
+
+use super::{longest_suffix, region_after};
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u' | b'y')
+}
+
+/// Letters a plural or genitive `s` may follow.
+const S_ENDING: &[u8] = b"bdfghklmnrt";
+/// Letters a verb `st` may follow.
+const ST_ENDING: &[u8] = b"bdfghklmnt";
+
+pub(super) fn stem(w: &mut Vec<u8>) {
+    // u and y between vowels are consonants
+    for i in 1..w.len().saturating_sub(1) {
+        if matches!(w[i], b'u' | b'y') && is_vowel(w[i - 1]) && is_vowel(w[i + 1]) {
+            w[i] = w[i].to_ascii_uppercase();
+        }
+    }
+
+    let r1 = region_after(w, 0, is_vowel);
+    let r2 = region_after(w, r1, is_vowel);
+    // At least three letters before R1
+    let r1 = r1.max(3);
+
+    step1(w, r1);
+    step2(w, r1);
+    step3(w, r1, r2);
+}
+
+/// Inflectional endings: "häuser" → "haus".
+fn step1(w: &mut Vec<u8>, r1: usize) {
+    let Some(suffix) = longest_suffix(w, &["em", "ern", "er", "e", "en", "es", "s"]) else {
+        return;
+    };
+    let stem_len = w.len() - suffix.len();
+    if stem_len < r1 {
+        return;
+    }
+    match suffix {
+        "s" => {
+            if stem_len > 0 && S_ENDING.contains(&w[stem_len - 1]) {
+                w.truncate(stem_len);
+            }
+        }
+        "e" | "en" | "es" => {
+            w.truncate(stem_len);
+            // "kenntnisse" → "kenntnis"
+            if w.ends_with(b"niss") {
+                w.pop();
+            }
+        }
+        _ => w.truncate(stem_len),
+    }
+}
+
+/// Adjective and verb endings: "kleinsten" → "klein".
+fn step2(w: &mut Vec<u8>, r1: usize) {
+    let Some(suffix) = longest_suffix(w, &["en", "er", "est", "st"]) else {
+        return;
+    };
+    let stem_len = w.len() - suffix.len();
+    let allowed =
+        stem_len >= r1 && (suffix != "st" || stem_len >= 4 && ST_ENDING.contains(&w[stem_len - 1]));
+    if allowed {
+        w.truncate(stem_len);
+    }
+}
+
+/// Derivational suffixes in R2: "versicherung" → "versicher".
+fn step3(w: &mut Vec<u8>, r1: usize, r2: usize) {
+    let Some(suffix) = longest_suffix(
+        w,
+        &["end", "ung", "ig", "ik", "isch", "lich", "heit", "keit"],
+    ) else {
+        return;
+    };
+    let stem_len = w.len() - suffix.len();
+    if stem_len < r2 {
+        return;
+    }
+    let not_after_e = |w: &[u8], len: usize| len == 0 || w[len - 1] != b'e';
+    match suffix {
+        "end" | "ung" => {
+            w.truncate(stem_len);
+            if w.ends_with(b"ig") && w.len() - 2 >= r2 && not_after_e(w, w.len() - 2) {
+                w.truncate(w.len() - 2);
+            }
+        }
+        "ig" | "ik" | "isch" => {
+            if not_after_e(w, stem_len) {
+                w.truncate(stem_len);
+            }
+        }
+        "lich" | "heit" => {
+            w.truncate(stem_len);
+            if (w.ends_with(b"er") || w.ends_with(b"en")) && w.len() - 2 >= r1 {
+                w.truncate(w.len() - 2);
+            }
+        }
+        _ => {
+            // "keit"
+            w.truncate(stem_len);
+            if let Some(before) = longest_suffix(w, &["lich", "ig"]) {
+                if w.len() - before.len() >= r2 {
+                    w.truncate(w.len() - before.len());
+                }
+            }
+        }
+    }
+}
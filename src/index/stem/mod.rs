@@ -0,0 +1,460 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stemming: "optimize", "optimized" and "optimizing" are one word.
+//!
+//! The tokenizer keeps every inflection as its own vocabulary term, so
+//! without stems they only meet through a prefix or fuzzy match, each with
+//! its penalty. A stemmer strips the inflection ("optimizing" → `optim`) and
+//! the terms sharing a stem become one exact match.
+//!
+//! The stemmers follow the Snowball algorithms (Porter2 for English) for
+//! English, German, French and Spanish. They see words after [`fold`], so
+//! rules that hinge on an accent ("é" vs "e") use the bare letter, and a
+//! handful of suffixes collapse into each other. Words that aren't made of
+//! `a`-`z` pass through unchanged: a stemmer for one alphabet has nothing to
+//! say about another.
+//!
+//! Stems aren't added to the vocabulary. The index stores which terms share
+//! each stem, per language ([`StemIndex`]), and the searcher unions them at
+//! query time, so prefix, fuzzy and completion still see the words as written.
+//!
+//! # Binary Format
+//!
+//! Appended to the LEV_DFA section (after the phonetic keys, if any),
+//! flagged by `FormatFlags::HAS_STEMS`:
+//!
+//! ```text
+//! varint(term_count)
+//! varint(table_count)
+//! For each table:
+//!   u8(language)                       index into TAGGED
+//!   varint(stem_count)
+//!   For each stem (sorted):
+//!     varint(stem_len), stem bytes
+//!     varint(member_count), varint(term_idx delta)...
+//! ```
+//!
+//! [`fold`]: crate::util::normalize::fold
+
+mod english;
+mod french;
+mod german;
+mod spanish;
+
+use crate::binary::{decode_varint, encode_varint};
+use crate::index::language::Language;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+
+/// Languages with a stemmer, in binary-format tag order.
+const TAGGED: [Language; 4] = [
+    Language::English,
+    Language::German,
+    Language::French,
+    Language::Spanish,
+];
+
+/// Byte identifying a stemmed language in the binary format. Tables only
+/// exist for [`TAGGED`] languages; anything else gets a tag decode rejects.
+fn tag(language: Language) -> u8 {
+    TAGGED
+        .iter()
+        .position(|&l| l == language)
+        .unwrap_or(u8::MAX as usize) as u8
+}
+
+fn from_tag(tag: u8) -> Option<Language> {
+    TAGGED.get(tag as usize).copied()
+}
+
+/// Stem of `word` in `language`.
+///
+/// Expects a folded word (lowercase, no diacritics); uppercase ASCII is
+/// lowered. Anything that isn't an ASCII word comes back unchanged, as do
+/// words too short to carry an inflection and words in a language without
+/// a stemmer.
+pub fn stem(word: &str, language: Language) -> String {
+    if !word.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return word.to_string();
+    }
+    let mut w = word.as_bytes().to_ascii_lowercase();
+    match language {
+        Language::English => english::stem(&mut w),
+        Language::German => german::stem(&mut w),
+        Language::French => french::stem(&mut w),
+        Language::Spanish => spanish::stem(&mut w),
+        _ => {}
+    }
+    // The stemmers mark letters acting as consonants in uppercase
+    w.make_ascii_lowercase();
+    String::from_utf8(w).unwrap_or_else(|_| word.to_string())
+}
+
+/// Start of the region after the first non-vowel following a vowel, looking
+/// from `from` (Snowball's R1; R2 is the same thing again from R1).
+fn region_after(w: &[u8], from: usize, is_vowel: fn(u8) -> bool) -> usize {
+    (from + 1..w.len())
+        .find(|&i| is_vowel(w[i - 1]) && !is_vowel(w[i]))
+        .map_or(w.len(), |i| i + 1)
+}
+
+/// Longest of `suffixes` that `w` ends with.
+///
+/// Snowball rules pick the longest suffix first and only then check its
+/// condition; a failed condition doesn't fall back to a shorter suffix.
+fn longest_suffix<'a>(w: &[u8], suffixes: &[&'a str]) -> Option<&'a str> {
+    suffixes
+        .iter()
+        .filter(|s| w.ends_with(s.as_bytes()))
+        .max_by_key(|s| s.len())
+        .copied()
+}
+
+/// Replace the last `len` bytes of `w` with `with`.
+fn replace_suffix(w: &mut Vec<u8>, len: usize, with: &str) {
+    w.truncate(w.len() - len);
+    w.extend_from_slice(with.as_bytes());
+}
+
+/// One language's stems: which vocabulary terms share each stem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StemTable {
+    language: Language,
+    /// Terms per stem, in vocabulary order
+    by_stem: HashMap<String, Vec<u32>>,
+}
+
+/// Vocabulary terms grouped by stem, one table per language.
+///
+/// Only valid for the vocabulary it was built from, in the same order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StemIndex {
+    tables: Vec<StemTable>,
+    /// Terms in the vocabulary it was built from
+    term_count: usize,
+}
+
+impl StemIndex {
+    /// Stem every term in `vocabulary` in each of `languages`.
+    pub fn build(vocabulary: &[String], languages: &[Language]) -> Self {
+        let all_terms: Vec<u32> = (0..vocabulary.len() as u32).collect();
+        let terms: BTreeMap<Language, Vec<u32>> = languages
+            .iter()
+            .map(|&language| (language, all_terms.clone()))
+            .collect();
+        Self::build_per_language(vocabulary, &terms)
+    }
+
+    /// Stem each language's vocabulary terms (indices into `vocabulary`),
+    /// such as the terms its documents use. Languages without a stemmer get
+    /// no table.
+    pub fn build_per_language(vocabulary: &[String], terms: &BTreeMap<Language, Vec<u32>>) -> Self {
+        let tables = terms
+            .iter()
+            .filter(|(language, _)| language.has_stemmer())
+            .map(|(&language, term_idxs)| {
+                let mut term_idxs = term_idxs.clone();
+                term_idxs.sort_unstable();
+                term_idxs.dedup();
+                let mut by_stem: HashMap<String, Vec<u32>> = HashMap::new();
+                for term_idx in term_idxs {
+                    by_stem
+                        .entry(stem(&vocabulary[term_idx as usize], language))
+                        .or_default()
+                        .push(term_idx);
+                }
+                StemTable { language, by_stem }
+            })
+            .collect();
+        Self {
+            tables,
+            term_count: vocabulary.len(),
+        }
+    }
+
+    /// Number of terms covered.
+    pub fn len(&self) -> usize {
+        self.term_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.term_count == 0
+    }
+
+    /// Languages the index has stems for.
+    pub fn languages(&self) -> impl Iterator<Item = Language> + '_ {
+        self.tables.iter().map(|t| t.language)
+    }
+
+    /// Vocabulary terms sharing a stem with `word` in any language, in
+    /// vocabulary order.
+    pub fn matches(&self, word: &str) -> Vec<u32> {
+        self.matches_where(word, |_| true)
+    }
+
+    /// Vocabulary terms sharing a stem with `word` in `language`, in
+    /// vocabulary order. Empty if the index has no stems for `language`.
+    pub fn matches_in(&self, word: &str, language: Language) -> Vec<u32> {
+        self.matches_where(word, |l| l == language)
+    }
+
+    fn matches_where(&self, word: &str, keep: impl Fn(Language) -> bool) -> Vec<u32> {
+        let mut terms: Vec<u32> = self
+            .tables
+            .iter()
+            .filter(|t| keep(t.language))
+            .filter_map(|t| t.by_stem.get(&stem(word, t.language)))
+            .flatten()
+            .copied()
+            .collect();
+        terms.sort_unstable();
+        terms.dedup();
+        terms
+    }
+
+    /// Append the binary encoding to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        encode_varint(self.term_count as u64, out);
+        encode_varint(self.tables.len() as u64, out);
+        for table in &self.tables {
+            out.push(tag(table.language));
+            encode_varint(table.by_stem.len() as u64, out);
+            // Sorted, so the same vocabulary always encodes the same way
+            let sorted: BTreeMap<&String, &Vec<u32>> = table.by_stem.iter().collect();
+            for (stem, terms) in sorted {
+                encode_varint(stem.len() as u64, out);
+                out.extend_from_slice(stem.as_bytes());
+                encode_varint(terms.len() as u64, out);
+                let mut prev = 0;
+                for &term_idx in terms {
+                    encode_varint((term_idx - prev) as u64, out);
+                    prev = term_idx;
+                }
+            }
+        }
+    }
+
+    /// Decode from the start of `bytes`, returning the index and bytes consumed.
+    pub fn decode(bytes: &[u8]) -> io::Result<(Self, usize)> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+        // Every count and length below takes at least one byte
+        let count = |value: u64, what: &str| -> io::Result<usize> {
+            usize::try_from(value)
+                .ok()
+                .filter(|&n| n <= bytes.len())
+                .ok_or_else(|| invalid(format!("Stem index claims {} {}", value, what)))
+        };
+
+        let (term_count, mut pos) = decode_varint(bytes)?;
+        let (table_count, consumed) = decode_varint(&bytes[pos..])?;
+        pos += consumed;
+        let mut tables = Vec::with_capacity(count(table_count, "tables")?);
+        for _ in 0..table_count {
+            let language = bytes
+                .get(pos)
+                .and_then(|&tag| from_tag(tag))
+                .ok_or_else(|| invalid("Unknown stem index language".to_string()))?;
+            pos += 1;
+            let (stem_count, consumed) = decode_varint(&bytes[pos..])?;
+            pos += consumed;
+
+            let mut by_stem = HashMap::with_capacity(count(stem_count, "stems")?);
+            for _ in 0..stem_count {
+                let (len, consumed) = decode_varint(&bytes[pos..])?;
+                pos += consumed;
+                let stem = pos
+                    .checked_add(count(len, "stem bytes")?)
+                    .and_then(|end| bytes.get(pos..end))
+                    .and_then(|stem| std::str::from_utf8(stem).ok())
+                    .ok_or_else(|| invalid("Invalid stem".to_string()))?;
+                pos += stem.len();
+
+                let (members, consumed) = decode_varint(&bytes[pos..])?;
+                pos += consumed;
+                let mut ids = Vec::with_capacity(count(members, "terms per stem")?);
+                let mut term_idx = 0u64;
+                for _ in 0..members {
+                    let (delta, consumed) = decode_varint(&bytes[pos..])?;
+                    pos += consumed;
+                    term_idx += delta;
+                    if term_idx >= term_count {
+                        return Err(invalid(format!(
+                            "Stem {:?} lists term {} of {}",
+                            stem, term_idx, term_count
+                        )));
+                    }
+                    ids.push(term_idx as u32);
+                }
+                by_stem.insert(stem.to_string(), ids);
+            }
+            tables.push(StemTable { language, by_stem });
+        }
+
+        let term_count = usize::try_from(term_count)
+            .map_err(|_| invalid(format!("Stem index claims {} terms", term_count)))?;
+        Ok((Self { tables, term_count }, pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stems(words: &[&str], language: Language) -> Vec<String> {
+        words.iter().map(|w| stem(w, language)).collect()
+    }
+
+    #[test]
+    fn test_english() {
+        let cases = [
+            ("optimize", "optim"),
+            ("optimized", "optim"),
+            ("optimizing", "optim"),
+            ("optimization", "optim"),
+            ("running", "run"),
+            ("runs", "run"),
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("ties", "tie"),
+            ("cried", "cri"),
+            ("happiness", "happi"),
+            ("happy", "happi"),
+            ("generously", "generous"),
+            ("connection", "connect"),
+            ("connected", "connect"),
+            ("hopeful", "hope"),
+            ("hoping", "hope"),
+            ("relational", "relat"),
+            ("skies", "sky"),
+            ("news", "news"),
+            ("gas", "gas"),
+            ("succeed", "succeed"),
+        ];
+        for (word, expected) in cases {
+            assert_eq!(
+                stem(word, Language::English),
+                expected,
+                "stem of {:?}",
+                word
+            );
+        }
+    }
+
+    #[test]
+    fn test_german() {
+        let s = stems(
+            &[
+                "versicherung",
+                "versicherungen",
+                "häuser",
+                "haus",
+                "kategorien",
+            ],
+            Language::German,
+        );
+        assert_eq!(s[0], s[1]);
+        assert_eq!(
+            stem("aufeinanderfolgenden", Language::German),
+            "aufeinanderfolg"
+        );
+        assert_eq!(stem("kategorie", Language::German), s[4]);
+        // Expects folded input: "häuser" isn't an ASCII word
+        assert_eq!(s[2], "häuser");
+        assert_eq!(stem("hauser", Language::German), "haus");
+    }
+
+    #[test]
+    fn test_french() {
+        let s = stems(
+            &["continuation", "continuer", "continuez", "continuellement"],
+            Language::French,
+        );
+        assert_eq!(s[0], "continu");
+        assert_eq!(s[1], "continu");
+        assert_eq!(s[2], "continu");
+        assert_eq!(s[3], "continuel");
+        assert_eq!(stem("chevaux", Language::French), "cheval");
+        assert_eq!(stem("majestueusement", Language::French), "majestu");
+    }
+
+    #[test]
+    fn test_spanish() {
+        let s = stems(
+            &["programacion", "programar", "programas", "programadores"],
+            Language::Spanish,
+        );
+        assert!(s.iter().all(|x| x == "program"), "{:?}", s);
+        assert_eq!(stem("rapidamente", Language::Spanish), "rapid");
+        assert_eq!(stem("chicas", Language::Spanish), "chic");
+    }
+
+    #[test]
+    fn test_non_words_pass_through() {
+        for language in Language::ALL {
+            assert_eq!(stem("v2", language), "v2");
+            assert_eq!(stem("москва", language), "москва");
+            assert_eq!(stem("", language), "");
+        }
+    }
+
+    #[test]
+    fn test_index_roundtrip() {
+        let vocabulary: Vec<String> = ["connect", "connected", "connection", "rust", "москва"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let index = StemIndex::build(&vocabulary, &[Language::English, Language::English]);
+        assert_eq!(index.len(), vocabulary.len());
+        assert_eq!(
+            index.languages().collect::<Vec<_>>(),
+            vec![Language::English]
+        );
+        assert_eq!(index.matches("connecting"), vec![0, 1, 2]);
+        assert_eq!(index.matches("rusts"), vec![3]);
+        assert_eq!(index.matches("москва"), vec![4]);
+        assert!(index.matches("python").is_empty());
+
+        let index = StemIndex::build(&vocabulary, &[Language::German, Language::English]);
+        let mut bytes = Vec::new();
+        index.encode(&mut bytes);
+        bytes.push(0xAB); // trailing data belongs to someone else
+        let (decoded, consumed) = StemIndex::decode(&bytes).unwrap();
+        assert_eq!(decoded, index);
+        assert_eq!(consumed, bytes.len() - 1);
+
+        assert!(StemIndex::decode(&bytes[..consumed - 1]).is_err());
+    }
+
+    #[test]
+    fn test_per_language_tables() {
+        let vocabulary: Vec<String> = ["die", "died", "dies", "haus", "hauser"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        // English documents use the first three terms, German ones the rest;
+        // Italian has no stemmer
+        let terms = BTreeMap::from([
+            (Language::English, vec![2, 0, 1]),
+            (Language::German, vec![3, 4]),
+            (Language::Italian, vec![0, 1, 2, 3, 4]),
+        ]);
+        let index = StemIndex::build_per_language(&vocabulary, &terms);
+        assert_eq!(
+            index.languages().collect::<Vec<_>>(),
+            vec![Language::English, Language::German]
+        );
+        assert_eq!(index.matches("dying"), vec![0, 1, 2]);
+        assert_eq!(index.matches("hausern"), vec![3, 4]);
+        assert_eq!(
+            index.matches_in("dying", Language::German),
+            Vec::<u32>::new()
+        );
+        assert_eq!(index.matches_in("hauser", Language::German), vec![3, 4]);
+        assert!(index.matches_in("dies", Language::Italian).is_empty());
+
+        let mut bytes = Vec::new();
+        index.encode(&mut bytes);
+        assert_eq!(StemIndex::decode(&bytes).unwrap().0, index);
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Spanish: the Snowball Spanish stemmer, on folded words ("ación" reads as
+//! "acion").
+
+use super::{longest_suffix, region_after, replace_suffix};
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u')
+}
+
+/// Pronouns attached to a gerund or infinitive ("dándole", "hacerlo").
+const PRONOUNS: &[&str] = &[
+    "me", "se", "sela", "selo", "selas", "selos", "la", "le", "lo", "las", "les", "los", "nos",
+];
+
+/// Verb endings deleted outright in RV.
+const VERB_ENDINGS: &[&str] = &[
+    "arian", "arias", "aran", "aras", "ariais", "aria", "areis", "ariamos", "aremos", "ara", "are",
+    "erian", "erias", "eran", "eras", "eriais", "eria", "ereis", "eriamos", "eremos", "era", "ere",
+    "irian", "irias", "iran", "iras", "iriais", "iria", "ireis", "iriamos", "iremos", "ira", "ire",
+    "aba", "ada", "ida", "ia", "iera", "ad", "ed", "id", "ase", "iese", "aste", "iste", "an",
+    "aban", "ian", "ieran", "asen", "iesen", "aron", "ieron", "ado", "ido", "ando", "iendo", "io",
+    "ar", "er", "ir", "as", "abas", "adas", "idas", "ias", "ieras", "ases", "ieses", "is", "ais",
+    "abais", "iais", "arais", "ierais", "aseis", "ieseis", "asteis", "isteis", "ados", "idos",
+    "amos", "abamos", "iamos", "imos", "aramos", "ieramos", "iesemos", "asemos",
+];
+
+/// Verb endings that drop the u of a preceding "gu" ("averigüen").
+const GU_ENDINGS: &[&str] = &["en", "es", "eis", "emos"];
+
+/// Step 1 suffixes, grouped by what happens to them.
+const PLAIN: &[&str] = &[
+    "anza", "anzas", "ico", "ica", "icos", "icas", "ismo", "ismos", "able", "ables", "ible",
+    "ibles", "ista", "istas", "oso", "osa", "osos", "osas", "amiento", "amientos", "imiento",
+    "imientos",
+];
+const AFTER_IC: &[&str] = &[
+    "adora", "ador", "acion", "adoras", "adores", "aciones", "ante", "antes", "ancia", "ancias",
+];
+
+/// RV: after the first vowel following a consonant in second place, after
+/// the first consonant following two vowels, else after the third letter.
+fn rv(w: &[u8]) -> usize {
+    let n = w.len();
+    if n < 2 {
+        return n;
+    }
+    let after = |pred: fn(u8) -> bool| (2..n).find(|&i| pred(w[i])).map_or(n, |i| i + 1);
+    if !is_vowel(w[1]) {
+        after(is_vowel)
+    } else if is_vowel(w[0]) {
+        after(|c| !is_vowel(c))
+    } else {
+        n.min(3)
+    }
+}
+
+pub(super) fn stem(w: &mut Vec<u8>) {
+    let rv = rv(w);
+    let r1 = region_after(w, 0, is_vowel);
+    let r2 = region_after(w, r1, is_vowel);
+
+    attached_pronoun(w, rv);
+    if !standard_suffix(w, r1, r2) && !y_verb_suffix(w, rv) {
+        verb_suffix(w, rv);
+    }
+    residual_suffix(w, rv);
+}
+
+/// Step 0: "hablandole" → "hablando".
+fn attached_pronoun(w: &mut Vec<u8>, rv: usize) {
+    let Some(pronoun) = longest_suffix(w, PRONOUNS) else {
+        return;
+    };
+    let stem_len = w.len() - pronoun.len();
+    let head = &w[..stem_len];
+    let allowed = match longest_suffix(head, &["iendo", "ando", "ar", "er", "ir", "yendo"]) {
+        Some("yendo") => stem_len - 5 >= rv && head[..stem_len - 5].ends_with(b"u"),
+        Some(ending) => stem_len - ending.len() >= rv,
+        None => false,
+    };
+    if allowed {
+        w.truncate(stem_len);
+    }
+}
+
+/// Step 1: derivational suffixes. Returns whether one was removed.
+fn standard_suffix(w: &mut Vec<u8>, r1: usize, r2: usize) -> bool {
+    let groups: &[&[&str]] = &[
+        PLAIN,
+        AFTER_IC,
+        &["logia", "logias"],
+        &["ucion", "uciones"],
+        &["encia", "encias"],
+        &["amente"],
+        &["mente"],
+        &["idad", "idades"],
+        &["iva", "ivo", "ivas", "ivos"],
+    ];
+    let all: Vec<&str> = groups.iter().flat_map(|g| g.iter().copied()).collect();
+    let Some(suffix) = longest_suffix(w, &all) else {
+        return false;
+    };
+    let stem_len = w.len() - suffix.len();
+    let in_r2 = stem_len >= r2;
+    // Delete `before` too if it ends the word within R2
+    let drop = |w: &mut Vec<u8>, before: &str| {
+        let ok = w.ends_with(before.as_bytes()) && w.len() - before.len() >= r2;
+        if ok {
+            w.truncate(w.len() - before.len());
+        }
+        ok
+    };
+
+    match suffix {
+        "logia" | "logias" if in_r2 => replace_suffix(w, suffix.len(), "log"),
+        "ucion" | "uciones" if in_r2 => replace_suffix(w, suffix.len(), "u"),
+        "encia" | "encias" if in_r2 => replace_suffix(w, suffix.len(), "ente"),
+        "amente" if stem_len >= r1 => {
+            w.truncate(stem_len);
+            if drop(w, "iv") {
+                drop(w, "at");
+            } else {
+                let _ = drop(w, "os") || drop(w, "ic") || drop(w, "ad");
+            }
+        }
+        "mente" if in_r2 => {
+            w.truncate(stem_len);
+            let _ = drop(w, "ante") || drop(w, "able") || drop(w, "ible");
+        }
+        "idad" | "idades" if in_r2 => {
+            w.truncate(stem_len);
+            let _ = drop(w, "abil") || drop(w, "ic") || drop(w, "iv");
+        }
+        "iva" | "ivo" | "ivas" | "ivos" if in_r2 => {
+            w.truncate(stem_len);
+            drop(w, "at");
+        }
+        _ if in_r2 && AFTER_IC.contains(&suffix) => {
+            w.truncate(stem_len);
+            drop(w, "ic");
+        }
+        _ if in_r2 && PLAIN.contains(&suffix) => w.truncate(stem_len),
+        _ => return false,
+    }
+    true
+}
+
+/// Step 2a: verb endings starting with y after a u ("huyeron" → "hu").
+fn y_verb_suffix(w: &mut Vec<u8>, rv: usize) -> bool {
+    let endings = [
+        "ya", "ye", "yan", "yen", "yeron", "yendo", "yo", "yas", "yes", "yais", "yamos",
+    ];
+    let Some(suffix) = longest_suffix(w, &endings) else {
+        return false;
+    };
+    let stem_len = w.len() - suffix.len();
+    let allowed = stem_len >= rv && w[..stem_len].ends_with(b"u");
+    if allowed {
+        w.truncate(stem_len);
+    }
+    allowed
+}
+
+/// Step 2b: other verb endings ("programar" → "program").
+fn verb_suffix(w: &mut Vec<u8>, rv: usize) {
+    let all: Vec<&str> = VERB_ENDINGS.iter().chain(GU_ENDINGS).copied().collect();
+    let Some(suffix) = longest_suffix(w, &all) else {
+        return;
+    };
+    let stem_len = w.len() - suffix.len();
+    if stem_len < rv {
+        return;
+    }
+    w.truncate(stem_len);
+    if GU_ENDINGS.contains(&suffix) && w.ends_with(b"gu") {
+        w.pop();
+    }
+}
+
+/// Step 3: a final vowel in RV ("chicas" → "chic").
+fn residual_suffix(w: &mut Vec<u8>, rv: usize) {
+    let Some(suffix) = longest_suffix(w, &["os", "a", "o", "i", "e"]) else {
+        return;
+    };
+    let stem_len = w.len() - suffix.len();
+    if stem_len < rv {
+        return;
+    }
+    w.truncate(stem_len);
+    if suffix == "e" && w.ends_with(b"gu") && w.len() > rv {
+        w.pop();
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Synonyms: "k8s" finds "kubernetes".
+//!
+//! Every docs site has words its readers use interchangeably that no
+//! stemmer or typo budget connects: abbreviations ("js", "javascript"),
+//! product names ("k8s", "kubernetes"), shorthand ("auth",
+//! "authentication"). The manifest lists them as rules in the Solr synonyms
+//! syntax, and the index carries them ([`Synonyms`]), so the searcher can
+//! retry a query with a rule's other side:
+//!
+//! ```text
+//! k8s, kubernetes                 two-way: each finds the other
+//! auth => authentication, authz   one-way: "auth" finds both, not back
+//! single sign on, sso             phrases work on either side
+//! ```
+//!
+//! Phrases are cut and folded like indexed text, so "Single Sign-On" and
+//! "single sign on" are the same phrase.
+//!
+//! # Binary Format
+//!
+//! Appended to the LEV_DFA section (after the stem tables, if any), flagged
+//! by `FormatFlags::HAS_SYNONYMS`:
+//!
+//! ```text
+//! varint(phrase_count)
+//! For each phrase (sorted):
+//!   varint(phrase_len), phrase bytes   words joined by single spaces
+//!   varint(expansion_count)
+//!   For each expansion: varint(len), bytes
+//! ```
+
+use super::word_spans;
+use crate::binary::{decode_varint, encode_varint};
+use std::collections::BTreeMap;
+use std::io;
+
+/// Phrases and what each expands to, from synonym rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Synonyms {
+    by_phrase: BTreeMap<String, Vec<String>>,
+    /// Words in the longest phrase
+    max_words: usize,
+}
+
+impl Synonyms {
+    /// Parse rules in the Solr synonyms syntax: `a, b, c` makes the phrases
+    /// equivalent, `a, b => c, d` rewrites `a` and `b` to `c` and `d` only.
+    ///
+    /// ```
+    /// use sorex::Synonyms;
+    ///
+    /// let synonyms = Synonyms::parse(&["k8s, Kubernetes", "auth => authentication"]).unwrap();
+    /// assert_eq!(synonyms.expansions("kubernetes"), ["k8s"]);
+    /// assert_eq!(synonyms.expansions("auth"), ["authentication"]);
+    /// assert!(synonyms.expansions("authentication").is_empty());
+    /// ```
+    pub fn parse<S: AsRef<str>>(rules: &[S]) -> Result<Self, String> {
+        let mut synonyms = Self::default();
+        for (i, rule) in rules.iter().enumerate() {
+            let rule = rule.as_ref();
+            let phrases = |side: &str| -> Result<Vec<String>, String> {
+                side.split(',')
+                    .map(|phrase| {
+                        let words: Vec<String> =
+                            word_spans(phrase).into_iter().map(|(w, _, _)| w).collect();
+                        if words.is_empty() {
+                            Err(format!(
+                                "synonym rule {} ({:?}) has an empty phrase",
+                                i + 1,
+                                rule
+                            ))
+                        } else {
+                            Ok(words.join(" "))
+                        }
+                    })
+                    .collect()
+            };
+            match rule.split_once("=>") {
+                Some((from, to)) => {
+                    let to = phrases(to)?;
+                    for from in phrases(from)? {
+                        synonyms.add(from, &to);
+                    }
+                }
+                None => {
+                    let equivalent = phrases(rule)?;
+                    if equivalent.len() < 2 {
+                        return Err(format!(
+                            "synonym rule {} ({:?}) needs two phrases, or \"=>\"",
+                            i + 1,
+                            rule
+                        ));
+                    }
+                    for phrase in &equivalent {
+                        synonyms.add(phrase.clone(), &equivalent);
+                    }
+                }
+            }
+        }
+        Ok(synonyms)
+    }
+
+    /// Expand `phrase` to each of `to` but itself.
+    fn add(&mut self, phrase: String, to: &[String]) {
+        let words = phrase.split(' ').count();
+        let expansions = self.by_phrase.entry(phrase.clone()).or_default();
+        for expansion in to {
+            if *expansion != phrase && !expansions.contains(expansion) {
+                expansions.push(expansion.clone());
+            }
+        }
+        if expansions.is_empty() {
+            self.by_phrase.remove(&phrase);
+        } else {
+            self.max_words = self.max_words.max(words);
+        }
+    }
+
+    /// What a folded phrase (words joined by single spaces) expands to.
+    pub fn expansions(&self, phrase: &str) -> &[String] {
+        self.by_phrase.get(phrase).map_or(&[], Vec::as_slice)
+    }
+
+    /// Words in the longest phrase with expansions.
+    pub fn max_words(&self) -> usize {
+        self.max_words
+    }
+
+    /// Number of phrases with expansions.
+    pub fn len(&self) -> usize {
+        self.by_phrase.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_phrase.is_empty()
+    }
+
+    /// Serialize (see the module docs for the layout).
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        let encode_str = |s: &str, out: &mut Vec<u8>| {
+            encode_varint(s.len() as u64, out);
+            out.extend_from_slice(s.as_bytes());
+        };
+        encode_varint(self.by_phrase.len() as u64, out);
+        for (phrase, expansions) in &self.by_phrase {
+            encode_str(phrase, out);
+            encode_varint(expansions.len() as u64, out);
+            for expansion in expansions {
+                encode_str(expansion, out);
+            }
+        }
+    }
+
+    /// Decode from the start of `bytes`, returning the table and bytes consumed.
+    pub fn decode(bytes: &[u8]) -> io::Result<(Self, usize)> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+        // Every count and length below takes at least one byte
+        let count = |value: u64, what: &str| -> io::Result<usize> {
+            usize::try_from(value)
+                .ok()
+                .filter(|&n| n <= bytes.len())
+                .ok_or_else(|| invalid(format!("Synonym table claims {} {}", value, what)))
+        };
+        let decode_str = |pos: &mut usize| -> io::Result<String> {
+            let (len, consumed) = decode_varint(&bytes[*pos..])?;
+            *pos += consumed;
+            let s = pos
+                .checked_add(count(len, "phrase bytes")?)
+                .and_then(|end| bytes.get(*pos..end))
+                .and_then(|s| std::str::from_utf8(s).ok())
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| invalid("Invalid synonym phrase".to_string()))?;
+            *pos += s.len();
+            Ok(s.to_string())
+        };
+
+        let mut synonyms = Self::default();
+        let (phrase_count, mut pos) = decode_varint(bytes)?;
+        for _ in 0..count(phrase_count, "phrases")? {
+            let phrase = decode_str(&mut pos)?;
+            let (expansion_count, consumed) = decode_varint(&bytes[pos..])?;
+            pos += consumed;
+            let mut expansions = Vec::with_capacity(count(expansion_count, "expansions")?);
+            for _ in 0..expansion_count {
+                expansions.push(decode_str(&mut pos)?);
+            }
+            synonyms.add(phrase, &expansions);
+        }
+        Ok((synonyms, pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_way_and_one_way_rules() {
+        let synonyms = Synonyms::parse(&[
+            "k8s, kubernetes",
+            "js, ecmascript => javascript",
+            "Single Sign-On, SSO",
+        ])
+        .unwrap();
+        assert_eq!(synonyms.expansions("k8s"), ["kubernetes"]);
+        assert_eq!(synonyms.expansions("kubernetes"), ["k8s"]);
+        assert_eq!(synonyms.expansions("js"), ["javascript"]);
+        assert_eq!(synonyms.expansions("ecmascript"), ["javascript"]);
+        assert!(synonyms.expansions("javascript").is_empty());
+        assert_eq!(synonyms.expansions("single sign on"), ["sso"]);
+        assert_eq!(synonyms.expansions("sso"), ["single sign on"]);
+        assert_eq!(synonyms.max_words(), 3);
+    }
+
+    #[test]
+    fn test_rules_merge() {
+        let synonyms = Synonyms::parse(&["auth => authentication", "auth, authz"]).unwrap();
+        assert_eq!(synonyms.expansions("auth"), ["authentication", "authz"]);
+        assert_eq!(synonyms.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(Synonyms::parse(&["kubernetes"]).is_err());
+        assert!(Synonyms::parse(&["k8s, "]).is_err());
+        assert!(Synonyms::parse(&["=> javascript"]).is_err());
+        assert!(Synonyms::parse::<&str>(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let synonyms =
+            Synonyms::parse(&["k8s, kubernetes", "auth => authentication, authz"]).unwrap();
+        let mut bytes = Vec::new();
+        synonyms.encode(&mut bytes);
+        let (decoded, consumed) = Synonyms::decode(&bytes).unwrap();
+        assert_eq!(decoded, synonyms);
+        assert_eq!(consumed, bytes.len());
+
+        assert!(Synonyms::decode(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Synonyms::decode(&[0xFF, 0xFF, 0x01]).is_err());
+    }
+}
@@ -0,0 +1,330 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tokenizers: how text is cut into words.
+//!
+//! The default tokenizer splits at anything that isn't a letter or digit,
+//! which is right for prose and wrong for API docs: `std::vec::Vec` falls
+//! apart into loose words, and `C++`, `C#` and `--flag` lose the characters
+//! that make them what they are.
+//!
+//! The code tokenizer (`"tokenizer": "code"` in the manifest) keeps runs of
+//! identifier characters joined by `::`, `.`, `/` or `-` together, along with
+//! a leading `-` or `.` and a trailing `+` or `#`. Each token is indexed
+//! whole and as its parts, split at that punctuation, at underscores and at
+//! camelCase humps:
+//!
+//! ```text
+//! std::vec::Vec     → std::vec::vec, std, vec, vec
+//! getHTTPResponse   → gethttpresponse, get, http, response
+//! snake_case_names  → snake_case_names, snake, case, names
+//! --dry-run         → --dry-run, dry, run
+//! v1.2.3            → v1.2.3, v1, 2, 3
+//! C++               → c++
+//! ```
+//!
+//! A trailing `+` or `#` is part of the name (C# isn't C), so those tokens
+//! are only indexed whole. The whole token takes its first part's word
+//! position, so a phrase over the parts still lines up.
+//!
+//! Queries are cut the same way: a token with several parts must match all
+//! of them, so `vec::push`, `VecPush` and `vec_push` all find `Vec::push`,
+//! and a token with one part (`C++`, `--verbose`) is looked up whole.
+
+use super::segment::{is_thai, segment};
+use crate::util::normalize::normalize;
+
+/// How an index cuts text into words. Queries must be cut the same way, so
+/// the choice is recorded in the index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Tokenizer {
+    /// Words are runs of letters and digits
+    #[default]
+    Text,
+    /// Identifiers, paths and versions stay whole, plus their parts
+    Code,
+}
+
+impl Tokenizer {
+    /// Parse a manifest value ("text", "code"), ignoring case.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "text" => Some(Tokenizer::Text),
+            "code" => Some(Tokenizer::Code),
+            _ => None,
+        }
+    }
+}
+
+/// Letters, digits and underscores: what identifiers are made of.
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || is_thai(c) || c == '_'
+}
+
+/// Punctuation that joins identifier runs into one token.
+fn is_joiner(c: char) -> bool {
+    matches!(c, ':' | '.' | '/' | '-')
+}
+
+/// Punctuation that ends a name ("C++", "F#").
+fn is_suffix(c: char) -> bool {
+    matches!(c, '+' | '#')
+}
+
+/// One code token: its byte range and its parts' byte ranges.
+struct CodeToken {
+    start: usize,
+    end: usize,
+    parts: Vec<(usize, usize)>,
+}
+
+impl CodeToken {
+    /// True if the token is only worth indexing whole ("c++", "f#").
+    fn whole_only(&self, text: &str) -> bool {
+        self.parts.len() == 1 && text[..self.end].ends_with(is_suffix)
+    }
+}
+
+/// Cut `text` into code tokens.
+fn code_tokens(text: &str) -> Vec<CodeToken> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let is_token_char = |c: char| is_identifier_char(c) || is_joiner(c) || is_suffix(c);
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if !is_token_char(chars[i].1) {
+            i += 1;
+            continue;
+        }
+        let run_start = i;
+        while i < chars.len() && is_token_char(chars[i].1) {
+            i += 1;
+        }
+        let run = &chars[run_start..i];
+
+        // Leading '-' and '.' stay ("--flag", ".env"), other leading
+        // punctuation goes; trailing joiners are sentence punctuation
+        let Some(first) = run.iter().position(|&(_, c)| is_identifier_char(c)) else {
+            continue;
+        };
+        let lead = run[..first]
+            .iter()
+            .rposition(|&(_, c)| !matches!(c, '-' | '.'))
+            .map_or(0, |p| p + 1);
+        let last = run
+            .iter()
+            .rposition(|&(_, c)| is_identifier_char(c))
+            .expect("has an identifier char");
+        let tail = run[last + 1..]
+            .iter()
+            .take_while(|&&(_, c)| is_suffix(c))
+            .count();
+        let token = &run[lead..=last + tail];
+
+        let start = token[0].0;
+        let (end_at, end_char) = token[token.len() - 1];
+        tokens.push(CodeToken {
+            start,
+            end: end_at + end_char.len_utf8(),
+            parts: parts(token),
+        });
+    }
+
+    tokens
+}
+
+/// Byte ranges of a token's parts: identifier runs, split at underscores
+/// and camelCase humps.
+fn parts(token: &[(usize, char)]) -> Vec<(usize, usize)> {
+    let mut parts = Vec::new();
+    let mut part: Option<(usize, usize)> = None;
+    for (k, &(at, c)) in token.iter().enumerate() {
+        let end = at + c.len_utf8();
+        if !is_identifier_char(c) || c == '_' {
+            parts.extend(part.take());
+            continue;
+        }
+        let prev = k.checked_sub(1).map(|p| token[p].1);
+        let next = token.get(k + 1).map(|&(_, c)| c);
+        // "camelCase", "utf8String": lower or digit, then upper.
+        // "HTTPServer": the last upper of a run, before a lower.
+        let hump = c.is_uppercase()
+            && prev.is_some_and(|p| {
+                p.is_lowercase()
+                    || p.is_numeric()
+                    || p.is_uppercase() && next.is_some_and(char::is_lowercase)
+            });
+        match part.as_mut() {
+            Some(current) if !hump => current.1 = end,
+            _ => {
+                parts.extend(part.take());
+                part = Some((at, end));
+            }
+        }
+    }
+    parts.extend(part);
+    parts
+}
+
+/// A part's normalized words: one, or bigrams for unspaced scripts.
+fn part_words(text: &str, (start, end): (usize, usize)) -> Vec<(String, usize, usize)> {
+    segment(&text[start..end])
+        .into_iter()
+        .map(|(s, e)| (normalize(&text[start + s..start + e]), start + s, start + e))
+        .filter(|(word, _, _)| !word.is_empty())
+        .collect()
+}
+
+/// Split text into `(normalized word, start, end)` the code tokenizer's way:
+/// each token whole, then its parts.
+///
+/// A whole token starts where its first part does, so starts never go down
+/// and the whole shares its first part's word position.
+pub(crate) fn code_spans(text: &str) -> Vec<(String, usize, usize)> {
+    let mut spans = Vec::new();
+    for token in code_tokens(text) {
+        let single = token.parts == [(token.start, token.end)];
+        if !single {
+            let start = if token.whole_only(text) {
+                token.start
+            } else {
+                token.parts[0].0
+            };
+            let whole = normalize(&text[token.start..token.end]);
+            spans.push((whole, start, token.end));
+        }
+        if single || !token.whole_only(text) {
+            for &part in &token.parts {
+                spans.extend(part_words(text, part));
+            }
+        }
+    }
+    spans
+}
+
+/// The words a query must match, cut the code tokenizer's way: the parts of
+/// tokens with several, the whole of tokens with one.
+pub(crate) fn code_query_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for token in code_tokens(text) {
+        if token.parts.len() == 1 && token.parts[0] != (token.start, token.end) {
+            words.push(normalize(&text[token.start..token.end]));
+        } else {
+            for &part in &token.parts {
+                words.extend(part_words(text, part).into_iter().map(|(w, _, _)| w));
+            }
+        }
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::word_spans_with;
+    use crate::search::tiered::{SearchOptions, TierSearcher};
+    use crate::testing::{build_layer, load_searcher};
+
+    fn words(text: &str) -> Vec<String> {
+        code_spans(text).into_iter().map(|(w, _, _)| w).collect()
+    }
+
+    #[test]
+    fn test_identifiers_index_whole_and_parts() {
+        assert_eq!(
+            words("std::vec::Vec"),
+            ["std::vec::vec", "std", "vec", "vec"]
+        );
+        assert_eq!(
+            words("getHTTPResponse"),
+            ["gethttpresponse", "get", "http", "response"]
+        );
+        assert_eq!(
+            words("snake_case_names"),
+            ["snake_case_names", "snake", "case", "names"]
+        );
+        assert_eq!(words("utf8String"), ["utf8string", "utf8", "string"]);
+        assert_eq!(
+            words("src/index/mod.rs"),
+            ["src/index/mod.rs", "src", "index", "mod", "rs"]
+        );
+    }
+
+    #[test]
+    fn test_symbols_that_matter_stay() {
+        assert_eq!(words("C++ and C#"), ["c++", "and", "c#"]);
+        assert_eq!(words("--dry-run"), ["--dry-run", "dry", "run"]);
+        assert_eq!(words("v1.2.3"), ["v1.2.3", "v1", "2", "3"]);
+        assert_eq!(words(".env"), [".env", "env"]);
+    }
+
+    #[test]
+    fn test_prose_reads_as_words() {
+        assert_eq!(words("Call it, then stop."), ["call", "it", "then", "stop"]);
+        assert_eq!(words("- a list item"), ["a", "list", "item"]);
+        assert_eq!(words("see Vec::push."), ["see", "vec::push", "vec", "push"]);
+    }
+
+    #[test]
+    fn test_whole_token_shares_first_part_start() {
+        let spans = code_spans("run --dry-run now");
+        let starts: Vec<usize> = spans.iter().map(|&(_, s, _)| s).collect();
+        assert_eq!(starts, [0, 6, 6, 10, 14]);
+    }
+
+    #[test]
+    fn test_query_words() {
+        assert_eq!(code_query_words("vec::push"), ["vec", "push"]);
+        assert_eq!(code_query_words("VecPush"), ["vec", "push"]);
+        assert_eq!(code_query_words("C++"), ["c++"]);
+        assert_eq!(code_query_words("--verbose"), ["--verbose"]);
+        assert_eq!(code_query_words("Rust"), ["rust"]);
+        assert_eq!(code_query_words("東京都"), ["東京", "京都"]);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Tokenizer::parse("Code"), Some(Tokenizer::Code));
+        assert_eq!(Tokenizer::parse("text"), Some(Tokenizer::Text));
+        assert_eq!(Tokenizer::parse("regex"), None);
+    }
+
+    /// A searcher over `texts`, one doc each, indexed with the code tokenizer.
+    fn searcher(texts: &[&str]) -> TierSearcher {
+        let docs: Vec<(&str, u8)> = texts.iter().map(|&t| (t, 5)).collect();
+        let (mut layer, _) = build_layer(&docs, true, &[], |text| {
+            word_spans_with(text, Tokenizer::Code)
+        });
+        layer.set_tokenizer(Tokenizer::Code);
+        load_searcher(layer)
+    }
+
+    fn doc_ids(searcher: &TierSearcher, query: &str) -> Vec<usize> {
+        let mut ids: Vec<usize> = searcher
+            .search_with_options(query, 10, SearchOptions::default())
+            .iter()
+            .map(|r| r.doc_id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_code_tokens_match_whole_and_by_part() {
+        let searcher = searcher(&[
+            "Call Vec::push to append",
+            "The push_vec helper in C++",
+            "Run cargo with --verbose for more output",
+            "Push onto a list in C",
+        ]);
+        let ids = |query: &str| doc_ids(&searcher, query);
+
+        assert_eq!(ids("vec::push"), vec![0, 1]);
+        assert_eq!(ids("VecPush"), vec![0, 1]);
+        assert_eq!(ids("\"vec::push\""), vec![0]);
+        assert_eq!(ids("C++"), vec![1]);
+        assert_eq!(ids("--verbose"), vec![2]);
+        assert_eq!(ids("push -vec"), vec![3]);
+    }
+}
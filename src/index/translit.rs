@@ -0,0 +1,497 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transliteration: "moskva" finds "Москва".
+//!
+//! People search Cyrillic, Greek and Hindi content from Latin keyboards, so
+//! they type the word the way it sounds. Folding only strips diacritics, and
+//! no amount of it turns "Москва" into "moskva".
+//!
+//! With transliteration on, every word containing one of these scripts is
+//! also indexed romanized, at the same offset and with the same score, so
+//! both spellings land on the same postings:
+//!
+//! ```text
+//! "Москва"      → "москва" + "moskva"
+//! "Αθήνα"       → "αθηνα"  + "athina"
+//! "नमस्ते"        →            "namaste"
+//! ```
+//!
+//! Queries in those scripts are romanized too, so the Cyrillic and Latin
+//! spellings of a word find each other whichever way round.
+//!
+//! The schemes are the plain ASCII ones people type, not the scholarly ones:
+//!
+//! - **Cyrillic**: Russian practical romanization ("ж" → "zh", "щ" →
+//!   "shch", soft and hard signs dropped), plus the Ukrainian, Belarusian
+//!   and Serbian letters.
+//! - **Greek**: ELOT 743 without the accents ("θ" → "th", "ου" → "ou", "ευ"
+//!   → "ev", "γγ" → "ng").
+//! - **Devanagari**: Hindi, with long and short vowels written alike and the
+//!   silent inherent vowel at the end of a word dropped ("भारत" → "bharat").
+//!
+//! Devanagari folds to nothing (its letters count as combining marks), so
+//! Hindi words are only searchable romanized.
+//!
+//! # References
+//!
+//! - **ELOT 743**: the Greek standard for romanization, as used on Greek
+//!   passports (ISO 843 type 2).
+//! - **Schwa deletion**: Ohala, "Aspects of Hindi Phonology" (1983) on the
+//!   inherent vowel Hindi drops at the end of a word.
+
+use super::segment::{is_cjk, is_thai};
+use crate::util::normalize::fold_char;
+
+/// Cyrillic, Greek or Devanagari: the scripts that get a romanized form.
+pub fn is_transliterable(c: char) -> bool {
+    is_cyrillic(c) || is_greek(c) || is_devanagari(c)
+}
+
+fn is_cyrillic(c: char) -> bool {
+    matches!(c, '\u{0400}'..='\u{04FF}')
+}
+
+fn is_greek(c: char) -> bool {
+    matches!(c, '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}')
+}
+
+fn is_devanagari(c: char) -> bool {
+    matches!(c, '\u{0900}'..='\u{097F}')
+}
+
+/// Part of a word for transliteration. Like the indexer's words, but
+/// Devanagari vowel signs and viramas don't split one, and CJK and Thai
+/// (segmented on their own) aren't part of one.
+fn is_word_char(c: char) -> bool {
+    if is_devanagari(c) {
+        // Danda, double danda and the abbreviation sign are punctuation
+        return !matches!(c, '\u{0964}' | '\u{0965}' | '\u{0970}');
+    }
+    c.is_alphanumeric() && !is_cjk(c) && !is_thai(c)
+}
+
+/// Romanize a word, folded like the indexer folds Latin ("Москва" →
+/// "moskva"). Characters outside the transliterated scripts are just folded.
+///
+/// ```
+/// use sorex::romanize;
+///
+/// assert_eq!(romanize("Москва"), "moskva");
+/// assert_eq!(romanize("Θεσσαλονίκη"), "thessaloniki");
+/// assert_eq!(romanize("हिन्दी"), "hindi");
+/// ```
+pub fn romanize(word: &str) -> String {
+    let chars: Vec<char> = word.chars().collect();
+    let mut out = String::with_capacity(word.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if is_cyrillic(c) {
+            for lower in c.to_lowercase() {
+                out.push_str(cyrillic(lower).unwrap_or(""));
+            }
+            i += 1;
+        } else if is_greek(c) {
+            i += greek(&chars[i..], &mut out);
+        } else if is_devanagari(c) {
+            i += devanagari(&chars[i..], &mut out);
+        } else {
+            fold_char(c, &mut out);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Romanized words of `text` as `(romanized word, start, end)`, for the words
+/// with Cyrillic, Greek or Devanagari in them.
+pub fn romanized_spans(text: &str) -> Vec<(String, usize, usize)> {
+    let mut spans = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if !is_word_char(c) {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        let mut transliterable = is_transliterable(c);
+        while let Some(&(at, c)) = chars.peek() {
+            if !is_word_char(c) {
+                break;
+            }
+            transliterable |= is_transliterable(c);
+            end = at + c.len_utf8();
+            chars.next();
+        }
+        if transliterable {
+            let word = romanize(&text[start..end]);
+            if !word.is_empty() {
+                spans.push((word, start, end));
+            }
+        }
+    }
+    spans
+}
+
+/// `spans` (the indexer's words) with the words [`romanized_spans`] covers
+/// replaced by their romanized form, in text order.
+pub fn with_romanized(
+    text: &str,
+    spans: Vec<(String, usize, usize)>,
+) -> Vec<(String, usize, usize)> {
+    let romanized = romanized_spans(text);
+    if romanized.is_empty() {
+        return spans;
+    }
+    let mut words: Vec<(String, usize, usize)> = spans
+        .into_iter()
+        .filter(|&(_, start, _)| {
+            !romanized
+                .iter()
+                .any(|&(_, from, to)| (from..to).contains(&start))
+        })
+        .collect();
+    words.extend(romanized);
+    words.sort_by_key(|&(_, start, _)| start);
+    words
+}
+
+/// A lowercase Cyrillic letter, romanized.
+fn cyrillic(c: char) -> Option<&'static str> {
+    Some(match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' | 'ґ' => "g",
+        'д' => "d",
+        'е' | 'ё' | 'э' => "e",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'й' | 'ы' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' | 'ў' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ю' => "yu",
+        'я' => "ya",
+        'є' => "ye",
+        'ї' => "yi",
+        'ђ' => "dj",
+        'ј' => "j",
+        'љ' => "lj",
+        'њ' => "nj",
+        'ћ' => "c",
+        'џ' | 'ѕ' => "dz",
+        'ѓ' => "gj",
+        'ќ' => "kj",
+        _ => return None,
+    })
+}
+
+/// Romanize the Greek letter starting `chars`, with the digraph it may start.
+/// Returns how many chars it used.
+fn greek(chars: &[char], out: &mut String) -> usize {
+    // Folding drops the accents and turns final sigma into sigma
+    let folded = |i: usize| {
+        let mut s = String::new();
+        if let Some(&c) = chars.get(i) {
+            fold_char(c, &mut s);
+        }
+        s.chars().next()
+    };
+    let (Some(c), next) = (folded(0), folded(1)) else {
+        return 1;
+    };
+    let digraph = match (c, next) {
+        ('ο', Some('υ')) => Some("ou"),
+        ('α', Some('υ')) => Some("av"),
+        ('ε', Some('υ')) => Some("ev"),
+        ('η', Some('υ')) => Some("iv"),
+        ('γ', Some('γ')) => Some("ng"),
+        _ => None,
+    };
+    if let Some(digraph) = digraph {
+        out.push_str(digraph);
+        return 2;
+    }
+    if c == 'γ' && matches!(next, Some('κ' | 'ξ' | 'χ')) {
+        out.push('n');
+        return 1;
+    }
+    let roman = match c {
+        'α' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' => "e",
+        'ζ' => "z",
+        'η' | 'ι' => "i",
+        'θ' => "th",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' | 'ω' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' => "s",
+        'τ' => "t",
+        'υ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        _ => "",
+    };
+    out.push_str(roman);
+    1
+}
+
+/// Romanize the run of Devanagari starting `chars`. Returns how many chars
+/// it used.
+///
+/// A consonant carries an inherent "a" unless a vowel sign or virama follows
+/// it. The last one's is silent in Hindi, unless it's the only vowel.
+fn devanagari(chars: &[char], out: &mut String) -> usize {
+    let mut inherent = false;
+    let mut vowels = 0;
+    let mut i = 0;
+    while let Some(&c) = chars.get(i).filter(|&&c| is_devanagari(c)) {
+        let nukta = chars.get(i + 1) == Some(&'\u{093C}');
+        if let Some(consonant) = devanagari_consonant(c, nukta) {
+            if inherent {
+                out.push('a');
+                vowels += 1;
+            }
+            out.push_str(consonant);
+            inherent = true;
+            i += if nukta { 2 } else { 1 };
+            continue;
+        }
+        if let Some(vowel) = devanagari_vowel_sign(c) {
+            out.push_str(vowel);
+            vowels += 1;
+            inherent = false;
+        } else if let Some(vowel) = devanagari_vowel(c) {
+            if inherent {
+                out.push('a');
+                vowels += 1;
+            }
+            out.push_str(vowel);
+            vowels += 1;
+            inherent = false;
+        } else {
+            if inherent && c != '\u{094D}' {
+                out.push('a');
+                vowels += 1;
+            }
+            inherent = false;
+            match c {
+                // Anusvara and candrabindu: a nasal, "m" before p, b or m
+                '\u{0901}' | '\u{0902}' => {
+                    let labial = matches!(chars.get(i + 1), Some('\u{092A}'..='\u{092E}'));
+                    out.push(if labial { 'm' } else { 'n' });
+                }
+                '\u{0903}' => out.push('h'),
+                '\u{0966}'..='\u{096F}' => {
+                    out.push(char::from(b'0' + (c as u32 - 0x0966) as u8));
+                }
+                '\u{0950}' => out.push_str("om"),
+                _ => {} // virama, avagraha, accents
+            }
+        }
+        i += 1;
+    }
+    if inherent && vowels == 0 {
+        out.push('a');
+    }
+    i.max(1)
+}
+
+/// A Devanagari consonant, romanized (`nukta`: with a dot below, as in
+/// Urdu loanwords).
+fn devanagari_consonant(c: char, nukta: bool) -> Option<&'static str> {
+    Some(match (c, nukta) {
+        ('क', true) | ('\u{0958}', _) => "q",
+        ('ख', true) | ('\u{0959}', _) => "kh",
+        ('ग', true) | ('\u{095A}', _) => "g",
+        ('ज', true) | ('\u{095B}', _) => "z",
+        ('ड', true) | ('\u{095C}', _) => "r",
+        ('ढ', true) | ('\u{095D}', _) => "rh",
+        ('फ', true) | ('\u{095E}', _) => "f",
+        ('य', true) | ('\u{095F}', _) => "y",
+        ('क', _) => "k",
+        ('ख', _) => "kh",
+        ('ग', _) => "g",
+        ('घ', _) => "gh",
+        ('ङ', _) | ('ञ', _) | ('ण', _) | ('न', _) | ('ऩ', _) => "n",
+        ('च', _) => "ch",
+        ('छ', _) => "chh",
+        ('ज', _) => "j",
+        ('झ', _) => "jh",
+        ('ट', _) | ('त', _) => "t",
+        ('ठ', _) | ('थ', _) => "th",
+        ('ड', _) | ('द', _) => "d",
+        ('ढ', _) | ('ध', _) => "dh",
+        ('प', _) => "p",
+        ('फ', _) => "ph",
+        ('ब', _) => "b",
+        ('भ', _) => "bh",
+        ('म', _) => "m",
+        ('य', _) => "y",
+        ('र', _) | ('ऱ', _) => "r",
+        ('ल', _) | ('ळ', _) | ('ऴ', _) => "l",
+        ('व', _) => "v",
+        ('श', _) | ('ष', _) => "sh",
+        ('स', _) => "s",
+        ('ह', _) => "h",
+        _ => return None,
+    })
+}
+
+/// A Devanagari vowel letter (starting a syllable), romanized.
+fn devanagari_vowel(c: char) -> Option<&'static str> {
+    Some(match c {
+        'अ' | 'आ' => "a",
+        'इ' | 'ई' => "i",
+        'उ' | 'ऊ' => "u",
+        'ऋ' => "ri",
+        'ए' | 'ऍ' => "e",
+        'ऐ' => "ai",
+        'ओ' | 'ऑ' => "o",
+        'औ' => "au",
+        _ => return None,
+    })
+}
+
+/// A Devanagari vowel sign (after a consonant), romanized.
+fn devanagari_vowel_sign(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ा' => "a",
+        'ि' | 'ी' => "i",
+        'ु' | 'ू' => "u",
+        'ृ' => "ri",
+        'े' | 'ॅ' => "e",
+        'ै' => "ai",
+        'ो' | 'ॉ' => "o",
+        'ौ' => "au",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::word_spans;
+    use crate::search::tiered::{SearchOptions, TierSearcher};
+    use crate::testing::{build_layer, load_searcher};
+
+    #[test]
+    fn test_cyrillic() {
+        assert_eq!(romanize("Москва"), "moskva");
+        assert_eq!(romanize("Чайковский"), "chaykovskiy");
+        assert_eq!(romanize("Щёлково"), "shchelkovo");
+        assert_eq!(romanize("объявление"), "obyavlenie");
+        assert_eq!(romanize("Львів"), "lviv");
+        assert_eq!(romanize("Москва2024"), "moskva2024");
+    }
+
+    #[test]
+    fn test_greek() {
+        assert_eq!(romanize("Αθήνα"), "athina");
+        assert_eq!(romanize("Θεσσαλονίκη"), "thessaloniki");
+        assert_eq!(romanize("ΕΥΡΩΠΗ"), "evropi");
+        assert_eq!(romanize("ουρανός"), "ouranos");
+        assert_eq!(romanize("άγγελος"), "angelos");
+    }
+
+    #[test]
+    fn test_devanagari() {
+        assert_eq!(romanize("नमस्ते"), "namaste");
+        assert_eq!(romanize("भारत"), "bharat");
+        assert_eq!(romanize("दिल्ली"), "dilli");
+        assert_eq!(romanize("मुंबई"), "mumbai");
+        assert_eq!(romanize("हिन्दी"), "hindi");
+        assert_eq!(romanize("ज़िंदगी"), "zindagi");
+        // The only vowel isn't dropped
+        assert_eq!(romanize("न"), "na");
+    }
+
+    #[test]
+    fn test_romanized_spans() {
+        let text = "Visit Москва, नमस्ते। Rust";
+        let spans = romanized_spans(text);
+        let words: Vec<(&str, &str)> = spans
+            .iter()
+            .map(|(word, start, end)| (word.as_str(), &text[*start..*end]))
+            .collect();
+        assert_eq!(words, [("moskva", "Москва"), ("namaste", "नमस्ते")]);
+    }
+
+    #[test]
+    fn test_with_romanized() {
+        let text = "Москва नमस्ते rust";
+        let spans = vec![("москва".to_string(), 0, 12), ("rust".to_string(), 32, 36)];
+        let words: Vec<String> = with_romanized(text, spans)
+            .into_iter()
+            .map(|(word, _, _)| word)
+            .collect();
+        assert_eq!(words, ["moskva", "namaste", "rust"]);
+    }
+
+    /// A searcher over `texts`, one doc each, with Cyrillic, Greek and
+    /// Devanagari words indexed romanized too.
+    fn searcher(texts: &[&str]) -> TierSearcher {
+        let docs: Vec<(&str, u8)> = texts.iter().map(|&t| (t, 5)).collect();
+        let (mut layer, _) = build_layer(&docs, true, &[], |text| {
+            let mut words = word_spans(text);
+            words.extend(romanized_spans(text));
+            words
+        });
+        layer.set_transliterated();
+        load_searcher(layer)
+    }
+
+    fn doc_ids(searcher: &TierSearcher, query: &str) -> Vec<usize> {
+        let mut ids: Vec<usize> = searcher
+            .search_with_options(query, 10, SearchOptions::default())
+            .iter()
+            .map(|r| r.doc_id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_either_script_finds_the_document() {
+        let searcher = searcher(&[
+            "Москва столица России",
+            "The Moskva river",
+            "Αθήνα και Πειραιάς",
+            "नमस्ते दुनिया",
+        ]);
+        let ids = |query: &str| doc_ids(&searcher, query);
+
+        assert_eq!(ids("moskva"), vec![0, 1]);
+        assert_eq!(ids("Москва"), vec![0, 1]);
+        assert_eq!(ids("athina"), vec![2]);
+        assert_eq!(ids("namaste"), vec![3]);
+        assert_eq!(ids("नमस्ते"), vec![3]);
+        // Romanized Devanagari words still hold their positions
+        assert_eq!(ids("\"namaste duniya\""), vec![3]);
+    }
+}
@@ -87,21 +87,40 @@ mod runtime;
 pub use runtime::deno as deno_runtime;
 
 // Re-exports for public API
-pub use index::fst::{build_fst_index, FstIndex};
+pub use index::fst::{build_fst_index, build_fst_index_with_languages, FstIndex};
 pub use index::hybrid::{build_hybrid_index, build_hybrid_index_parallel};
 pub use search::hybrid::{search_exact, search_expanded, search_fuzzy, search_hybrid};
 pub use index::{
     build_index, is_suffix_array_sorted, suffix_at,
-    build_inverted_index, build_inverted_index_parallel, build_unified_index, is_stop_word,
+    build_inverted_index, build_inverted_index_parallel, build_inverted_index_with_languages,
+    build_unified_index, is_stop_word, is_stop_word_in,
     select_index_mode, IndexThresholds,
 };
 pub use fuzzy::levenshtein_within;
 pub use fuzzy::dfa::{ParametricDFA, QueryMatcher, MAX_K, NUM_CHAR_CLASSES};
+pub use fuzzy::policy::Fuzziness;
+pub use fuzzy::phonetic::{phonetic_key, PhoneticIndex};
+pub use fuzzy::trie::VocabTrie;
+pub use index::decompound::Decompounder;
+pub use index::language::Language;
+pub use index::segment::segment;
+pub use index::stem::{stem, StemIndex};
+pub use index::synonyms::Synonyms;
+pub use index::tokenizer::Tokenizer;
+pub use index::translit::romanize;
 pub use scoring::ranking::compare_results;
 pub use scoring::{field_type_score, get_field_type};
 pub use search::{search, search_unified};
+pub use search::complete::{Completion, CompletionOptions};
+pub use search::facets::{FacetCount, FacetCounts, FacetedResults};
+pub use search::filter::SearchFilter;
+pub use search::paging::SearchPage;
+pub use search::query::QueryNode;
+pub use search::snippet::{Snippet, DEFAULT_SNIPPET_CHARS};
+pub use search::suggest::Suggestion;
 pub use search::tiered::{
-    fuzzy_search_vocabulary, prefix_search_vocabulary, FuzzyMatch, SearchResult as TierSearchResult,
+    fuzzy_prefix_search_vocabulary, fuzzy_search_vocabulary, infix_search_vocabulary,
+    prefix_search_vocabulary, FuzzyMatch, ResolvedTerm, SearchResult as TierSearchResult,
     TierSearcher,
 };
 pub use types::{
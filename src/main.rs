@@ -12,6 +12,9 @@
 //!
 //! # Search with tiered results (exact → prefix → fuzzy)
 //! sorex search ./search/index-*.sorex "query" --limit 10
+//!
+//! # Boolean syntax: OR, -exclude, "exact", (grouping)
+//! sorex search ./search/index-*.sorex "kernel -deprecated"
 //! ```
 //!
 //! The CLI also supports benchmarking with `--bench` for statistical analysis
@@ -119,7 +122,8 @@ fn inspect_sorex_file(path: &str) {
     let (hdr, header_size) = if version >= 3 {
         let h =
             SorexHeader::read(&mut std::io::Cursor::new(&bytes)).expect("failed to read header");
-        (h, SorexHeader::SIZE)
+        let size = h.size();
+        (h, size)
     } else {
         let mut cursor = std::io::Cursor::new(&bytes);
         let mut magic = [0u8; 4];
@@ -144,6 +148,7 @@ fn inspect_sorex_file(path: &str) {
             docs_len: 0,
             wasm_len: 0,
             dict_table_len: 0,
+            ext_len: 0,
         };
         (h, 36)
     };
@@ -279,7 +284,8 @@ fn inspect_sorex_file(path: &str) {
 
     // v12 layout order (dependency-optimized for streaming decode):
     // 1. HEADER, 2. WASM, 3. VOCABULARY, 4. DICT_TABLES, 5. POSTINGS,
-    // 6. SUFFIX_ARRAY, 7. DOCS, 8. SECTION_TABLE, 9. SKIP_LISTS, 10. LEV_DFA, 11. FOOTER
+    // 6. SUFFIX_ARRAY, 7. DOCS, 8. SECTION_TABLE, 9. SKIP_LISTS, 10. LEV_DFA,
+    // 11. EXTENSIONS, 12. FOOTER
     let mut sections = vec![Section {
         name: "HEADER",
         size: header_size,
@@ -367,6 +373,16 @@ fn inspect_sorex_file(path: &str) {
         });
     }
 
+    // v14+: EXTENSIONS (trie, phonetic keys, stems, synonyms)
+    if hdr.version >= 14 && hdr.ext_len > 0 {
+        sections.push(Section {
+            name: "EXTENSIONS",
+            size: hdr.ext_len as usize,
+            raw_size: hdr.ext_len as usize,
+            technique: "EXT",
+        });
+    }
+
     // FOOTER (always last)
     sections.push(Section {
         name: "FOOTER",
@@ -643,44 +659,72 @@ fn search_sorex_file(path: &str, query: &str, limit: usize) {
     let searcher = TierSearcher::from_layer(layer).expect("failed to build searcher");
     let load_time = load_start.elapsed();
 
-    // Warm up all tiers (prime caches and branch predictor)
-    for _ in 0..10 {
-        let _ = searcher.search_tier1_exact(query, limit);
-        let _ = searcher.search_tier2_prefix(query, &HashSet::new(), limit);
-        let _ = searcher.search_tier3_fuzzy(query, &HashSet::new(), limit);
-    }
+    // Boolean queries (OR, -term, quotes, parens) and queries against a code
+    // index have no tier split: they're evaluated in one pass, with the whole
+    // time reported against T1
+    let boolean = searcher.evaluates_in_one_pass(query);
+
+    let (results, tier_counts, tier_times) = if boolean {
+        for _ in 0..10 {
+            let _ = searcher.search(query, limit);
+        }
 
-    // Tier 1: Exact match (now with hot cache)
-    let t1_start = Instant::now();
-    let t1_results = searcher.search_tier1_exact(query, limit);
-    let t1_time = t1_start.elapsed();
-    let t1_count = t1_results.len();
-
-    // Tier 2: Prefix match (exclude T1 results)
-    let t1_ids: HashSet<usize> = t1_results.iter().map(|r| r.doc_id).collect();
-    let t2_start = Instant::now();
-    let t2_results = searcher.search_tier2_prefix(query, &t1_ids, limit);
-    let t2_time = t2_start.elapsed();
-    let t2_count = t2_results.len();
-
-    // Tier 3: Fuzzy match (exclude T1 and T2 results)
-    let mut exclude_ids = t1_ids.clone();
-    exclude_ids.extend(t2_results.iter().map(|r| r.doc_id));
-    let t3_start = Instant::now();
-    let t3_results = searcher.search_tier3_fuzzy(query, &exclude_ids, limit);
-    let t3_time = t3_start.elapsed();
-    let t3_count = t3_results.len();
+        let start = Instant::now();
+        let results = searcher.search(query, limit);
+        let elapsed = start.elapsed();
 
-    let total_search_time = t1_time + t2_time + t3_time;
+        let count = |tier: u8| results.iter().filter(|r| r.tier == tier).count();
+        let counts = [count(1), count(2), count(3)];
+        let zero = std::time::Duration::ZERO;
+        (results, counts, [elapsed, zero, zero])
+    } else {
+        // Warm up all tiers (prime caches and branch predictor)
+        for _ in 0..10 {
+            let _ = searcher.search_tier1_exact(query, limit);
+            let _ = searcher.search_tier2_prefix(query, &HashSet::new(), limit);
+            let _ = searcher.search_tier3_fuzzy(query, &HashSet::new(), limit);
+        }
+
+        // Tier 1: Exact match (now with hot cache)
+        let t1_start = Instant::now();
+        let t1_results = searcher.search_tier1_exact(query, limit);
+        let t1_time = t1_start.elapsed();
+        let t1_count = t1_results.len();
 
-    // Merge and sort results
-    let mut results: Vec<_> = t1_results
-        .into_iter()
-        .chain(t2_results)
-        .chain(t3_results)
-        .collect();
-    results.sort_by(|a, b| compare_results(a, b, searcher.docs()));
-    results.truncate(limit);
+        // Tier 2: Prefix match (exclude T1 results)
+        let t1_ids: HashSet<usize> = t1_results.iter().map(|r| r.doc_id).collect();
+        let t2_start = Instant::now();
+        let t2_results = searcher.search_tier2_prefix(query, &t1_ids, limit);
+        let t2_time = t2_start.elapsed();
+        let t2_count = t2_results.len();
+
+        // Tier 3: Fuzzy match (exclude T1 and T2 results)
+        let mut exclude_ids = t1_ids.clone();
+        exclude_ids.extend(t2_results.iter().map(|r| r.doc_id));
+        let t3_start = Instant::now();
+        let t3_results = searcher.search_tier3_fuzzy(query, &exclude_ids, limit);
+        let t3_time = t3_start.elapsed();
+        let t3_count = t3_results.len();
+
+        // Merge and sort results
+        let mut results: Vec<_> = t1_results
+            .into_iter()
+            .chain(t2_results)
+            .chain(t3_results)
+            .collect();
+        results.sort_by(|a, b| compare_results(a, b, searcher.docs()));
+        results.truncate(limit);
+
+        (
+            results,
+            [t1_count, t2_count, t3_count],
+            [t1_time, t2_time, t3_time],
+        )
+    };
+
+    let [t1_count, t2_count, t3_count] = tier_counts;
+    let [t1_time, t2_time, t3_time] = tier_times;
+    let total_search_time = t1_time + t2_time + t3_time;
 
     // Display header
     println!();
@@ -690,6 +734,9 @@ fn search_sorex_file(path: &str, query: &str, limit: usize) {
     row_double(&format!("  File:   {}", truncate_path(path, 57)));
     row_double(&format!("  Query:  \"{}\"", query));
     row_double(&format!("  Limit:  {}", limit));
+    if boolean {
+        row_double("  Mode:   boolean (single pass, timed as T1)");
+    }
     double_footer();
     println!();
 
@@ -25,13 +25,24 @@
 #[cfg(feature = "rayon")]
 use crate::binary::IncrementalLoader;
 use crate::binary::LoadedLayer;
+use crate::fuzzy::dfa::MAX_K;
+use crate::fuzzy::policy::Fuzziness;
+use crate::index::language::Language;
 use crate::scoring::ranking::compare_results;
+use crate::search::complete::{Completion, CompletionOptions};
 use crate::search::dedup::ResultMerger;
+use crate::search::facets::FacetCounts;
+use crate::search::snippet::{Snippet, DEFAULT_SNIPPET_CHARS};
+use crate::search::suggest::Suggestion;
 #[cfg(feature = "rayon")]
 use crate::search::tiered::UIMessage;
-use crate::search::tiered::{SearchOptions, SearchResult, TierSearcher};
-use crate::types::SearchDoc;
+use crate::search::tiered::{
+    SearchFilter, SearchOptions, SearchResult, TierSearcher, SYNONYM_PENALTY,
+};
+use crate::types::{FieldType, SearchDoc};
+use crate::util::normalize::fold;
 use js_sys::Function;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_wasm_bindgen::to_value;
 use std::rc::Rc;
@@ -55,6 +66,38 @@ struct JsSearchResult {
     match_type: u8,
     score: f64,
     matched_term: Option<String>,
+    /// Stored text around the match (indexes built with `storeText`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snippet: Option<JsSnippet>,
+}
+
+/// Snippet for JavaScript consumption.
+///
+/// Highlight offsets are UTF-16 code units, so they index JS strings directly
+/// (`text.slice(start, end)`).
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsSnippet {
+    text: String,
+    highlights: Vec<(usize, usize)>,
+    clipped_start: bool,
+    clipped_end: bool,
+}
+
+impl From<Snippet> for JsSnippet {
+    fn from(snippet: Snippet) -> Self {
+        let utf16 = |byte: usize| snippet.text[..byte].encode_utf16().count();
+        Self {
+            highlights: snippet
+                .highlights
+                .iter()
+                .map(|&(start, end)| (utf16(start), utf16(end)))
+                .collect(),
+            text: snippet.text,
+            clipped_start: snippet.clipped_start,
+            clipped_end: snippet.clipped_end,
+        }
+    }
 }
 
 impl JsSearchResult {
@@ -85,6 +128,7 @@ impl JsSearchResult {
             match_type: r.match_type.to_u8(),
             score: r.score,
             matched_term,
+            snippet: None,
         }
     }
 }
@@ -102,6 +146,86 @@ struct TierTimingResult {
     t3_time_us: f64,
 }
 
+/// Results page plus facet counts, for JavaScript consumption.
+#[derive(Serialize)]
+struct FacetedSearchResult {
+    results: Vec<JsSearchResult>,
+    facets: FacetCounts,
+}
+
+/// A completion for the word being typed, for JavaScript consumption.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsCompletion {
+    term: String,
+    doc_count: usize,
+    /// 0=title … 4=content, like `SearchResult.matchType`
+    match_type: u8,
+    infix: bool,
+}
+
+impl From<Completion> for JsCompletion {
+    fn from(completion: Completion) -> Self {
+        Self {
+            term: completion.term,
+            doc_count: completion.doc_count,
+            match_type: completion.match_type.to_u8(),
+            infix: completion.infix,
+        }
+    }
+}
+
+/// Completion options for JavaScript consumption.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct JsCompletionOptions {
+    /// Also complete terms containing the fragment mid-word.
+    #[serde(default)]
+    infix: bool,
+}
+
+impl From<JsCompletionOptions> for CompletionOptions {
+    fn from(js: JsCompletionOptions) -> Self {
+        Self { infix: js.infix }
+    }
+}
+
+/// A "did you mean" suggestion, for JavaScript consumption.
+///
+/// Like snippet highlights, `corrected` offsets are UTF-16 code units.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsSuggestion {
+    query: String,
+    corrected: Vec<(usize, usize)>,
+    doc_count: usize,
+}
+
+impl From<Suggestion> for JsSuggestion {
+    fn from(suggestion: Suggestion) -> Self {
+        let utf16 = |byte: usize| suggestion.query[..byte].encode_utf16().count();
+        Self {
+            corrected: suggestion
+                .corrected
+                .iter()
+                .map(|&(start, end)| (utf16(start), utf16(end)))
+                .collect(),
+            query: suggestion.query,
+            doc_count: suggestion.doc_count,
+        }
+    }
+}
+
+/// One page of results, for JavaScript consumption.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsSearchPage {
+    results: Vec<JsSearchResult>,
+    offset: usize,
+    total: usize,
+    has_more: bool,
+}
+
 /// Search options for JavaScript consumption.
 ///
 /// Passed to search methods to configure behavior.
@@ -112,16 +236,165 @@ struct JsSearchOptions {
     /// When false, returns multiple results per document if different sections match.
     #[serde(default = "default_dedup_sections")]
     dedup_sections: bool,
+    /// Restrict the query to one field: "title", "heading" or "content".
+    #[serde(default)]
+    field: Option<FieldType>,
+    /// Metadata filter applied before the limit.
+    #[serde(default)]
+    filter: JsSearchFilter,
+    /// Snippet length in characters when the index stores text (default 160, 0 = off).
+    #[serde(default)]
+    snippet_length: Option<usize>,
+    /// Skip this many ranked results (for page 2 onward).
+    #[serde(default)]
+    offset: usize,
+    /// Typo tolerance for this query, 0-3 (default: the index's).
+    #[serde(default)]
+    max_edit_distance: Option<u8>,
+    /// How term length sets each term's typo budget (default: "adaptive").
+    #[serde(default)]
+    fuzziness: Option<JsFuzziness>,
+    /// The last term is still being typed: fuzzy-match it as a prefix.
+    #[serde(default)]
+    as_you_type: bool,
+    /// Fall back to terms that sound alike (indexes built with `phonetic`).
+    #[serde(default)]
+    phonetic: bool,
+    /// Language the query is in ("en", "de", ...): its stop words and
+    /// stemmer. Unknown languages are ignored.
+    #[serde(default)]
+    lang: Option<String>,
+    /// Score of synonym matches relative to exact ones (default 0.8, 0 = off).
+    #[serde(default)]
+    synonym_penalty: Option<f64>,
+}
+
+/// Fuzziness policy for JavaScript consumption.
+///
+/// `"adaptive"`, `"uniform"`, or ascending minimum term lengths for 1, 2 and
+/// 3 edits (`[4, 6, 12]` is the adaptive table). Parsing rejects longer or
+/// unsorted tables.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsFuzziness {
+    Preset(JsFuzzinessPreset),
+    MinChars(Vec<usize>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum JsFuzzinessPreset {
+    Adaptive,
+    Uniform,
+}
+
+impl From<JsFuzziness> for Fuzziness {
+    fn from(js: JsFuzziness) -> Self {
+        match js {
+            JsFuzziness::Preset(JsFuzzinessPreset::Adaptive) => Fuzziness::ADAPTIVE,
+            JsFuzziness::Preset(JsFuzzinessPreset::Uniform) => Fuzziness::Uniform,
+            // Levels left out are never reached
+            JsFuzziness::MinChars(lengths) => {
+                let mut min_chars = [usize::MAX; MAX_K as usize];
+                for (slot, len) in min_chars.iter_mut().zip(lengths) {
+                    *slot = len;
+                }
+                Fuzziness::ByLength(min_chars)
+            }
+        }
+    }
+}
+
+/// Metadata filter for JavaScript consumption.
+///
+/// Every condition must hold; omitted conditions don't filter.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct JsSearchFilter {
+    /// Category is one of these
+    #[serde(default)]
+    category: Vec<String>,
+    /// Has every one of these tags
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Author equals this
+    #[serde(default)]
+    author: Option<String>,
+    /// Document type is one of these
+    #[serde(default, rename = "type")]
+    kind: Vec<String>,
+    /// Href starts with this
+    #[serde(default)]
+    href_prefix: Option<String>,
+}
+
+impl From<JsSearchFilter> for SearchFilter {
+    fn from(js: JsSearchFilter) -> Self {
+        SearchFilter {
+            categories: js.category,
+            tags: js.tags,
+            author: js.author,
+            kinds: js.kind,
+            href_prefix: js.href_prefix,
+        }
+    }
 }
 
 fn default_dedup_sections() -> bool {
     true
 }
 
+/// An options object from JavaScript, checked once parsed.
+trait JsOptions: DeserializeOwned + Default {
+    /// Reject values that parse but would search wrong.
+    fn check(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl JsOptions for JsSearchOptions {
+    fn check(&self) -> Result<(), String> {
+        // `Fuzziness::ByLength` counts thresholds in order: an unsorted table
+        // would give the wrong budget
+        if let Some(JsFuzziness::MinChars(lengths)) = &self.fuzziness {
+            if lengths.len() > MAX_K as usize || lengths.windows(2).any(|w| w[0] > w[1]) {
+                return Err(format!(
+                    "fuzziness needs at most {} ascending term lengths, got {:?}",
+                    MAX_K, lengths
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl JsOptions for JsCompletionOptions {}
+
+/// Parse JS options, using defaults if undefined/null.
+fn parse_options<T: JsOptions>(options: JsValue) -> Result<T, JsValue> {
+    if options.is_undefined() || options.is_null() {
+        return Ok(T::default());
+    }
+    let opts: T = serde_wasm_bindgen::from_value(options)
+        .map_err(|e| JsValue::from_str(&format!("Invalid options: {}", e)))?;
+    opts.check()
+        .map_err(|e| JsValue::from_str(&format!("Invalid options: {}", e)))?;
+    Ok(opts)
+}
+
 impl From<JsSearchOptions> for SearchOptions {
     fn from(js: JsSearchOptions) -> Self {
         SearchOptions {
             dedup_sections: js.dedup_sections,
+            field: js.field,
+            filter: js.filter.into(),
+            offset: js.offset,
+            max_edit_distance: js.max_edit_distance,
+            fuzziness: js.fuzziness.map(Fuzziness::from).unwrap_or_default(),
+            as_you_type: js.as_you_type,
+            phonetic: js.phonetic,
+            language: js.lang.as_deref().and_then(Language::parse),
+            synonym_penalty: js.synonym_penalty.unwrap_or(SYNONYM_PENALTY),
         }
     }
 }
@@ -157,6 +430,12 @@ impl SorexSearcher {
         self.searcher.vocabulary().len()
     }
 
+    /// True if the index stores document text, so results carry snippets.
+    #[wasm_bindgen(js_name = "hasText")]
+    pub fn has_text(&self) -> bool {
+        self.searcher.has_text()
+    }
+
     /// Progressive search with callbacks after each tier.
     ///
     /// - `on_update`: Called after each tier (1-3 times) with current results
@@ -164,6 +443,10 @@ impl SorexSearcher {
     ///
     /// Each callback receives the full deduplicated result set (not deltas).
     ///
+    /// Supports boolean syntax: `a OR b`, `-term`/`NOT term`, `+term`,
+    /// `"exact"` (no prefix/fuzzy), and parentheses. Boolean queries are
+    /// evaluated in one pass, so `on_update` fires once.
+    ///
     /// ```js
     /// searcher.search(query, 10, onUpdate, onFinish);
     /// ```
@@ -185,7 +468,17 @@ impl SorexSearcher {
             return Ok(());
         }
 
-        let query_lower = query.to_lowercase();
+        // Boolean queries (OR, -term, quotes) and code queries are evaluated
+        // in one pass
+        if self.searcher.evaluates_in_one_pass(query) {
+            let results = self.to_js_results(self.searcher.search(query, limit));
+            let js_results = to_value(&results).map_err(|e| JsValue::from_str(&e.to_string()))?;
+            on_update.call1(&JsValue::NULL, &js_results)?;
+            on_finish.call1(&JsValue::NULL, &js_results)?;
+            return Ok(());
+        }
+
+        let query_lower = fold(query);
         let docs = self.searcher.docs();
 
         // Use ResultMerger for type-safe doc_id-only deduplication
@@ -232,8 +525,15 @@ impl SorexSearcher {
     /// # Arguments
     /// * `query` - Search query
     /// * `limit` - Maximum results (default: 10)
-    /// * `options` - Search options object: `{ dedupSections: boolean }`
+    /// * `options` - Search options object: `{ dedupSections: boolean, field: string, filter: object, snippetLength: number, offset: number }`
     ///   - `dedupSections`: Whether to deduplicate sections within a document (default: true)
+    ///   - `field`: Only match in `"title"`, `"heading"` or `"content"` (default: all)
+    ///   - `filter`: Metadata conditions, checked before the limit:
+    ///     `{ category: string[], tags: string[], author: string, type: string[], hrefPrefix: string }`
+    ///   - `snippetLength`: Snippet size in characters for indexes built with
+    ///     `storeText` (default: 160, 0 = no snippets)
+    ///   - `offset`: Skip this many ranked results (default: 0). Every call
+    ///     pages through the same ranking as `searchPage`, offset 0 included
     ///
     /// ```js
     /// // Default behavior (section dedup enabled)
@@ -241,6 +541,18 @@ impl SorexSearcher {
     ///
     /// // Return all matching sections per document
     /// searcher.searchSyncWithOptions("kernel", 10, { dedupSections: false });
+    ///
+    /// // Titles only (same as "title:kernel")
+    /// searcher.searchSyncWithOptions("kernel", 10, { field: "title" });
+    ///
+    /// // Only API reference pages tagged "async"
+    /// searcher.searchSyncWithOptions("spawn", 10, {
+    ///   filter: { hrefPrefix: "/api/", tags: ["async"] },
+    /// });
+    ///
+    /// // Snippets: { text, highlights: [[start, end], ...], clippedStart, clippedEnd }
+    /// const [hit] = searcher.searchSyncWithOptions("borrow", 1, { snippetLength: 80 });
+    /// hit.snippet?.highlights.map(([s, e]) => hit.snippet.text.slice(s, e));
     /// ```
     #[wasm_bindgen(js_name = "searchSyncWithOptions")]
     pub fn search_sync_with_options(
@@ -254,19 +566,124 @@ impl SorexSearcher {
             return to_value(&Vec::<JsSearchResult>::new()).map_err(|e| e.to_string().into());
         }
 
-        // Parse options, using defaults if undefined/null
-        let opts: JsSearchOptions = if options.is_undefined() || options.is_null() {
-            JsSearchOptions::default()
-        } else {
-            serde_wasm_bindgen::from_value(options)
-                .map_err(|e| JsValue::from_str(&format!("Invalid options: {}", e)))?
+        let opts: JsSearchOptions = parse_options(options)?;
+        let snippet_length = opts.snippet_length;
+        let results = self.searcher.search_with_options(query, limit, opts.into());
+        let output = self.to_js_results_with_snippets(results, query, snippet_length);
+        to_value(&output).map_err(|e| e.to_string().into())
+    }
+
+    /// Search plus facet counts over every matching document (blocking).
+    ///
+    /// Returns `{ results, facets }`. `results` is the same page
+    /// `searchSyncWithOptions` returns; `facets` counts category, author,
+    /// each tag and document type across all matches, not just the page:
+    ///
+    /// ```js
+    /// const { results, facets } = searcher.searchWithFacets("rust", 10);
+    /// // facets.total === 15
+    /// // facets.categories === [{ value: "engineering", count: 12 }, { value: "adventures", count: 3 }]
+    /// ```
+    ///
+    /// Accepts the same options as `searchSyncWithOptions`.
+    #[wasm_bindgen(js_name = "searchWithFacets")]
+    pub fn search_with_facets(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+        options: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let limit = limit.unwrap_or(10).min(10000);
+        let opts: JsSearchOptions = parse_options(options)?;
+        let snippet_length = opts.snippet_length;
+        let faceted = self.searcher.search_with_facets(query, limit, opts.into());
+        let output = FacetedSearchResult {
+            results: self.to_js_results_with_snippets(faceted.results, query, snippet_length),
+            facets: faceted.facets,
         };
+        to_value(&output).map_err(|e| e.to_string().into())
+    }
 
-        let results = self.searcher.search_with_options(query, limit, opts.into());
-        let output = self.to_js_results(results);
+    /// One page of results plus the total match count (blocking).
+    ///
+    /// Returns `{ results, offset, total, hasMore }`. Pages are cut from one
+    /// ranking of every match, so stepping `offset` by `limit` never repeats
+    /// or skips a result:
+    ///
+    /// ```js
+    /// const page = searcher.searchPage("rust", 10, { offset: 20 });
+    /// // page.total === 57, page.hasMore === true
+    /// ```
+    ///
+    /// Accepts the same options as `searchSyncWithOptions`.
+    #[wasm_bindgen(js_name = "searchPage")]
+    pub fn search_page(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+        options: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let limit = limit.unwrap_or(10).min(10000);
+        let opts: JsSearchOptions = parse_options(options)?;
+        let snippet_length = opts.snippet_length;
+        let page = self.searcher.search_page(query, limit, opts.into());
+        let output = JsSearchPage {
+            offset: page.offset,
+            total: page.total,
+            has_more: page.has_more(),
+            results: self.to_js_results_with_snippets(page.results, query, snippet_length),
+        };
         to_value(&output).map_err(|e| e.to_string().into())
     }
 
+    /// Vocabulary terms completing the last word of `input`, for a search
+    /// box's dropdown.
+    ///
+    /// Returns `[{ term, docCount, matchType, infix }]`, best first: title
+    /// words before body words, then by document count. Pass
+    /// `{ infix: true }` to also offer terms containing the word mid-term:
+    ///
+    /// ```js
+    /// searcher.complete("auth", 5);
+    /// // [{ term: "authentication", docCount: 12, ... }, { term: "author", ... }]
+    /// ```
+    #[wasm_bindgen]
+    pub fn complete(
+        &self,
+        input: &str,
+        limit: Option<usize>,
+        options: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let limit = limit.unwrap_or(10).min(10000);
+        let opts: JsCompletionOptions = parse_options(options)?;
+        let completions: Vec<JsCompletion> = self
+            .searcher
+            .complete(input, limit, opts.into())
+            .into_iter()
+            .map(JsCompletion::from)
+            .collect();
+        to_value(&completions).map_err(|e| e.to_string().into())
+    }
+
+    /// "Did you mean" for a query that found little or nothing.
+    ///
+    /// Returns `{ query, corrected, docCount }`, or `null` when every word
+    /// is fine as typed or no correction finds anything:
+    ///
+    /// ```js
+    /// const s = searcher.suggest("pyhton tutoral");
+    /// // s.query === "python tutorial", s.corrected === [[0, 6], [7, 15]]
+    /// ```
+    #[wasm_bindgen]
+    pub fn suggest(&self, query: &str) -> Result<JsValue, JsValue> {
+        match self.searcher.suggest(query) {
+            Some(suggestion) => {
+                to_value(&JsSuggestion::from(suggestion)).map_err(|e| e.to_string().into())
+            }
+            None => Ok(JsValue::NULL),
+        }
+    }
+
     /// Three-tier search with per-tier timing breakdown.
     ///
     /// Returns an object with:
@@ -300,6 +717,25 @@ impl SorexSearcher {
             return to_value(&empty).map_err(|e| e.to_string().into());
         }
 
+        // Boolean and code queries have no tier split: the whole evaluation is
+        // reported as T1 time
+        if self.searcher.evaluates_in_one_pass(query) {
+            let start = Date::now();
+            let results = self.searcher.search(query, limit);
+            let elapsed = Date::now() - start;
+            let count = |tier: u8| results.iter().filter(|r| r.tier == tier).count();
+            let output = TierTimingResult {
+                t1_count: count(1),
+                t2_count: count(2),
+                t3_count: count(3),
+                t1_time_us: elapsed * 1000.0,
+                t2_time_us: 0.0,
+                t3_time_us: 0.0,
+                results: self.to_js_results(results),
+            };
+            return to_value(&output).map_err(|e| e.to_string().into());
+        }
+
         // T1: Exact match
         let t1_start = Date::now();
         let t1_results = self.searcher.search_tier1_exact(query, limit);
@@ -447,20 +883,46 @@ impl SorexSearcher {
 
     /// Convert internal results to JS-serializable format.
     fn to_js_results(&self, results: Vec<SearchResult>) -> Vec<JsSearchResult> {
+        results
+            .iter()
+            .filter_map(|r| self.to_js_result(r))
+            .collect()
+    }
+
+    /// Like `to_js_results`, with a snippet per result if the index stores text.
+    fn to_js_results_with_snippets(
+        &self,
+        results: Vec<SearchResult>,
+        query: &str,
+        snippet_length: Option<usize>,
+    ) -> Vec<JsSearchResult> {
+        let max_chars = snippet_length.unwrap_or(DEFAULT_SNIPPET_CHARS);
+        if max_chars == 0 || !self.searcher.has_text() {
+            return self.to_js_results(results);
+        }
         results
             .iter()
             .filter_map(|r| {
-                self.searcher.docs().get(r.doc_id).map(|doc| {
-                    JsSearchResult::from_result(
-                        r,
-                        doc,
-                        self.searcher.section_table(),
-                        self.searcher.vocabulary(),
-                    )
-                })
+                let mut js = self.to_js_result(r)?;
+                js.snippet = self
+                    .searcher
+                    .snippet(r, query, max_chars)
+                    .map(JsSnippet::from);
+                Some(js)
             })
             .collect()
     }
+
+    fn to_js_result(&self, r: &SearchResult) -> Option<JsSearchResult> {
+        self.searcher.docs().get(r.doc_id).map(|doc| {
+            JsSearchResult::from_result(
+                r,
+                doc,
+                self.searcher.section_table(),
+                self.searcher.vocabulary(),
+            )
+        })
+    }
 }
 
 // ============================================================================
@@ -499,6 +961,9 @@ pub struct JsSectionOffsets {
     /// Levenshtein DFA section (start, end)
     pub lev_dfa_start: usize,
     pub lev_dfa_end: usize,
+    /// Extensions section (start, end)
+    pub extensions_start: usize,
+    pub extensions_end: usize,
     /// Total content size (before footer)
     pub content_size: usize,
     /// Header info
@@ -587,6 +1052,8 @@ impl SorexIncrementalLoader {
             skip_lists_end: offsets.skip_lists.1,
             lev_dfa_start: offsets.lev_dfa.0,
             lev_dfa_end: offsets.lev_dfa.1,
+            extensions_start: offsets.extensions.0,
+            extensions_end: offsets.extensions.1,
             content_size: offsets.content_size(),
             term_count: self.term_count,
             doc_count: loader.header().map(|h| h.doc_count).unwrap_or(0),
@@ -693,6 +1160,17 @@ impl SorexIncrementalLoader {
         Ok(())
     }
 
+    /// Store extensions section bytes. Non-blocking.
+    #[wasm_bindgen(js_name = "loadExtensions")]
+    pub fn load_extensions(&self, bytes: &[u8]) -> Result<(), JsValue> {
+        let loader = self
+            .loader
+            .as_ref()
+            .ok_or_else(|| JsValue::from_str("Loader already finalized"))?;
+        loader.load_extensions(bytes.to_vec());
+        Ok(())
+    }
+
     /// Check if all sections are loaded (non-blocking).
     #[wasm_bindgen(js_name = "isComplete")]
     pub fn is_complete(&self) -> bool {
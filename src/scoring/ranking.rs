@@ -27,7 +27,8 @@ use std::cmp::Ordering;
 /// 1. **Match type** - bucket hierarchy dominates (Title > Section > ... > Content)
 /// 2. **Score** - only within the same bucket (higher wins)
 /// 3. **Title** - alphabetical tiebreaker for determinism
-/// 4. **Doc ID** - final tiebreaker when everything else is equal
+/// 4. **Doc ID** - tiebreaker when everything else is equal
+/// 5. **Section** - orders sections of one document (when not deduplicated)
 ///
 /// The key insight: a title match at score 50 beats a content match at score 100.
 /// Buckets are impermeable - scores can't cross bucket boundaries.
@@ -55,8 +56,11 @@ pub fn compare_results(a: &SearchResult, b: &SearchResult, docs: &[SearchDoc]) -
                     let b_title = docs.get(b.doc_id).map(|d| d.title.as_str()).unwrap_or("");
                     match a_title.cmp(b_title) {
                         Ordering::Equal => {
-                            // Final tie-breaker: doc_id for absolute determinism
-                            a.doc_id.cmp(&b.doc_id)
+                            // Final tie-breakers: doc_id, then section, for
+                            // absolute determinism (stable pages)
+                            a.doc_id
+                                .cmp(&b.doc_id)
+                                .then(a.section_idx.cmp(&b.section_idx))
                         }
                         ord => ord,
                     }
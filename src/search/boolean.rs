@@ -0,0 +1,468 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Boolean query evaluation over the three-tier searcher.
+//!
+//! Plain word lists never get here; they take the T1 → T2 → T3 pipeline in
+//! `tiered.rs`. This is the path for queries with `OR`, exclusions, quotes,
+//! or grouping, parsed into a [`QueryNode`] by `query.rs`.
+//!
+//! Each term is resolved per document at its best tier: a document that
+//! contains "kernel" exactly is a T1 hit for that term, one that only contains
+//! "kernels" is a T2 hit, "kernal" a T3 hit. Other forms of the word that the
+//! tier pipeline's Tier 1 accepts ("data base" for "database") are T1 hits
+//! too. Quoted terms stop after T1, and
+//! quoted phrases and `NEAR/k` are matched by word position (see `phrase.rs`
+//! and `proximity.rs`). Field operators (`title:`, `heading:`, `content:`)
+//! scope everything beneath them to postings from that field; the innermost
+//! field wins.
+//!
+//! Nodes then combine per-document hits:
+//!
+//! - **AND**: intersect documents, sum section scores, take the *worst* tier
+//!   (the result is only as good as its weakest term, same as multi-term T3)
+//! - **OR**: union documents, sum section scores, take the *best* tier
+//! - **NOT**: inside an AND, drop every document the child matches exactly
+//!   (`rust -test` keeps documents that only say "tests" or "text")
+//!
+//! A `NOT` can't produce documents on its own (there's no "all documents"
+//! posting list to subtract from), so a query made only of exclusions returns
+//! nothing, and negated operands of an `OR` are ignored.
+
+use super::proximity::{near_window, proximity_boost};
+use super::query::QueryNode;
+use super::tiered::{
+    finalize_results, prefix_search_vocabulary, EditBudget, SearchOptions, SearchResult,
+    TierSearcher,
+};
+use crate::index::is_stop_word_in;
+use crate::types::{FieldType, MatchType};
+use std::collections::{HashMap, HashSet};
+
+/// Best match for one (doc, section) pair within a node.
+#[derive(Debug, Clone, Copy)]
+struct SectionHit {
+    score: f64,
+    match_type: MatchType,
+    matched_term: u32,
+}
+
+impl SectionHit {
+    /// Merge another node's hit on the same section: scores add, best
+    /// match_type (and its term) wins.
+    fn combine(&mut self, other: SectionHit) {
+        self.score += other.score;
+        if other.match_type < self.match_type {
+            self.match_type = other.match_type;
+            self.matched_term = other.matched_term;
+        }
+    }
+}
+
+/// All hits for one document within a node.
+#[derive(Debug, Clone)]
+struct DocHits {
+    tier: u8,
+    sections: HashMap<u32, SectionHit>,
+}
+
+impl DocHits {
+    fn merge(&mut self, other: DocHits) {
+        for (section_idx, hit) in other.sections {
+            self.sections
+                .entry(section_idx)
+                .and_modify(|h| h.combine(hit))
+                .or_insert(hit);
+        }
+    }
+}
+
+/// doc_id → hits for that document.
+type NodeHits = HashMap<usize, DocHits>;
+
+/// `node` with every term quoted, so it only matches exactly.
+fn exact_terms(node: &QueryNode) -> QueryNode {
+    match node {
+        QueryNode::Term { text, .. } => QueryNode::Term {
+            text: text.clone(),
+            exact: true,
+        },
+        QueryNode::And(children) => QueryNode::And(children.iter().map(exact_terms).collect()),
+        QueryNode::Or(children) => QueryNode::Or(children.iter().map(exact_terms).collect()),
+        QueryNode::Not(node) => QueryNode::Not(Box::new(exact_terms(node))),
+        QueryNode::Field { field, node } => QueryNode::Field {
+            field: *field,
+            node: Box::new(exact_terms(node)),
+        },
+        QueryNode::Phrase(_) | QueryNode::Near { .. } => node.clone(),
+    }
+}
+
+impl TierSearcher {
+    /// Evaluate a parsed boolean query.
+    ///
+    /// Results carry the tier of the weakest term needed to match, so
+    /// `kernel -deprecated` is a T1 result when "kernel" matched exactly.
+    /// Exclusions never lower a tier; they only remove documents.
+    pub fn search_query(
+        &self,
+        query: &QueryNode,
+        limit: usize,
+        options: SearchOptions,
+    ) -> Vec<SearchResult> {
+        if limit == 0 {
+            return Vec::new();
+        }
+
+        let budget = self.edit_budget(&options);
+        // Terms widen like the tier pipeline's Tier 1, keeping every section
+        let term_options = SearchOptions {
+            dedup_sections: false,
+            ..options.clone()
+        };
+        let mut hits = self.eval_node(query, None, budget, &term_options);
+        if let Some(mask) = options.filter.mask(self.docs()) {
+            hits.retain(|&doc_id, _| mask[doc_id]);
+        }
+
+        let section_results: Vec<SearchResult> = hits
+            .into_iter()
+            .flat_map(|(doc_id, doc)| {
+                let tier = doc.tier;
+                doc.sections
+                    .into_iter()
+                    .map(move |(section_idx, hit)| SearchResult {
+                        doc_id,
+                        score: hit.score,
+                        section_idx,
+                        tier,
+                        match_type: hit.match_type,
+                        matched_term: Some(hit.matched_term),
+                    })
+            })
+            .collect();
+
+        finalize_results(section_results, limit, self.docs(), options.dedup_sections)
+    }
+
+    /// `scope` restricts postings to one field (set by the nearest enclosing
+    /// `field:` operator). `budget` bounds fuzzy term expansion. `options`
+    /// say how terms widen at Tier 1, and their language picks the stop
+    /// words that phrases and `NEAR` skip.
+    fn eval_node(
+        &self,
+        node: &QueryNode,
+        scope: Option<FieldType>,
+        budget: EditBudget,
+        options: &SearchOptions,
+    ) -> NodeHits {
+        match node {
+            QueryNode::Term { text, exact } => self.eval_term(text, *exact, scope, budget, options),
+            QueryNode::Phrase(words) => self.eval_phrase(words, scope, options),
+            QueryNode::Near { terms, distance } => self.eval_near(terms, *distance, scope, options),
+            QueryNode::And(children) => self.eval_and(children, scope, budget, options),
+            QueryNode::Or(children) => self.eval_or(children, scope, budget, options),
+            QueryNode::Field { field, node } => self.eval_node(node, Some(*field), budget, options),
+            // A bare NOT has nothing to subtract from
+            QueryNode::Not(_) => NodeHits::new(),
+        }
+    }
+
+    fn eval_and(
+        &self,
+        children: &[QueryNode],
+        scope: Option<FieldType>,
+        budget: EditBudget,
+        options: &SearchOptions,
+    ) -> NodeHits {
+        let (negated, positive): (Vec<&QueryNode>, Vec<&QueryNode>) = children
+            .iter()
+            .partition(|c| matches!(c, QueryNode::Not(_)));
+
+        let mut positive = positive.into_iter();
+        let Some(first) = positive.next() else {
+            return NodeHits::new();
+        };
+        let mut acc = self.eval_node(first, scope, budget, options);
+
+        for child in positive {
+            if acc.is_empty() {
+                return acc;
+            }
+            let mut hits = self.eval_node(child, scope, budget, options);
+            acc = acc
+                .into_iter()
+                .filter_map(|(doc_id, mut doc)| {
+                    let other = hits.remove(&doc_id)?;
+                    doc.tier = doc.tier.max(other.tier);
+                    doc.merge(other);
+                    Some((doc_id, doc))
+                })
+                .collect();
+        }
+
+        for child in negated {
+            if acc.is_empty() {
+                break;
+            }
+            if let QueryNode::Not(inner) = child {
+                let excluded =
+                    self.eval_node(&exact_terms(inner), scope, EditBudget::EXACT, options);
+                for doc_id in excluded.keys() {
+                    acc.remove(doc_id);
+                }
+            }
+        }
+
+        acc
+    }
+
+    fn eval_or(
+        &self,
+        children: &[QueryNode],
+        scope: Option<FieldType>,
+        budget: EditBudget,
+        options: &SearchOptions,
+    ) -> NodeHits {
+        let mut acc = NodeHits::new();
+        for child in children {
+            if matches!(child, QueryNode::Not(_)) {
+                continue;
+            }
+            for (doc_id, doc) in self.eval_node(child, scope, budget, options) {
+                match acc.get_mut(&doc_id) {
+                    Some(existing) => {
+                        existing.tier = existing.tier.min(doc.tier);
+                        existing.merge(doc);
+                    }
+                    None => {
+                        acc.insert(doc_id, doc);
+                    }
+                }
+            }
+        }
+        acc
+    }
+
+    /// Resolve one term per document at its best tier.
+    ///
+    /// Documents hit exactly, or through the other forms the tier pipeline
+    /// accepts at T1, are T1. Remaining documents hit through a prefix
+    /// expansion are T2, and the rest hit through a fuzzy match (distance > 0)
+    /// are T3. Penalties match the tier pipeline. `exact` stops after the
+    /// word as typed.
+    fn eval_term(
+        &self,
+        term: &str,
+        exact: bool,
+        scope: Option<FieldType>,
+        budget: EditBudget,
+        options: &SearchOptions,
+    ) -> NodeHits {
+        let mut hits = NodeHits::new();
+
+        // Tier 1: exact
+        if let Some(vocab_idx) = self.vocabulary().iter().position(|t| t == term) {
+            self.collect_postings(&mut hits, vocab_idx, 1, 1.0, scope);
+        }
+        if exact {
+            return hits;
+        }
+        let exact_ids: HashSet<usize> = hits.keys().copied().collect();
+        for result in self.tier1_expansions(term, &exact_ids, usize::MAX, None, options) {
+            self.collect_result(&mut hits, result, scope);
+        }
+
+        // Tier 2: prefix (query.len / term.len)
+        for vocab_idx in prefix_search_vocabulary(self.suffix_array(), self.vocabulary(), term) {
+            let term_len = self.vocabulary().get(vocab_idx).map_or(1, |t| t.len());
+            let penalty = term.len() as f64 / term_len.max(1) as f64;
+            self.collect_postings(&mut hits, vocab_idx, 2, penalty, scope);
+        }
+
+        // Tier 3: fuzzy (1 / (1 + distance))
+        for m in self.fuzzy_terms(term, budget.max_distance, budget.fuzziness, false) {
+            if m.distance == 0 {
+                continue;
+            }
+            let penalty = 1.0 / (1.0 + m.distance as f64);
+            self.collect_postings(&mut hits, m.term_idx, 3, penalty, scope);
+        }
+
+        hits
+    }
+
+    /// Match a quoted phrase. Phrase hits are always T1.
+    ///
+    /// Each section keeps its best occurrence. Indexes without positions
+    /// fall back to requiring every word exactly, anywhere in the document.
+    fn eval_phrase(
+        &self,
+        words: &[String],
+        scope: Option<FieldType>,
+        options: &SearchOptions,
+    ) -> NodeHits {
+        let language = options.language;
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let Some(phrase_hits) = self.phrase_hits(&words, scope, language) else {
+            let terms: Vec<QueryNode> = words
+                .iter()
+                .filter(|w| !is_stop_word_in(w, language))
+                .map(|w| QueryNode::Term {
+                    text: w.to_string(),
+                    exact: true,
+                })
+                .collect();
+            return self.eval_and(&terms, scope, EditBudget::EXACT, options);
+        };
+
+        let mut hits = NodeHits::new();
+        for phrase_hit in phrase_hits {
+            let hit = SectionHit {
+                score: phrase_hit.score,
+                match_type: phrase_hit.match_type,
+                matched_term: phrase_hit.matched_term,
+            };
+            hits.entry(phrase_hit.doc_id)
+                .or_insert_with(|| DocHits {
+                    tier: 1,
+                    sections: HashMap::new(),
+                })
+                .sections
+                .entry(phrase_hit.section_idx)
+                .and_modify(|h| {
+                    if hit.match_type < h.match_type
+                        || (hit.match_type == h.match_type && hit.score > h.score)
+                    {
+                        *h = hit;
+                    }
+                })
+                .or_insert(hit);
+        }
+        hits
+    }
+
+    /// Match `a NEAR/k b`: every term exactly, within the NEAR window.
+    ///
+    /// Matching documents are scored like an AND of the exact terms, times
+    /// the proximity boost for their tightest span. Without positions this
+    /// is just the AND.
+    fn eval_near(
+        &self,
+        terms: &[String],
+        distance: u32,
+        scope: Option<FieldType>,
+        options: &SearchOptions,
+    ) -> NodeHits {
+        let language = options.language;
+        let exact_terms: Vec<QueryNode> = terms
+            .iter()
+            .filter(|t| !is_stop_word_in(t, language))
+            .map(|t| QueryNode::Term {
+                text: t.clone(),
+                exact: true,
+            })
+            .collect();
+        let mut hits = self.eval_and(&exact_terms, scope, EditBudget::EXACT, options);
+
+        let words: Vec<&str> = terms.iter().map(String::as_str).collect();
+        let Some(spans) = self.doc_spans(&words, scope, language) else {
+            return hits;
+        };
+
+        let window = near_window(distance, exact_terms.len());
+        hits.retain(|doc_id, doc| match spans.get(doc_id) {
+            Some(&span) if span <= window => {
+                let boost = proximity_boost(span, exact_terms.len());
+                for hit in doc.sections.values_mut() {
+                    hit.score *= boost;
+                }
+                true
+            }
+            _ => false,
+        });
+        hits
+    }
+
+    /// Add a Tier 1 section result to `hits`, unless it's outside `scope`.
+    ///
+    /// Like postings, a section keeps its best match.
+    fn collect_result(&self, hits: &mut NodeHits, result: SearchResult, scope: Option<FieldType>) {
+        if scope.is_some_and(|f| !f.contains(result.match_type)) {
+            return;
+        }
+        let hit = SectionHit {
+            score: result.score,
+            match_type: result.match_type,
+            matched_term: result.matched_term.unwrap_or(u32::MAX),
+        };
+        hits.entry(result.doc_id)
+            .or_insert_with(|| DocHits {
+                tier: 1,
+                sections: HashMap::new(),
+            })
+            .sections
+            .entry(result.section_idx)
+            .and_modify(|h| {
+                if hit.match_type < h.match_type
+                    || (hit.match_type == h.match_type && hit.score > h.score)
+                {
+                    *h = hit;
+                }
+            })
+            .or_insert(hit);
+    }
+
+    /// Add a vocabulary term's postings to `hits` at `tier`.
+    ///
+    /// Documents already hit at a better tier are skipped. Within a tier, a
+    /// section keeps its best-scoring match rather than summing expansions,
+    /// so "optim" doesn't outrank "optimization" by matching five variants.
+    /// Postings outside `scope` are ignored.
+    fn collect_postings(
+        &self,
+        hits: &mut NodeHits,
+        vocab_idx: usize,
+        tier: u8,
+        penalty: f64,
+        scope: Option<FieldType>,
+    ) {
+        let Some(postings) = self.postings().get(vocab_idx) else {
+            return;
+        };
+
+        for entry in postings {
+            if scope.is_some_and(|f| !f.contains_heading_level(entry.heading_level)) {
+                continue;
+            }
+            let doc_id = entry.doc_id as usize;
+            if self.docs().get(doc_id).is_none() {
+                continue;
+            }
+
+            let doc = hits.entry(doc_id).or_insert_with(|| DocHits {
+                tier,
+                sections: HashMap::new(),
+            });
+            if doc.tier < tier {
+                continue;
+            }
+
+            let hit = SectionHit {
+                score: entry.score as f64 * penalty,
+                match_type: MatchType::from_heading_level(entry.heading_level),
+                matched_term: vocab_idx as u32,
+            };
+            doc.sections
+                .entry(entry.section_idx)
+                .and_modify(|h| {
+                    if hit.match_type < h.match_type
+                        || (hit.match_type == h.match_type && hit.score > h.score)
+                    {
+                        *h = hit;
+                    }
+                })
+                .or_insert(hit);
+        }
+    }
+}
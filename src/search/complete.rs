@@ -0,0 +1,202 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Completions: vocabulary terms for a half-typed word.
+//!
+//! Search results answer a query; completions help write one. Typing "auth"
+//! should offer "authentication", "authorization" and "author" before the
+//! user presses enter, so they pick the word the index actually has.
+//!
+//! Candidates come straight from the vocabulary suffix array, the same one
+//! Tier 2 uses. By default only terms *starting* with the fragment count.
+//! With `infix`, any suffix does, so "auth" also offers "oauth", ranked after
+//! every word-start match.
+//!
+//! Ranking follows the result ranking's priorities: the best place a term
+//! appears (a title word beats a body word), then how many documents use it,
+//! then the shorter term, which is usually the one being typed.
+
+use super::tiered::{infix_search_vocabulary, prefix_search_vocabulary, TierSearcher};
+use crate::types::MatchType;
+use crate::util::normalize::fold;
+use std::collections::HashSet;
+
+/// A vocabulary term completing what was typed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The completed term (normalized, like the vocabulary)
+    pub term: String,
+    /// Documents containing the term
+    pub doc_count: usize,
+    /// Best place the term appears in any document
+    pub match_type: MatchType,
+    /// The fragment appears mid-term, not at its start
+    pub infix: bool,
+}
+
+/// Options for [`TierSearcher::complete`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompletionOptions {
+    /// Also complete terms containing the fragment mid-word (default: false).
+    pub infix: bool,
+}
+
+impl CompletionOptions {
+    /// Also offer terms that contain the fragment after their first letter.
+    pub fn with_infix(self) -> Self {
+        Self { infix: true }
+    }
+}
+
+impl TierSearcher {
+    /// Up to `limit` vocabulary terms completing the last word of `input`.
+    ///
+    /// Only the last whitespace-separated word is completed, so a search box
+    /// can pass its whole contents. Terms no loaded document contains are
+    /// skipped. Empty when `input` ends in whitespace (the word is finished)
+    /// or the last word matches nothing.
+    pub fn complete(
+        &self,
+        input: &str,
+        limit: usize,
+        options: CompletionOptions,
+    ) -> Vec<Completion> {
+        // A trailing space means the last word is finished
+        let input = fold(input);
+        let fragment = match input.split_whitespace().last() {
+            Some(word) if input.ends_with(word) => word,
+            _ => return Vec::new(),
+        };
+
+        let suffix_array = self.suffix_array();
+        let vocabulary = self.vocabulary();
+        let starts = prefix_search_vocabulary(suffix_array, vocabulary, fragment);
+        let mut candidates: Vec<(usize, bool)> = starts.iter().map(|&idx| (idx, false)).collect();
+        if options.infix {
+            let starts: HashSet<usize> = starts.into_iter().collect();
+            candidates.extend(
+                infix_search_vocabulary(suffix_array, vocabulary, fragment)
+                    .into_iter()
+                    .filter(|idx| !starts.contains(idx))
+                    .map(|idx| (idx, true)),
+            );
+        }
+
+        let mut completions: Vec<Completion> = candidates
+            .into_iter()
+            .filter_map(|(vocab_idx, infix)| self.completion(vocab_idx, infix))
+            .collect();
+        completions.sort_by(|a, b| {
+            a.infix
+                .cmp(&b.infix)
+                .then(a.match_type.cmp(&b.match_type))
+                .then(b.doc_count.cmp(&a.doc_count))
+                .then(a.term.len().cmp(&b.term.len()))
+                .then_with(|| a.term.cmp(&b.term))
+        });
+        completions.truncate(limit);
+        completions
+    }
+
+    /// Document count and best match type of a vocabulary term.
+    fn completion(&self, vocab_idx: usize, infix: bool) -> Option<Completion> {
+        let doc_count = self.docs().len();
+        let mut docs = HashSet::new();
+        let mut best = MatchType::Content;
+        for entry in self.postings().get(vocab_idx)? {
+            if entry.doc_id as usize >= doc_count {
+                continue;
+            }
+            docs.insert(entry.doc_id);
+            best = best.min(MatchType::from_heading_level(entry.heading_level));
+        }
+
+        (!docs.is_empty()).then(|| Completion {
+            term: self.vocabulary()[vocab_idx].clone(),
+            doc_count: docs.len(),
+            match_type: best,
+            infix,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::phrase::tests::build_searcher_with_levels;
+
+    fn terms(completions: &[Completion]) -> Vec<&str> {
+        completions.iter().map(|c| c.term.as_str()).collect()
+    }
+
+    #[test]
+    fn test_completes_word_starts_by_doc_frequency() {
+        let searcher = build_searcher_with_levels(
+            &[
+                ("authentication tokens", 5),
+                ("authentication flows", 5),
+                ("authorization rules", 5),
+                ("author notes", 5),
+                ("oauth setup", 5),
+            ],
+            false,
+        );
+        let completions = searcher.complete("Auth", 10, CompletionOptions::default());
+        assert_eq!(
+            terms(&completions),
+            vec!["authentication", "author", "authorization"]
+        );
+        assert_eq!(completions[0].doc_count, 2);
+        assert!(completions.iter().all(|c| !c.infix));
+
+        assert_eq!(
+            terms(&searcher.complete("auth", 1, CompletionOptions::default())),
+            vec!["authentication"]
+        );
+    }
+
+    #[test]
+    fn test_title_words_rank_first() {
+        let searcher = build_searcher_with_levels(
+            &[
+                ("authorization", 0),
+                ("authentication", 5),
+                ("authentication", 5),
+            ],
+            false,
+        );
+        let completions = searcher.complete("auth", 10, CompletionOptions::default());
+        assert_eq!(terms(&completions), vec!["authorization", "authentication"]);
+        assert_eq!(completions[0].match_type, MatchType::Title);
+    }
+
+    #[test]
+    fn test_infix_matches_come_last() {
+        let searcher =
+            build_searcher_with_levels(&[("oauth", 0), ("oauth", 0), ("author", 5)], false);
+        let completions = searcher.complete("auth", 10, CompletionOptions::default());
+        assert_eq!(terms(&completions), vec!["author"]);
+
+        let completions = searcher.complete("auth", 10, CompletionOptions::default().with_infix());
+        assert_eq!(terms(&completions), vec!["author", "oauth"]);
+        assert!(completions[1].infix);
+    }
+
+    #[test]
+    fn test_completes_last_word_only() {
+        let searcher = build_searcher_with_levels(&[("rust authentication", 5)], false);
+        assert_eq!(
+            terms(&searcher.complete("rust auth", 10, CompletionOptions::default())),
+            vec!["authentication"]
+        );
+        assert!(searcher
+            .complete("auth ", 10, CompletionOptions::default())
+            .is_empty());
+        assert!(searcher
+            .complete("", 10, CompletionOptions::default())
+            .is_empty());
+        assert!(searcher
+            .complete("zzz", 10, CompletionOptions::default())
+            .is_empty());
+    }
+}
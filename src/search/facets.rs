@@ -0,0 +1,205 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Facet counts: "Engineering (12) · Adventures (3)" next to the results.
+//!
+//! A results page shows ten documents, but the facet chips describe every
+//! document the query matched. So counting happens over the full match set,
+//! before `limit` truncates it, in the same pass that produces the page. The
+//! alternative (one extra query per chip) gets expensive fast on a search box
+//! that fires on every keystroke.
+//!
+//! Counts cover category, author, each tag, and document type. A document
+//! with three tags counts once toward each of them. Values are sorted by
+//! count (highest first), then alphabetically, which is the order a UI wants
+//! to render chips in.
+
+use super::tiered::{SearchOptions, SearchResult, TierSearcher};
+use crate::types::SearchDoc;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// How many matching documents carry one facet value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// Facet counts over every document a query matched.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FacetCounts {
+    /// Number of matching documents (before `limit`)
+    pub total: usize,
+    pub categories: Vec<FacetCount>,
+    pub authors: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+    /// Document types
+    #[serde(rename = "types")]
+    pub kinds: Vec<FacetCount>,
+}
+
+impl FacetCounts {
+    /// Count facet values over a set of documents (each counted once).
+    pub fn from_docs<'a>(docs: impl IntoIterator<Item = &'a SearchDoc>) -> Self {
+        let mut total = 0;
+        let mut categories: HashMap<&str, usize> = HashMap::new();
+        let mut authors: HashMap<&str, usize> = HashMap::new();
+        let mut tags: HashMap<&str, usize> = HashMap::new();
+        let mut kinds: HashMap<&str, usize> = HashMap::new();
+
+        for doc in docs {
+            total += 1;
+            if let Some(category) = &doc.category {
+                *categories.entry(category).or_default() += 1;
+            }
+            if let Some(author) = &doc.author {
+                *authors.entry(author).or_default() += 1;
+            }
+            for tag in &doc.tags {
+                *tags.entry(tag).or_default() += 1;
+            }
+            *kinds.entry(&doc.kind).or_default() += 1;
+        }
+
+        Self {
+            total,
+            categories: sorted(categories),
+            authors: sorted(authors),
+            tags: sorted(tags),
+            kinds: sorted(kinds),
+        }
+    }
+}
+
+/// Count descending, then value ascending.
+fn sorted(counts: HashMap<&str, usize>) -> Vec<FacetCount> {
+    let mut facets: Vec<FacetCount> = counts
+        .into_iter()
+        .map(|(value, count)| FacetCount {
+            value: value.to_string(),
+            count,
+        })
+        .collect();
+    facets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    facets
+}
+
+/// A page of results plus facet counts over the whole match set.
+#[derive(Debug, Clone)]
+pub struct FacetedResults {
+    pub results: Vec<SearchResult>,
+    pub facets: FacetCounts,
+}
+
+impl TierSearcher {
+    /// Search and count facets over every matching document.
+    ///
+    /// Ranks the matches once: `results` is cut from that ranking, so it's
+    /// the page [`TierSearcher::search_with_options`] returns for the same
+    /// arguments, and `facets` counts every document in it, not just the
+    /// first `limit`. Filters and field scopes in `options` apply to both.
+    pub fn search_with_facets(
+        &self,
+        query: &str,
+        limit: usize,
+        options: SearchOptions,
+    ) -> FacetedResults {
+        let offset = options.offset;
+        let mut ranked = self.rank_all(query, options);
+
+        // Sections of one document count once
+        let mut seen = HashSet::new();
+        let facets = FacetCounts::from_docs(
+            ranked
+                .iter()
+                .filter(|r| seen.insert(r.doc_id))
+                .filter_map(|r| self.docs().get(r.doc_id)),
+        );
+        let results = ranked
+            .drain(offset.min(ranked.len())..)
+            .take(limit)
+            .collect();
+
+        FacetedResults { results, facets }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::tiered::multi_term_tests::create_filter_searcher;
+    use crate::search::tiered::SearchFilter;
+
+    fn counts(facets: &[FacetCount]) -> Vec<(&str, usize)> {
+        facets.iter().map(|f| (f.value.as_str(), f.count)).collect()
+    }
+
+    #[test]
+    fn test_facets_count_every_match() {
+        let searcher = create_filter_searcher();
+
+        let faceted = searcher.search_with_facets("rust", 1, SearchOptions::default());
+        assert_eq!(faceted.results.len(), 1);
+        assert_eq!(faceted.facets.total, 2);
+        assert_eq!(counts(&faceted.facets.categories), vec![("tutorials", 2)]);
+        assert_eq!(
+            counts(&faceted.facets.authors),
+            vec![("ada", 1), ("bob", 1)]
+        );
+        assert_eq!(counts(&faceted.facets.tags), vec![("rust", 2), ("perf", 1)]);
+        assert_eq!(counts(&faceted.facets.kinds), vec![("post", 2)]);
+    }
+
+    #[test]
+    fn test_facets_match_plain_search() {
+        let searcher = create_filter_searcher();
+
+        let options = [
+            SearchOptions::default(),
+            SearchOptions::default().starting_at(1),
+            SearchOptions::without_section_dedup(),
+            SearchOptions::without_section_dedup().starting_at(1),
+        ];
+        for query in ["programming", "optim", "pythn", "rust OR python", "zzz"] {
+            for options in &options {
+                let faceted = searcher.search_with_facets(query, 2, options.clone());
+                let plain = searcher.search_with_options(query, 2, options.clone());
+                assert_eq!(
+                    faceted
+                        .results
+                        .iter()
+                        .map(|r| (r.doc_id, r.section_idx))
+                        .collect::<Vec<_>>(),
+                    plain
+                        .iter()
+                        .map(|r| (r.doc_id, r.section_idx))
+                        .collect::<Vec<_>>(),
+                    "query {:?}, {:?}",
+                    query,
+                    options
+                );
+            }
+        }
+        assert_eq!(
+            searcher
+                .search_with_facets("zzz", 10, SearchOptions::default())
+                .facets,
+            FacetCounts::default()
+        );
+    }
+
+    #[test]
+    fn test_facets_respect_filters() {
+        let searcher = create_filter_searcher();
+        let options = SearchOptions::without_section_dedup().with_filter(SearchFilter {
+            kinds: vec!["page".to_string()],
+            ..Default::default()
+        });
+
+        let faceted = searcher.search_with_facets("programming", 10, options);
+        assert_eq!(faceted.facets.total, 1);
+        assert_eq!(counts(&faceted.facets.categories), vec![("programming", 1)]);
+        assert_eq!(faceted.results.len(), 1);
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Query-time metadata filters.
+//!
+//! The manifest's `IncludeFilter` decides which documents make it into an
+//! index at all. This is the other half: one index, narrowed per query. "Only
+//! engineering posts", "only pages under /api/", "only things tagged async".
+//!
+//! Filters are checked against [`SearchDoc`] metadata inside the tier loops,
+//! before any `limit` is applied. Filtering the top 10 after the fact would
+//! hand back two results when the index has fifty that qualify; filtering
+//! first means a filtered query still gets a full page.
+//!
+//! Every condition must hold (AND). Conditions left empty don't filter.
+
+use crate::types::SearchDoc;
+
+/// Metadata conditions a document must meet to appear in results.
+///
+/// The default filter matches every document.
+///
+/// # Example
+///
+/// ```
+/// use sorex::SearchFilter;
+///
+/// let filter = SearchFilter {
+///     categories: vec!["engineering".to_string()],
+///     href_prefix: Some("/blog/".to_string()),
+///     ..Default::default()
+/// };
+/// assert!(!filter.is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchFilter {
+    /// Category must be one of these (`category in [...]`).
+    pub categories: Vec<String>,
+    /// Document must carry every one of these tags (`tags contains X`).
+    pub tags: Vec<String>,
+    /// Author must be exactly this (`author = Y`).
+    pub author: Option<String>,
+    /// Document type must be one of these (`type in [...]`).
+    pub kinds: Vec<String>,
+    /// Href must start with this (`href starts with /api/`).
+    pub href_prefix: Option<String>,
+}
+
+impl SearchFilter {
+    /// True if this filter lets every document through.
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+            && self.tags.is_empty()
+            && self.author.is_none()
+            && self.kinds.is_empty()
+            && self.href_prefix.is_none()
+    }
+
+    /// Does `doc` meet every condition?
+    pub fn matches(&self, doc: &SearchDoc) -> bool {
+        let in_list =
+            |list: &[String], value: &str| list.is_empty() || list.iter().any(|v| v == value);
+
+        (self.categories.is_empty()
+            || doc
+                .category
+                .as_deref()
+                .is_some_and(|c| in_list(&self.categories, c)))
+            && self.tags.iter().all(|tag| doc.tags.contains(tag))
+            && self
+                .author
+                .as_deref()
+                .is_none_or(|author| doc.author.as_deref() == Some(author))
+            && in_list(&self.kinds, &doc.kind)
+            && self
+                .href_prefix
+                .as_deref()
+                .is_none_or(|prefix| doc.href.starts_with(prefix))
+    }
+
+    /// Per-document pass/fail, indexed by doc_id.
+    ///
+    /// `None` for an empty filter, so unfiltered searches skip the lookup.
+    pub(crate) fn mask(&self, docs: &[SearchDoc]) -> Option<Vec<bool>> {
+        (!self.is_empty()).then(|| docs.iter().map(|doc| self.matches(doc)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(category: Option<&str>, author: Option<&str>, tags: &[&str], href: &str) -> SearchDoc {
+        SearchDoc {
+            id: 0,
+            title: "Doc".to_string(),
+            excerpt: String::new(),
+            href: href.to_string(),
+            kind: "post".to_string(),
+            category: category.map(str::to_string),
+            author: author.map(str::to_string),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = SearchFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.matches(&doc(None, None, &[], "/")));
+        assert!(filter.mask(&[doc(None, None, &[], "/")]).is_none());
+    }
+
+    #[test]
+    fn test_category_in_list() {
+        let filter = SearchFilter {
+            categories: strings(&["engineering", "adventures"]),
+            ..Default::default()
+        };
+        assert!(filter.matches(&doc(Some("adventures"), None, &[], "/")));
+        assert!(!filter.matches(&doc(Some("cooking"), None, &[], "/")));
+        assert!(!filter.matches(&doc(None, None, &[], "/")));
+    }
+
+    #[test]
+    fn test_tags_must_all_be_present() {
+        let filter = SearchFilter {
+            tags: strings(&["rust", "async"]),
+            ..Default::default()
+        };
+        assert!(filter.matches(&doc(None, None, &["async", "rust", "tokio"], "/")));
+        assert!(!filter.matches(&doc(None, None, &["rust"], "/")));
+    }
+
+    #[test]
+    fn test_author_kind_and_href_prefix() {
+        let filter = SearchFilter {
+            author: Some("ada".to_string()),
+            kinds: strings(&["post"]),
+            href_prefix: Some("/api/".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.matches(&doc(None, Some("ada"), &[], "/api/search")));
+        assert!(!filter.matches(&doc(None, Some("bob"), &[], "/api/search")));
+        assert!(!filter.matches(&doc(None, None, &[], "/api/search")));
+        assert!(!filter.matches(&doc(None, Some("ada"), &[], "/guide/api/")));
+
+        let pages_only = SearchFilter {
+            kinds: strings(&["page"]),
+            ..Default::default()
+        };
+        assert!(!pages_only.matches(&doc(None, None, &[], "/")));
+    }
+}
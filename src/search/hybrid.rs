@@ -21,10 +21,23 @@
 //! - p = number of prefix-matching terms
 
 use crate::fuzzy::levenshtein_within;
+use crate::index::segment::segment;
 use crate::types::{HybridIndex, PostingList, ScoredDoc, SearchDoc};
 use super::utils::{merge_score_sets, parse_query};
 use std::collections::HashMap;
 
+/// Query words cut like the indexer cuts them: "東京都" is "東京" and "京都".
+fn query_terms(query: &str) -> Vec<String> {
+    parse_query(query)
+        .iter()
+        .flat_map(|part| {
+            segment(part)
+                .into_iter()
+                .map(|(s, e)| part[s..e].to_string())
+        })
+        .collect()
+}
+
 /// Search the hybrid index.
 ///
 /// Strategy:
@@ -32,7 +45,7 @@ use std::collections::HashMap;
 /// 2. If no exact match, use prefix search via vocabulary suffix array (O(log k))
 /// 3. For multi-word queries, intersect posting lists
 pub fn search_hybrid(index: &HybridIndex, query: &str) -> Vec<SearchDoc> {
-    let parts = parse_query(query);
+    let parts = query_terms(query);
     if parts.is_empty() {
         return Vec::new();
     }
@@ -102,7 +115,7 @@ pub fn search_hybrid(index: &HybridIndex, query: &str) -> Vec<SearchDoc> {
 ///   | none => []
 /// ```
 pub fn search_exact(index: &HybridIndex, query: &str) -> Vec<SearchDoc> {
-    let parts = parse_query(query);
+    let parts = query_terms(query);
     if parts.is_empty() {
         return Vec::new();
     }
@@ -135,7 +148,7 @@ pub fn search_exact(index: &HybridIndex, query: &str) -> Vec<SearchDoc> {
 ///   (expandedLookup vocabulary suffixArray term).filter (fun id => id ∉ excludeIds)
 /// ```
 pub fn search_expanded(index: &HybridIndex, query: &str, exclude_ids: &[usize]) -> Vec<SearchDoc> {
-    let parts = parse_query(query);
+    let parts = query_terms(query);
     if parts.is_empty() {
         return Vec::new();
     }
@@ -176,7 +189,7 @@ pub fn search_expanded(index: &HybridIndex, query: &str, exclude_ids: &[usize])
 /// O(vocabulary × max_distance²) for edit distance computation.
 /// Safe to run in a separate worker thread.
 pub fn search_fuzzy(index: &HybridIndex, query: &str, exclude_ids: &[usize]) -> Vec<SearchDoc> {
-    let parts = parse_query(query);
+    let parts = query_terms(query);
     if parts.is_empty() {
         return Vec::new();
     }
@@ -458,9 +471,10 @@ fn find_substring_matches(index: &HybridIndex, query: &str) -> Vec<String> {
     while lo < hi {
         let mid = (lo + hi) / 2;
         let entry = &suffix_array[mid];
-        let suffix = &vocab[entry.term_idx][entry.offset..];
+        // Offsets are bytes and may fall inside a character: compare bytes
+        let suffix = &vocab[entry.term_idx].as_bytes()[entry.offset..];
 
-        if suffix < query {
+        if suffix < query.as_bytes() {
             lo = mid + 1;
         } else {
             hi = mid;
@@ -475,9 +489,9 @@ fn find_substring_matches(index: &HybridIndex, query: &str) -> Vec<String> {
 
     for i in lo..suffix_array.len() {
         let entry = &suffix_array[i];
-        let suffix = &vocab[entry.term_idx][entry.offset..];
+        let suffix = &vocab[entry.term_idx].as_bytes()[entry.offset..];
 
-        if !suffix.starts_with(query) {
+        if !suffix.starts_with(query.as_bytes()) {
             break;
         }
 
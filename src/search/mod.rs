@@ -8,9 +8,22 @@
 //! (exact → prefix → fuzzy) ensures users get results fast while still
 //! catching typos.
 
+mod boolean;
+pub mod complete;
 pub mod dedup;
+pub mod facets;
+pub mod filter;
+pub mod paging;
 pub mod hybrid;
+mod phrase;
+mod proximity;
+pub mod query;
+pub mod snippet;
+mod split_join;
+mod stemmed;
 mod suffix;
+mod synonyms;
+pub mod suggest;
 pub mod tiered;
 pub mod union;
 pub mod utils;
@@ -0,0 +1,180 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pagination: page 2 without re-running page 1 and slicing.
+//!
+//! A three-tier search doesn't page naively. Each tier is cut at `limit`, and
+//! prefix and fuzzy results exclude documents the tiers before them returned,
+//! so asking for 20 results isn't guaranteed to start with the 10 a limit-10
+//! query returned. Slice two differently-sized searches and a document can
+//! show up on both pages, or on neither.
+//!
+//! So pages are cut from one ranking of the *whole* match set: every tier
+//! runs uncut, the merge sorts everything with [`compare_results`] (a total
+//! order, down to doc_id and section), and a page is `offset..offset + limit`
+//! of that list. Consecutive offsets tile it exactly, and the match count
+//! falls out for free. This is the same pass facet counting makes, and on a
+//! client-side index it costs about what a single page does: the tiers scan
+//! the same postings either way, and only the final sort gets longer.
+//!
+//! [`compare_results`]: crate::scoring::ranking::compare_results
+
+use super::tiered::{SearchOptions, SearchResult, TierSearcher};
+
+/// One page of ranked results.
+#[derive(Debug, Clone)]
+pub struct SearchPage {
+    pub results: Vec<SearchResult>,
+    /// Position of `results[0]` in the full ranking
+    pub offset: usize,
+    /// Number of results across all pages (documents, or sections when
+    /// `dedup_sections` is off)
+    pub total: usize,
+}
+
+impl SearchPage {
+    /// True if results remain after this page.
+    pub fn has_more(&self) -> bool {
+        self.offset + self.results.len() < self.total
+    }
+
+    /// Offset of the next page, if there is one.
+    pub fn next_offset(&self) -> Option<usize> {
+        self.has_more().then_some(self.offset + self.results.len())
+    }
+}
+
+impl TierSearcher {
+    /// Search one page, starting `options.offset` results in.
+    ///
+    /// Pages from consecutive offsets never overlap and never skip a result,
+    /// even across tiers. `total` counts every match, not just this page.
+    /// [`TierSearcher::search_with_options`] returns the same results
+    /// without the count, at any offset.
+    pub fn search_page(&self, query: &str, limit: usize, options: SearchOptions) -> SearchPage {
+        let offset = options.offset;
+        let mut ranked = self.rank_all(query, options);
+
+        let total = ranked.len();
+        let results = ranked.drain(offset.min(total)..).take(limit).collect();
+        SearchPage {
+            results,
+            offset,
+            total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::phrase::tests::build_searcher;
+    use crate::search::tiered::multi_term_tests::create_filter_searcher;
+    use std::collections::HashSet;
+
+    fn key(r: &SearchResult) -> (usize, u32) {
+        (r.doc_id, r.section_idx)
+    }
+
+    /// Exact and prefix matches for "rust", spread over 24 docs.
+    fn paging_searcher() -> TierSearcher {
+        let texts: Vec<String> = (0..24)
+            .map(|i| match i % 2 {
+                0 => format!("rust notes part{}", i),
+                _ => format!("rusty tools part{}", i),
+            })
+            .collect();
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        build_searcher(&texts, true)
+    }
+
+    #[test]
+    fn test_pages_tile_the_ranking() {
+        let searcher = paging_searcher();
+        let all = searcher.search_page("rust", usize::MAX, SearchOptions::default());
+        assert_eq!(all.total, 24);
+        assert_eq!(all.results.len(), 24);
+        let tiers: HashSet<u8> = all.results.iter().map(|r| r.tier).collect();
+        assert_eq!(tiers, HashSet::from([1, 2]));
+
+        let mut paged = Vec::new();
+        let mut offset = Some(0);
+        while let Some(start) = offset {
+            let page = searcher.search_page("rust", 5, SearchOptions::default().starting_at(start));
+            assert_eq!(page.offset, start);
+            assert_eq!(page.total, 24);
+            assert!(page.results.len() <= 5);
+            paged.extend(page.results.iter().map(key));
+            offset = page.next_offset();
+        }
+
+        let expected: Vec<(usize, u32)> = all.results.iter().map(key).collect();
+        assert_eq!(paged, expected);
+    }
+
+    #[test]
+    fn test_offset_in_search_options() {
+        let searcher = paging_searcher();
+        let page = searcher.search_page("rust", 4, SearchOptions::default().starting_at(6));
+        let results =
+            searcher.search_with_options("rust", 4, SearchOptions::default().starting_at(6));
+        assert_eq!(
+            results.iter().map(key).collect::<Vec<_>>(),
+            page.results.iter().map(key).collect::<Vec<_>>()
+        );
+
+        // Past the end: no results, but the count still comes back
+        let past = searcher.search_page("rust", 4, SearchOptions::default().starting_at(100));
+        assert!(past.results.is_empty());
+        assert_eq!(past.total, 24);
+        assert!(!past.has_more());
+        assert_eq!(past.next_offset(), None);
+    }
+
+    #[test]
+    fn test_pages_from_search_with_options_tile_the_ranking() {
+        // Tier 1 alone fills a first page of 3 with the exact matches, but
+        // the full ranking puts others first
+        let mut texts = vec!["rust notes"; 6];
+        texts.extend(["rust rusty notes"; 2]);
+        let searcher = build_searcher(&texts, true);
+        let page = |offset: usize| -> Vec<(usize, u32)> {
+            let options = SearchOptions::default().starting_at(offset);
+            let results = searcher.search_with_options("rust notes", 3, options);
+            results.iter().map(key).collect()
+        };
+
+        let paged: Vec<(usize, u32)> = [page(0), page(3), page(6)].concat();
+        let unique: HashSet<(usize, u32)> = paged.iter().copied().collect();
+        assert_eq!(paged.len(), 8);
+        assert_eq!(unique.len(), 8);
+        let all = searcher.search_page("rust notes", usize::MAX, SearchOptions::default());
+        assert_eq!(paged, all.results.iter().map(key).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_paging_respects_options() {
+        let searcher = create_filter_searcher();
+        let options = SearchOptions::without_section_dedup();
+
+        let all = searcher.search_page("rust", usize::MAX, options.clone());
+        let first = searcher.search_page("rust", 1, options.clone());
+        let rest = searcher.search_page("rust", usize::MAX, options.starting_at(1));
+        assert_eq!(first.total, all.total);
+        assert_eq!(
+            first
+                .results
+                .iter()
+                .chain(&rest.results)
+                .map(key)
+                .collect::<Vec<_>>(),
+            all.results.iter().map(key).collect::<Vec<_>>()
+        );
+
+        // Boolean queries page the same way
+        let boolean = searcher.search_page("rust OR python", 1, SearchOptions::default());
+        assert_eq!(boolean.results.len(), 1);
+        assert!(boolean.total > 1);
+        assert!(boolean.has_more());
+    }
+}
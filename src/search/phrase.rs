@@ -0,0 +1,344 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Phrase matching over word positions.
+//!
+//! Postings tell you *which* sections contain "memory" and "safety". They
+//! don't tell you whether anyone ever wrote "memory safety". For that, indexes
+//! built with positions (`FormatFlags::HAS_POSITIONS`) carry a word ordinal for
+//! every posting entry, and a phrase is just: same document, positions
+//! `p, p+1, p+2, ...` for consecutive words.
+//!
+//! Stop words are never indexed, but they still take a position slot at build
+//! time. So `"state of the art"` checks "state" at `p` and "art" at `p+3`, and
+//! doesn't match "state art" or "state of art".
+//!
+//! Quoted queries (`"memory safety"`) only match phrase occurrences. Plain
+//! multi-word queries don't need the words in order, but rank sections where
+//! they're close together higher (see `proximity.rs`), so a phrase occurrence
+//! still ranks above scattered terms.
+//!
+//! Indexes without positions fall back to AND-ing exact terms.
+
+use super::tiered::TierSearcher;
+use crate::binary::PostingEntry;
+use crate::index::is_stop_word_in;
+use crate::index::language::Language;
+use crate::types::{FieldType, MatchType};
+use std::collections::HashMap;
+
+/// One occurrence of a phrase.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PhraseHit {
+    pub doc_id: usize,
+    /// Section of the phrase's first indexed word
+    pub section_idx: u32,
+    pub match_type: MatchType,
+    /// Sum of the posting scores of the phrase's words
+    pub score: f64,
+    /// Vocabulary index of the phrase's first indexed word
+    pub matched_term: u32,
+}
+
+impl TierSearcher {
+    /// True if this index stores word positions (phrase search available).
+    #[inline]
+    pub fn has_positions(&self) -> bool {
+        !self.positions().is_empty()
+    }
+
+    /// Find every occurrence of `words` as a contiguous phrase.
+    ///
+    /// Words must already be lowercased. With a `scope`, every word must
+    /// occur in that field. Stop words in `language` (any language without
+    /// one) leave a gap. Returns `None` when the index has no positions, so
+    /// callers can fall back to plain AND semantics.
+    pub(crate) fn phrase_hits(
+        &self,
+        words: &[&str],
+        scope: Option<FieldType>,
+        language: Option<Language>,
+    ) -> Option<Vec<PhraseHit>> {
+        if !self.has_positions() {
+            return None;
+        }
+
+        // (offset within the phrase, vocab index) for every indexed word.
+        // Stop words leave a gap; any other unknown word means no match.
+        let mut slots = Vec::with_capacity(words.len());
+        for (offset, word) in words.iter().enumerate() {
+            if is_stop_word_in(word, language) {
+                continue;
+            }
+            match self.vocabulary().binary_search_by(|t| t.as_str().cmp(word)) {
+                Ok(vocab_idx) => slots.push((offset as u32, vocab_idx)),
+                Err(_) => return Some(Vec::new()),
+            }
+        }
+
+        let Some((&(first_offset, first_vocab), rest)) = slots.split_first() else {
+            return Some(Vec::new());
+        };
+
+        // Candidates keyed by (doc_id, phrase start position)
+        let mut candidates: HashMap<(u32, u32), PhraseHit> = HashMap::new();
+        for (entry, start) in self.term_starts(first_vocab, first_offset, scope) {
+            if self.docs().get(entry.doc_id as usize).is_none() {
+                continue;
+            }
+            candidates
+                .entry((entry.doc_id, start))
+                .or_insert(PhraseHit {
+                    doc_id: entry.doc_id as usize,
+                    section_idx: entry.section_idx,
+                    match_type: MatchType::from_heading_level(entry.heading_level),
+                    score: entry.score as f64,
+                    matched_term: first_vocab as u32,
+                });
+        }
+
+        for &(offset, vocab_idx) in rest {
+            if candidates.is_empty() {
+                break;
+            }
+            let scores: HashMap<(u32, u32), f64> = self
+                .term_starts(vocab_idx, offset, scope)
+                .map(|(entry, start)| ((entry.doc_id, start), entry.score as f64))
+                .collect();
+            candidates.retain(|key, hit| match scores.get(key) {
+                Some(score) => {
+                    hit.score += score;
+                    true
+                }
+                None => false,
+            });
+        }
+
+        Some(candidates.into_values().collect())
+    }
+
+    /// Postings of a term paired with the phrase start they imply when the
+    /// term sits at `offset` within the phrase. Postings outside `scope` are
+    /// skipped.
+    fn term_starts(
+        &self,
+        vocab_idx: usize,
+        offset: u32,
+        scope: Option<FieldType>,
+    ) -> impl Iterator<Item = (&PostingEntry, u32)> {
+        let postings = self.postings().get(vocab_idx).map_or(&[][..], |p| p);
+        let positions = self.positions().get(vocab_idx).map_or(&[][..], |p| p);
+        postings
+            .iter()
+            .zip(positions)
+            .filter(move |(entry, _)| {
+                scope.is_none_or(|f| f.contains_heading_level(entry.heading_level))
+            })
+            .filter_map(move |(entry, &pos)| Some((entry, pos.checked_sub(offset)?)))
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::index::language::Language;
+    use crate::index::stem::StemIndex;
+    use crate::index::synonyms::Synonyms;
+    use crate::index::word_spans;
+    use crate::search::tiered::SearchOptions;
+    use crate::testing::{build_layer, load_searcher as load};
+    use std::collections::BTreeMap;
+
+    /// Build a searcher through the binary format, one content section per doc.
+    ///
+    /// Each doc is a list of words; a word's position is its index. Stop
+    /// words are skipped when building postings, like the real indexer.
+    pub(in crate::search) fn build_searcher(texts: &[&str], with_positions: bool) -> TierSearcher {
+        let docs: Vec<(&str, u8)> = texts.iter().map(|&t| (t, 5)).collect();
+        build_searcher_with_levels(&docs, with_positions)
+    }
+
+    /// Like `build_searcher`, with a heading level per doc (0 = title).
+    pub(in crate::search) fn build_searcher_with_levels(
+        texts: &[(&str, u8)],
+        with_positions: bool,
+    ) -> TierSearcher {
+        let (layer, _) = build_layer(texts, with_positions, &[], word_spans);
+        load(layer)
+    }
+
+    /// Like `build_searcher` with positions, grouping terms by stem in
+    /// `languages`.
+    pub(in crate::search) fn build_stemmed_searcher(
+        texts: &[&str],
+        languages: &[Language],
+    ) -> TierSearcher {
+        let docs: Vec<(&str, u8)> = texts.iter().map(|&t| (t, 5)).collect();
+        let (mut layer, vocabulary) = build_layer(&docs, true, &[], word_spans);
+        layer.set_stems(&StemIndex::build(&vocabulary, languages));
+        load(layer)
+    }
+
+    /// Like `build_searcher`, with synonym rules.
+    pub(in crate::search) fn build_synonym_searcher(
+        texts: &[&str],
+        rules: &[&str],
+        with_positions: bool,
+    ) -> TierSearcher {
+        let docs: Vec<(&str, u8)> = texts.iter().map(|&t| (t, 5)).collect();
+        let (mut layer, _) = build_layer(&docs, with_positions, &[], word_spans);
+        layer.set_synonyms(&Synonyms::parse(rules).unwrap());
+        load(layer)
+    }
+
+    /// Like `build_stemmed_searcher`, with a language per doc: each doc
+    /// skips its own stop words and its terms are stemmed in its language.
+    pub(in crate::search) fn build_multilingual_searcher(
+        texts: &[(&str, Language)],
+    ) -> TierSearcher {
+        let docs: Vec<(&str, u8)> = texts.iter().map(|&(t, _)| (t, 5)).collect();
+        let languages: Vec<Option<Language>> = texts.iter().map(|&(_, l)| Some(l)).collect();
+        let (mut layer, vocabulary) = build_layer(&docs, true, &languages, word_spans);
+        let mut terms: BTreeMap<Language, Vec<u32>> = BTreeMap::new();
+        for &(text, language) in texts {
+            for word in text.split_whitespace() {
+                if let Ok(idx) = vocabulary.binary_search_by(|t| t.as_str().cmp(word)) {
+                    terms.entry(language).or_default().push(idx as u32);
+                }
+            }
+        }
+        layer.set_stems(&StemIndex::build_per_language(&vocabulary, &terms));
+        load(layer)
+    }
+
+    fn doc_ids(searcher: &TierSearcher, query: &str) -> Vec<usize> {
+        let mut ids: Vec<usize> = searcher
+            .search_with_options(query, 10, SearchOptions::default())
+            .iter()
+            .map(|r| r.doc_id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_quoted_phrase_requires_adjacency() {
+        let searcher = build_searcher(
+            &[
+                "rust guarantees memory safety without gc",
+                "safety first then memory management",
+                "memory and thread safety",
+            ],
+            true,
+        );
+        assert!(searcher.has_positions());
+        assert_eq!(doc_ids(&searcher, "\"memory safety\""), vec![0]);
+        // Word order matters
+        assert!(doc_ids(&searcher, "\"safety memory\"").is_empty());
+    }
+
+    #[test]
+    fn test_phrase_skips_stop_word_slots() {
+        let searcher = build_searcher(&["state of the art search", "state art"], true);
+        assert_eq!(doc_ids(&searcher, "\"state of the art\""), vec![0]);
+        assert_eq!(doc_ids(&searcher, "\"state art\""), vec![1]);
+    }
+
+    #[test]
+    fn test_phrase_inside_boolean_query() {
+        let searcher = build_searcher(
+            &[
+                "memory safety in rust",
+                "memory safety in c",
+                "safety of memory in rust",
+            ],
+            true,
+        );
+        assert_eq!(doc_ids(&searcher, "\"memory safety\" -c"), vec![0]);
+        assert_eq!(
+            doc_ids(&searcher, "\"memory safety\" OR \"in c\""),
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn test_phrase_ranks_above_scattered_terms() {
+        let searcher = build_searcher(
+            &[
+                "safety is about memory",
+                "memory safety matters",
+                "memory leaks hurt safety",
+            ],
+            true,
+        );
+        let results = searcher.search("memory safety", 10);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].doc_id, 1);
+        assert!(results[0].score > results[1].score);
+    }
+
+    #[test]
+    fn test_without_positions_falls_back_to_and() {
+        let searcher = build_searcher(
+            &[
+                "memory safety matters",
+                "safety is about memory",
+                "memory only",
+            ],
+            false,
+        );
+        assert!(!searcher.has_positions());
+        assert!(searcher
+            .phrase_hits(&["memory", "safety"], None, None)
+            .is_none());
+        assert_eq!(doc_ids(&searcher, "\"memory safety\""), vec![0, 1]);
+
+        // No boost either: both docs score the same
+        let results = searcher.search("memory safety", 10);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].score, results[1].score);
+    }
+
+    #[test]
+    fn test_phrase_hits_report_each_occurrence() {
+        let searcher = build_searcher(&["memory safety and more memory safety"], true);
+        let hits = searcher
+            .phrase_hits(&["memory", "safety"], None, None)
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|h| h.score == 20.0));
+        assert!(searcher
+            .phrase_hits(&["memory", "unknown"], None, None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_stop_words_follow_the_query_language() {
+        let searcher = build_multilingual_searcher(&[
+            ("die hard sequel", Language::English),
+            ("rock hard candy", Language::English),
+            ("die katze schlaft", Language::German),
+        ]);
+        let phrase = |query: &str, language: Option<Language>| -> Vec<usize> {
+            let options = SearchOptions {
+                language,
+                ..SearchOptions::default()
+            };
+            let mut ids: Vec<usize> = searcher
+                .search_with_options(query, 10, options)
+                .iter()
+                .map(|r| r.doc_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+        // "die" is a stop word somewhere, so by default it's only a gap
+        assert_eq!(phrase("\"die hard\"", None), vec![0, 1]);
+        // English documents index it
+        assert_eq!(phrase("\"die hard\"", Some(Language::English)), vec![0]);
+        // German ones don't, and German queries skip it
+        assert!(phrase("\"die katze\"", Some(Language::English)).is_empty());
+        assert_eq!(phrase("\"die katze\"", Some(Language::German)), vec![2]);
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Term proximity: how close together do the query terms occur?
+//!
+//! "rust memory safety" should prefer a section that says "Rust's memory
+//! safety guarantees" over one that mentions Rust in the first paragraph,
+//! memory in the fifth and safety in a footnote. Both match all three terms;
+//! the first one is what the user meant.
+//!
+//! The measure is the **minimum span**: the smallest window of word positions
+//! that contains at least one occurrence of every query term. Adjacent terms
+//! give the tightest possible span (`terms - 1`), and the score multiplier
+//! falls off from [`MAX_PROXIMITY_BOOST`] toward 1.0 as the span widens.
+//!
+//! The boost only multiplies scores. It never changes a result's match type,
+//! so the bucket hierarchy in `compare_results` (title > section > ... >
+//! content) holds exactly as the Lean proofs state: a tight content match
+//! still loses to any title match.
+//!
+//! The same span drives the `NEAR/k` operator, which requires the terms to
+//! occur within `k` words of each other.
+//!
+//! Indexes without positions skip all of this: scores are plain sums and
+//! `NEAR` degrades to AND.
+
+use super::tiered::TierSearcher;
+use crate::index::is_stop_word_in;
+use crate::index::language::Language;
+use crate::types::FieldType;
+use std::collections::HashMap;
+
+/// Score multiplier for terms at the tightest possible span (adjacent).
+pub(crate) const MAX_PROXIMITY_BOOST: f64 = 2.0;
+
+/// Smallest window (last position - first position) containing every term.
+///
+/// `occurrences` are `(word position, query term index)` pairs, in any
+/// order. Returns `None` if some term in `0..num_terms` never occurs.
+pub(crate) fn min_span(occurrences: &mut [(u32, usize)], num_terms: usize) -> Option<u32> {
+    if num_terms == 0 {
+        return None;
+    }
+    occurrences.sort_unstable();
+
+    // Classic sliding window: extend right until every term is covered,
+    // then shrink from the left while it still is.
+    let mut counts = vec![0usize; num_terms];
+    let mut covered = 0;
+    let mut best: Option<u32> = None;
+    let mut left = 0;
+
+    for right in 0..occurrences.len() {
+        let (_, term) = occurrences[right];
+        if term >= num_terms {
+            continue;
+        }
+        if counts[term] == 0 {
+            covered += 1;
+        }
+        counts[term] += 1;
+
+        while covered == num_terms {
+            let (left_pos, left_term) = occurrences[left];
+            let span = occurrences[right].0 - left_pos;
+            best = Some(best.map_or(span, |b| b.min(span)));
+
+            if left_term < num_terms {
+                counts[left_term] -= 1;
+                if counts[left_term] == 0 {
+                    covered -= 1;
+                }
+            }
+            left += 1;
+        }
+    }
+
+    best
+}
+
+/// Score multiplier for a span covering `num_terms` terms.
+///
+/// `1 + (MAX - 1) * tightest / span`: adjacent terms get the full
+/// [`MAX_PROXIMITY_BOOST`], twice that span gets half the bonus, and so on.
+/// Single-term queries have no proximity and get 1.0.
+pub(crate) fn proximity_boost(span: u32, num_terms: usize) -> f64 {
+    if num_terms < 2 {
+        return 1.0;
+    }
+    let tightest = (num_terms - 1) as f64;
+    1.0 + (MAX_PROXIMITY_BOOST - 1.0) * tightest / (span as f64).max(tightest)
+}
+
+/// Widest span `NEAR/k` allows for `num_terms` terms.
+///
+/// Two terms may be at most `k` positions apart. Each extra term widens the
+/// window by another `k`, so `a NEAR/3 b NEAR/3 c` fits in 6 positions.
+pub(crate) fn near_window(distance: u32, num_terms: usize) -> u32 {
+    distance.saturating_mul(num_terms.saturating_sub(1) as u32)
+}
+
+impl TierSearcher {
+    /// Minimum span per document for exact occurrences of `terms`.
+    ///
+    /// Stop words in `language` (any language without one) are ignored, as
+    /// they're never indexed, and so are occurrences outside `scope`. Returns
+    /// `None` when the index has no positions. Documents missing any term
+    /// are absent.
+    pub(crate) fn doc_spans(
+        &self,
+        terms: &[&str],
+        scope: Option<FieldType>,
+        language: Option<Language>,
+    ) -> Option<HashMap<usize, u32>> {
+        if !self.has_positions() {
+            return None;
+        }
+
+        let mut vocab_ids = Vec::with_capacity(terms.len());
+        for term in terms.iter().filter(|t| !is_stop_word_in(t, language)) {
+            match self.vocabulary().binary_search_by(|t| t.as_str().cmp(term)) {
+                Ok(vocab_idx) => vocab_ids.push(vocab_idx),
+                Err(_) => return Some(HashMap::new()),
+            }
+        }
+
+        let mut occurrences: HashMap<usize, Vec<(u32, usize)>> = HashMap::new();
+        for (term_idx, &vocab_idx) in vocab_ids.iter().enumerate() {
+            let postings = self.postings().get(vocab_idx).map_or(&[][..], |p| p);
+            let positions = self.positions().get(vocab_idx).map_or(&[][..], |p| p);
+            for (entry, &pos) in postings.iter().zip(positions) {
+                if scope.is_some_and(|f| !f.contains_heading_level(entry.heading_level)) {
+                    continue;
+                }
+                occurrences
+                    .entry(entry.doc_id as usize)
+                    .or_default()
+                    .push((pos, term_idx));
+            }
+        }
+
+        Some(
+            occurrences
+                .into_iter()
+                .filter_map(|(doc_id, mut occ)| {
+                    Some((doc_id, min_span(&mut occ, vocab_ids.len())?))
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::phrase::tests::{build_searcher, build_searcher_with_levels};
+    use crate::types::MatchType;
+
+    fn ranked(searcher: &TierSearcher, query: &str) -> Vec<usize> {
+        searcher
+            .search(query, 10)
+            .iter()
+            .map(|r| r.doc_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_min_span_adjacent() {
+        let mut occ = vec![(4, 1), (3, 0)];
+        assert_eq!(min_span(&mut occ, 2), Some(1));
+    }
+
+    #[test]
+    fn test_min_span_picks_tightest_window() {
+        // term 0 at 0 and 20, term 1 at 10 and 22, term 2 at 21
+        let mut occ = vec![(0, 0), (10, 1), (20, 0), (21, 2), (22, 1)];
+        assert_eq!(min_span(&mut occ, 3), Some(2));
+    }
+
+    #[test]
+    fn test_min_span_missing_term() {
+        let mut occ = vec![(0, 0), (5, 0)];
+        assert_eq!(min_span(&mut occ, 2), None);
+        assert_eq!(min_span(&mut [], 0), None);
+    }
+
+    #[test]
+    fn test_proximity_boost_decays_with_span() {
+        assert_eq!(proximity_boost(1, 2), MAX_PROXIMITY_BOOST);
+        assert_eq!(proximity_boost(2, 3), MAX_PROXIMITY_BOOST);
+        assert!(proximity_boost(2, 2) < proximity_boost(1, 2));
+        assert!(proximity_boost(50, 2) > 1.0);
+        assert_eq!(proximity_boost(7, 1), 1.0);
+    }
+
+    #[test]
+    fn test_near_window_grows_with_terms() {
+        assert_eq!(near_window(3, 2), 3);
+        assert_eq!(near_window(3, 3), 6);
+        assert_eq!(near_window(3, 1), 0);
+    }
+
+    #[test]
+    fn test_close_terms_rank_higher() {
+        let searcher = build_searcher(
+            &[
+                "memory bugs are common while safety is a goal",
+                "memory safety matters",
+                "we need safety for memory",
+            ],
+            true,
+        );
+        assert_eq!(ranked(&searcher, "memory safety"), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_proximity_applies_to_prefix_tier() {
+        let searcher = build_searcher(
+            &[
+                "memory bugs are common while safety is a goal",
+                "memory safety matters",
+            ],
+            true,
+        );
+        let results = searcher.search("memo safe", 10);
+        assert_eq!(
+            results.iter().map(|r| r.doc_id).collect::<Vec<_>>(),
+            vec![1, 0]
+        );
+        assert!(results.iter().all(|r| r.tier == 2));
+    }
+
+    #[test]
+    fn test_proximity_stays_inside_bucket() {
+        // Scattered terms in a title beat adjacent terms in content
+        let searcher = build_searcher_with_levels(
+            &[
+                ("memory safety matters", 5),
+                ("memory bugs are common while safety is a goal", 0),
+            ],
+            true,
+        );
+        let results = searcher.search("memory safety", 10);
+        assert_eq!(results[0].doc_id, 1);
+        assert_eq!(results[0].match_type, MatchType::Title);
+        assert!(results[1].score > results[0].score);
+    }
+
+    #[test]
+    fn test_near_operator_limits_distance() {
+        let searcher = build_searcher(
+            &[
+                "memory safety matters",
+                "safety of your memory",
+                "memory bugs are common while safety is a goal",
+            ],
+            true,
+        );
+        assert_eq!(ranked(&searcher, "memory NEAR/1 safety"), vec![0]);
+        // Either order counts
+        let mut ids = ranked(&searcher, "memory NEAR/3 safety");
+        ids.sort();
+        assert_eq!(ids, vec![0, 1]);
+        assert_eq!(ranked(&searcher, "memory NEAR/5 safety").len(), 3);
+        assert!(ranked(&searcher, "memory NEAR/3 unknown").is_empty());
+    }
+
+    #[test]
+    fn test_without_positions_near_is_and() {
+        let searcher = build_searcher(
+            &[
+                "memory safety matters",
+                "memory bugs are common while safety is a goal",
+            ],
+            false,
+        );
+        assert!(searcher
+            .doc_spans(&["memory", "safety"], None, None)
+            .is_none());
+        assert_eq!(ranked(&searcher, "memory NEAR/1 safety").len(), 2);
+    }
+}
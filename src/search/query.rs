@@ -0,0 +1,804 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Boolean query parser: turns a search box string into a small AST.
+//!
+//! Most queries are just words, and those keep going through the fast
+//! whitespace-split path in `TierSearcher`. But docs sites need a little more:
+//! "kernel -deprecated", "tokio OR async-std", `"exact"` to switch off typo
+//! tolerance for one word. This module parses that syntax into a `QueryNode`
+//! tree that the tiered searcher evaluates.
+//!
+//! # Syntax
+//!
+//! | Input            | Meaning                                              |
+//! |------------------|------------------------------------------------------|
+//! | `a b`, `a AND b` | both terms required (the default)                    |
+//! | `a OR b`         | either term                                          |
+//! | `-a`, `NOT a`    | exclude documents matching `a`                       |
+//! | `+a`             | `a` is required (explicit form of the default)       |
+//! | `"a"`            | exact-only: no prefix or fuzzy expansion for `a`     |
+//! | `"a b"`          | phrase: `a` immediately followed by `b`              |
+//! | `a NEAR/5 b`     | both terms, at most 5 words apart (either order)     |
+//! | `title:a`        | `a` must match in a document title                   |
+//! | `heading:a`      | `a` must match in a section heading                  |
+//! | `content:a`      | `a` must match in body text                          |
+//! | `(a OR b) c`     | grouping                                             |
+//!
+//! `OR` binds tighter than the implicit AND, so `linux kernel OR driver` means
+//! `linux AND (kernel OR driver)`, and `NEAR/k` binds tighter still. Operators
+//! are only recognized in uppercase; a lowercase "or" is just a word.
+//!
+//! `NEAR/k` takes plain words on both sides and chains (`a NEAR/3 b NEAR/3 c`).
+//! Anything else around it (a group, a phrase) is just ANDed.
+//!
+//! Field prefixes apply to whatever follows them, so `title:"memory safety"`
+//! and `heading:(install OR setup)` work too. Like operators, field names are
+//! only recognized in lowercase and glued to their operand; `title: rust` is
+//! two words.
+//!
+//! Against an index built with the code tokenizer ([`QueryNode::parse_with`]),
+//! words are cut the way the indexer cut them: `vec::push` and `VecPush` both
+//! need "vec" and "push", `C++` stays whole, and a word starting with `--` is
+//! a command-line flag rather than an exclusion.
+//!
+//! The parser never fails. Search boxes see half-typed input all the time, so
+//! unbalanced parentheses are closed at the end, stray `)` and dangling
+//! operators are dropped, and an unterminated quote runs to the end of input.
+
+use crate::index::segment::{is_cjk, is_thai};
+use crate::index::tokenizer::{code_query_words, Tokenizer};
+use crate::index::translit::{is_transliterable, romanize, with_romanized};
+use crate::index::word_spans;
+use crate::types::FieldType;
+use crate::util::normalize::fold;
+
+/// A node in a parsed boolean query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryNode {
+    /// A single lowercased term.
+    ///
+    /// `exact` is set for quoted terms: only Tier 1 (exact) matches count.
+    Term { text: String, exact: bool },
+    /// Quoted words that must appear consecutively, in order.
+    ///
+    /// Words are lowercased and may include stop words: they aren't indexed,
+    /// but they still hold a position, so `"state of the art"` keeps its gaps.
+    Phrase(Vec<String>),
+    /// Terms that must occur within `distance` words of each other.
+    ///
+    /// Matched exactly, like phrases. With more than two terms the window
+    /// grows by `distance` per extra term.
+    Near { terms: Vec<String>, distance: u32 },
+    /// All children must match. `Not` children act as exclusions.
+    And(Vec<QueryNode>),
+    /// At least one child must match.
+    Or(Vec<QueryNode>),
+    /// Documents matching the child are excluded.
+    Not(Box<QueryNode>),
+    /// The child only matches postings from one field.
+    Field {
+        field: FieldType,
+        node: Box<QueryNode>,
+    },
+}
+
+impl QueryNode {
+    /// Parse a query string into an AST.
+    ///
+    /// Returns `None` when nothing searchable is left (empty input, only
+    /// operators, empty quotes).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sorex::QueryNode;
+    ///
+    /// let ast = QueryNode::parse("kernel -deprecated").unwrap();
+    /// assert!(ast.plain_terms().is_none());
+    ///
+    /// let plain = QueryNode::parse("Rust  Guide").unwrap();
+    /// assert_eq!(plain.plain_terms(), Some(vec!["rust", "guide"]));
+    /// ```
+    pub fn parse(input: &str) -> Option<QueryNode> {
+        Self::parse_with(input, Tokenizer::Text)
+    }
+
+    /// [`QueryNode::parse`] for an index whose words `tokenizer` cut.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sorex::{QueryNode, Tokenizer};
+    ///
+    /// let ast = QueryNode::parse_with("VecPush", Tokenizer::Code).unwrap();
+    /// assert_eq!(ast.plain_terms(), Some(vec!["vec", "push"]));
+    /// ```
+    pub fn parse_with(input: &str, tokenizer: Tokenizer) -> Option<QueryNode> {
+        Self::parse_for(input, tokenizer, false)
+    }
+
+    /// [`QueryNode::parse_with`] for an index that also holds its Cyrillic,
+    /// Greek and Devanagari words romanized: words in those scripts are
+    /// romanized to match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sorex::{QueryNode, Tokenizer};
+    ///
+    /// let ast = QueryNode::parse_transliterated("Москва", Tokenizer::Text).unwrap();
+    /// assert_eq!(ast.plain_terms(), Some(vec!["moskva"]));
+    /// ```
+    pub fn parse_transliterated(input: &str, tokenizer: Tokenizer) -> Option<QueryNode> {
+        Self::parse_for(input, tokenizer, true)
+    }
+
+    fn parse_for(input: &str, tokenizer: Tokenizer, transliterate: bool) -> Option<QueryNode> {
+        let tokens = tokenize(input, tokenizer);
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            tokenizer,
+            transliterate,
+        };
+        let mut node = parser.parse_and();
+
+        // Stray ')' at top level: skip it and keep parsing the rest
+        while parser.pos < parser.tokens.len() {
+            parser.pos += 1;
+            if let Some(rest) = parser.parse_and() {
+                node = Some(match node {
+                    Some(left) => QueryNode::and(vec![left, rest]),
+                    None => rest,
+                });
+            }
+        }
+
+        node
+    }
+
+    /// True if `input` needs the boolean evaluator rather than the plain
+    /// whitespace-split tier pipeline.
+    pub fn uses_boolean_syntax(input: &str) -> bool {
+        Self::parse(input).is_some_and(|ast| ast.plain_terms().is_none())
+    }
+
+    /// If this query is a plain list of ANDed, unquoted terms, return them.
+    ///
+    /// Plain queries take the existing whitespace-split tier path unchanged.
+    /// Anything using OR, exclusion, quotes, or fields returns `None`.
+    pub fn plain_terms(&self) -> Option<Vec<&str>> {
+        match self {
+            QueryNode::Term { text, exact: false } => Some(vec![text.as_str()]),
+            QueryNode::And(children) => children
+                .iter()
+                .map(|child| match child {
+                    QueryNode::Term { text, exact: false } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    /// Build an AND node, flattening nested ANDs and unwrapping single children.
+    fn and(children: Vec<QueryNode>) -> QueryNode {
+        Self::flatten(children, true)
+    }
+
+    /// Build an OR node, flattening nested ORs and unwrapping single children.
+    fn or(children: Vec<QueryNode>) -> QueryNode {
+        Self::flatten(children, false)
+    }
+
+    fn flatten(children: Vec<QueryNode>, is_and: bool) -> QueryNode {
+        let mut flat = Vec::with_capacity(children.len());
+        for child in children {
+            match child {
+                QueryNode::And(inner) if is_and => flat.extend(inner),
+                QueryNode::Or(inner) if !is_and => flat.extend(inner),
+                other => flat.push(other),
+            }
+        }
+        if flat.len() == 1 {
+            flat.pop().expect("len checked")
+        } else if is_and {
+            QueryNode::And(flat)
+        } else {
+            QueryNode::Or(flat)
+        }
+    }
+}
+
+// ============================================================================
+// TOKENIZER
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Near(u32),
+    Field(FieldType),
+    Plus,
+    Minus,
+}
+
+fn is_word_boundary(c: char) -> bool {
+    c.is_whitespace() || c == '(' || c == ')' || c == '"'
+}
+
+fn tokenize(input: &str, tokenizer: Tokenizer) -> Vec<Token> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '"' => {
+                let start = i + 1;
+                let end = chars[start..]
+                    .iter()
+                    .position(|&c| c == '"')
+                    .map_or(chars.len(), |p| start + p);
+                tokens.push(Token::Quoted(chars[start..end].iter().collect()));
+                i = end + 1;
+            }
+            // '-'/'+' only act as modifiers when glued to what follows, and
+            // "--flag" is a word when searching code
+            '-' | '+'
+                if chars.get(i + 1).is_some_and(|&n| !n.is_whitespace())
+                    && !(tokenizer == Tokenizer::Code && chars[i..].starts_with(&['-', '-'])) =>
+            {
+                tokens.push(if c == '-' { Token::Minus } else { Token::Plus });
+                i += 1;
+            }
+            _ if field_prefix(&chars[i..]).is_some() => {
+                let (field, len) = field_prefix(&chars[i..]).expect("checked");
+                tokens.push(Token::Field(field));
+                i += len;
+            }
+            _ => {
+                let start = i;
+                while i < chars.len() && !is_word_boundary(chars[i]) {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => match word.strip_prefix("NEAR/").map(str::parse::<u32>) {
+                        Some(Ok(distance)) => Token::Near(distance),
+                        _ => Token::Word(word),
+                    },
+                });
+            }
+        }
+    }
+
+    tokens
+}
+
+/// A `field:` prefix glued to an operand, with its length in chars.
+fn field_prefix(chars: &[char]) -> Option<(FieldType, usize)> {
+    let colon = chars
+        .iter()
+        .take_while(|c| c.is_ascii_lowercase() || **c == ':')
+        .position(|&c| c == ':')?;
+    let name: String = chars[..colon].iter().collect();
+    let field = FieldType::from_name(&name)?;
+    chars
+        .get(colon + 1)
+        .is_some_and(|c| !c.is_whitespace())
+        .then_some((field, colon + 1))
+}
+
+// ============================================================================
+// PARSER (recursive descent)
+// ============================================================================
+//
+//   and_expr  := or_expr ( [AND] or_expr )*
+//   or_expr   := near_expr ( OR near_expr )*
+//   near_expr := unary ( NEAR/k unary )*
+//   unary     := ( '-' | NOT ) unary | '+' unary | FIELD unary | primary
+//   primary  := WORD | QUOTED | '(' and_expr ')'
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    tokenizer: Tokenizer,
+    /// Romanize Cyrillic, Greek and Devanagari words
+    transliterate: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_and(&mut self) -> Option<QueryNode> {
+        let mut children = Vec::new();
+        while let Some(token) = self.peek() {
+            match token {
+                Token::RParen => break,
+                Token::And | Token::Or | Token::Near(_) => self.pos += 1, // dangling operator
+                _ => {
+                    if let Some(node) = self.parse_or() {
+                        children.push(node);
+                    }
+                }
+            }
+        }
+        (!children.is_empty()).then(|| QueryNode::and(children))
+    }
+
+    fn parse_or(&mut self) -> Option<QueryNode> {
+        let mut children: Vec<QueryNode> = self.parse_near().into_iter().collect();
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            if let Some(node) = self.parse_near() {
+                children.push(node);
+            }
+        }
+        (!children.is_empty()).then(|| QueryNode::or(children))
+    }
+
+    fn parse_near(&mut self) -> Option<QueryNode> {
+        let mut operands: Vec<QueryNode> = self.parse_unary().into_iter().collect();
+        let mut distance = None;
+        while let Some(&Token::Near(k)) = self.peek() {
+            self.pos += 1;
+            if let Some(node) = self.parse_unary() {
+                operands.push(node);
+                // Mixed distances in one chain: the loosest wins
+                distance = Some(distance.map_or(k, |d: u32| d.max(k)));
+            }
+        }
+
+        let Some(distance) = distance else {
+            return operands.pop();
+        };
+        if operands.len() < 2 {
+            return operands.pop();
+        }
+
+        let terms: Option<Vec<String>> = operands
+            .iter()
+            .map(|node| match node {
+                QueryNode::Term { text, .. } => Some(text.clone()),
+                _ => None,
+            })
+            .collect();
+        Some(match terms {
+            Some(terms) => QueryNode::Near { terms, distance },
+            None => QueryNode::and(operands),
+        })
+    }
+
+    fn parse_unary(&mut self) -> Option<QueryNode> {
+        match self.peek()? {
+            Token::Minus | Token::Not => {
+                self.pos += 1;
+                self.parse_unary().map(|n| QueryNode::Not(Box::new(n)))
+            }
+            Token::Plus => {
+                self.pos += 1;
+                self.parse_unary()
+            }
+            &Token::Field(field) => {
+                self.pos += 1;
+                // Keep exclusions on the outside so AND still sees them:
+                // `title:-a` means `-title:a`
+                self.parse_unary().map(|node| match node {
+                    QueryNode::Not(inner) => {
+                        QueryNode::Not(Box::new(QueryNode::Field { field, node: inner }))
+                    }
+                    node => QueryNode::Field {
+                        field,
+                        node: Box::new(node),
+                    },
+                })
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Option<QueryNode> {
+        let token = self.peek()?.clone();
+        match token {
+            Token::Word(word)
+                if self.tokenizer == Tokenizer::Code
+                    || word.chars().any(|c| is_cjk(c) || is_thai(c))
+                    || (self.transliterate && word.chars().any(is_transliterable)) =>
+            {
+                self.pos += 1;
+                // Cut into the indexer's bigrams: "東京都" needs "東京" and
+                // "京都"; or its code parts: "VecPush" needs "vec" and "push";
+                // or romanized: "Москва" is "moskva"
+                let terms = self
+                    .index_words(&word)
+                    .into_iter()
+                    .map(|text| QueryNode::Term { text, exact: false })
+                    .collect::<Vec<_>>();
+                (!terms.is_empty()).then(|| QueryNode::and(terms))
+            }
+            Token::Word(word) => {
+                self.pos += 1;
+                // Same folding as the indexer: "Café" finds "cafe"
+                let text = fold(&word);
+                (!text.is_empty()).then_some(QueryNode::Term { text, exact: false })
+            }
+            Token::Quoted(content) => {
+                self.pos += 1;
+                // Split like the indexer does so "e-mail" is the phrase "e mail"
+                // and "東京都" the phrase "東京 京都"
+                let mut words = self.index_words(&content);
+                match words.len() {
+                    0 => None,
+                    1 => Some(QueryNode::Term {
+                        text: words.pop().expect("len checked"),
+                        exact: true,
+                    }),
+                    _ => Some(QueryNode::Phrase(words)),
+                }
+            }
+            Token::LParen => {
+                self.pos += 1;
+                let inner = self.parse_and();
+                if self.peek() == Some(&Token::RParen) {
+                    self.pos += 1;
+                }
+                inner
+            }
+            // Operators where a term was expected: skip them
+            Token::And
+            | Token::Or
+            | Token::Near(_)
+            | Token::Field(_)
+            | Token::RParen
+            | Token::Plus
+            | Token::Minus
+            | Token::Not => None,
+        }
+    }
+
+    /// The words the indexer would store for `text`, in order. For code,
+    /// the parts of identifiers with several and the whole of the rest.
+    /// Transliterated, words in Cyrillic, Greek or Devanagari are romanized.
+    fn index_words(&self, text: &str) -> Vec<String> {
+        match (self.tokenizer, self.transliterate) {
+            (Tokenizer::Text, false) => word_spans(text)
+                .into_iter()
+                .map(|(word, _, _)| word)
+                .collect(),
+            (Tokenizer::Text, true) => with_romanized(text, word_spans(text))
+                .into_iter()
+                .map(|(word, _, _)| word)
+                .collect(),
+            (Tokenizer::Code, false) => code_query_words(text),
+            (Tokenizer::Code, true) => code_query_words(text)
+                .into_iter()
+                .map(|word| {
+                    if word.chars().any(is_transliterable) {
+                        romanize(&word)
+                    } else {
+                        word
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(text: &str) -> QueryNode {
+        QueryNode::Term {
+            text: text.to_string(),
+            exact: false,
+        }
+    }
+
+    fn exact(text: &str) -> QueryNode {
+        QueryNode::Term {
+            text: text.to_string(),
+            exact: true,
+        }
+    }
+
+    fn phrase(words: &[&str]) -> QueryNode {
+        QueryNode::Phrase(words.iter().map(|w| w.to_string()).collect())
+    }
+
+    fn not(node: QueryNode) -> QueryNode {
+        QueryNode::Not(Box::new(node))
+    }
+
+    fn field(field: FieldType, node: QueryNode) -> QueryNode {
+        QueryNode::Field {
+            field,
+            node: Box::new(node),
+        }
+    }
+
+    #[test]
+    fn test_plain_terms() {
+        let ast = QueryNode::parse("Rust Programming").unwrap();
+        assert_eq!(ast, QueryNode::And(vec![term("rust"), term("programming")]));
+        assert_eq!(ast.plain_terms(), Some(vec!["rust", "programming"]));
+
+        // Explicit AND and '+' are the default
+        let ast = QueryNode::parse("+rust AND programming").unwrap();
+        assert_eq!(ast.plain_terms(), Some(vec!["rust", "programming"]));
+    }
+
+    #[test]
+    fn test_exclusion() {
+        let ast = QueryNode::parse("kernel -deprecated").unwrap();
+        assert_eq!(
+            ast,
+            QueryNode::And(vec![term("kernel"), not(term("deprecated"))])
+        );
+        assert!(ast.plain_terms().is_none());
+        assert!(QueryNode::uses_boolean_syntax("kernel -deprecated"));
+        assert!(!QueryNode::uses_boolean_syntax("kernel deprecated"));
+
+        let ast = QueryNode::parse("kernel NOT deprecated").unwrap();
+        assert_eq!(
+            ast,
+            QueryNode::And(vec![term("kernel"), not(term("deprecated"))])
+        );
+    }
+
+    #[test]
+    fn test_or_binds_tighter_than_and() {
+        let ast = QueryNode::parse("linux kernel OR driver").unwrap();
+        assert_eq!(
+            ast,
+            QueryNode::And(vec![
+                term("linux"),
+                QueryNode::Or(vec![term("kernel"), term("driver")])
+            ])
+        );
+    }
+
+    #[test]
+    fn test_grouping() {
+        let ast = QueryNode::parse("(rust OR go) -python").unwrap();
+        assert_eq!(
+            ast,
+            QueryNode::And(vec![
+                QueryNode::Or(vec![term("rust"), term("go")]),
+                not(term("python"))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_quoted_is_exact() {
+        let ast = QueryNode::parse("\"Rust\"").unwrap();
+        assert_eq!(ast, exact("rust"));
+        assert!(ast.plain_terms().is_none());
+
+        let ast = QueryNode::parse("\"e-mail\"").unwrap();
+        assert_eq!(ast, phrase(&["e", "mail"]));
+    }
+
+    #[test]
+    fn test_quoted_words_are_a_phrase() {
+        let ast = QueryNode::parse("\"Memory Safety\" rust").unwrap();
+        assert_eq!(
+            ast,
+            QueryNode::And(vec![phrase(&["memory", "safety"]), term("rust")])
+        );
+        assert!(ast.plain_terms().is_none());
+
+        // Stop words stay in the phrase to keep their slot
+        let ast = QueryNode::parse("\"state of the art\"").unwrap();
+        assert_eq!(ast, phrase(&["state", "of", "the", "art"]));
+    }
+
+    #[test]
+    fn test_unspaced_scripts_split_into_bigrams() {
+        let ast = QueryNode::parse("東京都 rust").unwrap();
+        assert_eq!(
+            ast,
+            QueryNode::And(vec![term("東京"), term("京都"), term("rust")])
+        );
+        assert_eq!(ast.plain_terms(), Some(vec!["東京", "京都", "rust"]));
+
+        let ast = QueryNode::parse("\"東京都\"").unwrap();
+        assert_eq!(ast, phrase(&["東京", "京都"]));
+        assert_eq!(QueryNode::parse("猫").unwrap(), term("猫"));
+    }
+
+    #[test]
+    fn test_code_tokens() {
+        let code = |input| QueryNode::parse_with(input, Tokenizer::Code).unwrap();
+        let push = QueryNode::And(vec![term("vec"), term("push")]);
+        assert_eq!(code("vec::push"), push);
+        assert_eq!(code("VecPush"), push);
+        assert_eq!(code("vec_push"), push);
+        assert_eq!(
+            code("C++ -java"),
+            QueryNode::And(vec![term("c++"), QueryNode::Not(Box::new(term("java")))])
+        );
+        assert_eq!(code("--verbose"), term("--verbose"));
+        assert_eq!(code("\"Vec::push\""), phrase(&["vec", "push"]));
+        assert_eq!(
+            code("\"C#\""),
+            QueryNode::Term {
+                text: "c#".to_string(),
+                exact: true
+            }
+        );
+
+        // Text mode still shreds them
+        assert_eq!(
+            QueryNode::parse("--verbose").unwrap(),
+            QueryNode::Not(Box::new(QueryNode::Not(Box::new(term("verbose")))))
+        );
+    }
+
+    #[test]
+    fn test_transliterated() {
+        let translit = |input| QueryNode::parse_transliterated(input, Tokenizer::Text).unwrap();
+        assert_eq!(translit("Москва"), term("moskva"));
+        assert_eq!(translit("नमस्ते"), term("namaste"));
+        assert_eq!(
+            translit("\"Красная площадь\" -Αθήνα"),
+            QueryNode::And(vec![
+                phrase(&["krasnaya", "ploshchad"]),
+                QueryNode::Not(Box::new(term("athina")))
+            ])
+        );
+        // Latin words stay as they are
+        assert_eq!(translit("moskva"), term("moskva"));
+
+        // Without transliteration Cyrillic is only folded
+        assert_eq!(QueryNode::parse("Москва").unwrap(), term("москва"));
+    }
+
+    #[test]
+    fn test_near_operator() {
+        let ast = QueryNode::parse("memory NEAR/3 Safety").unwrap();
+        assert_eq!(
+            ast,
+            QueryNode::Near {
+                terms: vec!["memory".to_string(), "safety".to_string()],
+                distance: 3,
+            }
+        );
+        assert!(ast.plain_terms().is_none());
+
+        // Chains take the loosest distance; NEAR binds tighter than OR
+        let ast = QueryNode::parse("a NEAR/2 b NEAR/5 c OR d").unwrap();
+        assert_eq!(
+            ast,
+            QueryNode::Or(vec![
+                QueryNode::Near {
+                    terms: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                    distance: 5,
+                },
+                term("d"),
+            ])
+        );
+
+        // Non-term operands degrade to AND
+        let ast = QueryNode::parse("(a OR b) NEAR/2 c").unwrap();
+        assert_eq!(
+            ast,
+            QueryNode::And(vec![QueryNode::Or(vec![term("a"), term("b")]), term("c")])
+        );
+    }
+
+    #[test]
+    fn test_malformed_near() {
+        assert_eq!(QueryNode::parse("rust NEAR/3"), Some(term("rust")));
+        assert_eq!(QueryNode::parse("NEAR/3 rust"), Some(term("rust")));
+        // Not a valid distance: just a word
+        assert_eq!(QueryNode::parse("NEAR/x"), Some(term("near/x")));
+    }
+
+    #[test]
+    fn test_field_operators() {
+        let ast = QueryNode::parse("title:Rust guide").unwrap();
+        assert_eq!(
+            ast,
+            QueryNode::And(vec![field(FieldType::Title, term("rust")), term("guide")])
+        );
+        assert!(ast.plain_terms().is_none());
+        assert!(QueryNode::uses_boolean_syntax("heading:install"));
+
+        // Fields scope phrases and groups
+        let ast = QueryNode::parse("content:\"memory safety\"").unwrap();
+        assert_eq!(
+            ast,
+            field(FieldType::Content, phrase(&["memory", "safety"]))
+        );
+        let ast = QueryNode::parse("heading:(install OR setup)").unwrap();
+        assert_eq!(
+            ast,
+            field(
+                FieldType::Heading,
+                QueryNode::Or(vec![term("install"), term("setup")])
+            )
+        );
+
+        // Exclusions stay outermost either way
+        assert_eq!(
+            QueryNode::parse("rust title:-deprecated"),
+            QueryNode::parse("rust -title:deprecated")
+        );
+        assert_eq!(
+            QueryNode::parse("-title:deprecated"),
+            Some(not(field(FieldType::Title, term("deprecated"))))
+        );
+    }
+
+    #[test]
+    fn test_field_lookalikes_are_words() {
+        // Unknown names, uppercase, and a trailing space aren't fields
+        assert_eq!(QueryNode::parse("author:bob"), Some(term("author:bob")));
+        assert_eq!(QueryNode::parse("Title:rust"), Some(term("title:rust")));
+        assert_eq!(
+            QueryNode::parse("title: rust"),
+            Some(QueryNode::And(vec![term("title:"), term("rust")]))
+        );
+        assert_eq!(QueryNode::parse("title:"), Some(term("title:")));
+        assert_eq!(QueryNode::parse("title:()"), None);
+    }
+
+    #[test]
+    fn test_hyphen_inside_word_is_literal() {
+        let ast = QueryNode::parse("async-std").unwrap();
+        assert_eq!(ast, term("async-std"));
+    }
+
+    #[test]
+    fn test_lowercase_or_is_a_word() {
+        let ast = QueryNode::parse("this or that").unwrap();
+        assert_eq!(ast.plain_terms(), Some(vec!["this", "or", "that"]));
+    }
+
+    #[test]
+    fn test_malformed_input_never_fails() {
+        assert_eq!(QueryNode::parse(""), None);
+        assert_eq!(QueryNode::parse("   "), None);
+        assert_eq!(QueryNode::parse("OR AND"), None);
+        assert_eq!(QueryNode::parse("\"\""), None);
+        assert_eq!(QueryNode::parse("(rust"), Some(term("rust")));
+        assert_eq!(
+            QueryNode::parse("rust) guide"),
+            Some(QueryNode::And(vec![term("rust"), term("guide")]))
+        );
+        assert_eq!(QueryNode::parse("rust OR"), Some(term("rust")));
+        assert_eq!(
+            QueryNode::parse("\"rust guide"),
+            Some(phrase(&["rust", "guide"]))
+        );
+        assert_eq!(
+            QueryNode::parse("- rust"),
+            Some(QueryNode::And(vec![term("-"), term("rust")]))
+        );
+    }
+}
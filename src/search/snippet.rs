@@ -0,0 +1,582 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Snippets: the words around a match, with the matched terms marked.
+//!
+//! A result that says "Rust Guide" and shows the same excerpt for every query
+//! makes the user click to find out why it matched. A snippet shows them: a
+//! window of the document's stored text around the match, in the section the
+//! match came from, with every query term highlighted.
+//!
+//! "Every query term" means what actually matched, not just what was typed.
+//! A prefix result for `optim` highlights "optimization", and a fuzzy result
+//! for `pyhton` highlights "python". Expansions follow the result's tier: an
+//! exact hit only highlights exact words, so searching "rust" doesn't light
+//! up every "trust" in the paragraph.
+//!
+//! Window choice is greedy and cheap. Among the highlighted words in the
+//! section, pick the window of at most `max_chars` that covers the most
+//! distinct query terms (then the most matches, then the earliest). Put about
+//! a third of the spare room before it, and snap the edges to word boundaries.
+//!
+//! Snippets need an index built with stored text (`FormatFlags::HAS_TEXT`).
+//! Without it, [`TierSearcher::snippet`] returns `None` and callers fall back
+//! to the excerpt.
+
+use super::query::QueryNode;
+use super::tiered::{prefix_search_vocabulary, SearchOptions, SearchResult, TierSearcher};
+use crate::binary::DocText;
+use crate::index::tokenizer::Tokenizer;
+use crate::index::translit::romanized_spans;
+use crate::index::{is_stop_word, word_spans_with};
+use std::collections::{HashMap, HashSet};
+
+/// Snippet length (in characters) used when the caller doesn't pick one.
+pub const DEFAULT_SNIPPET_CHARS: usize = 160;
+
+/// A window of stored text around a match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub text: String,
+    /// Byte ranges of matched words within `text`, in order
+    pub highlights: Vec<(usize, usize)>,
+    /// The document has more text before `text` (render a leading "…")
+    pub clipped_start: bool,
+    /// The document has more text after `text`
+    pub clipped_end: bool,
+}
+
+/// A highlighted word: byte range in the doc text and the query term it matched.
+#[derive(Debug, Clone, Copy)]
+struct Hit {
+    start: usize,
+    end: usize,
+    term: usize,
+}
+
+impl TierSearcher {
+    /// True if this index stores document text (snippets available).
+    #[inline]
+    pub fn has_text(&self) -> bool {
+        self.stored_text().is_some()
+    }
+
+    /// Decode one document's stored text.
+    pub fn doc_text(&self, doc_id: usize) -> Option<DocText> {
+        self.stored_text()?.get(doc_id)
+    }
+
+    /// Cut a snippet for `result`, which `query` produced.
+    ///
+    /// The window comes from the section the result points at, falling back
+    /// to the rest of the document if the section has no highlightable word
+    /// (and to the section's opening if nothing does). `max_chars` counts
+    /// characters, not bytes. Returns `None` without stored text.
+    pub fn snippet(&self, result: &SearchResult, query: &str, max_chars: usize) -> Option<Snippet> {
+        let doc = self.doc_text(result.doc_id)?;
+        let text = doc.text.as_str();
+        let terms = self.highlight_terms(query, result);
+        let max_chars = max_chars.max(1);
+
+        // Titles are already on screen, so prefer body text over them
+        let whole = (0, text.len());
+        let in_section = span_range(&doc, |s| {
+            s.section_idx == result.section_idx && s.heading_level > 0
+        });
+        let body = span_range(&doc, |s| s.heading_level > 0);
+        let regions: Vec<(usize, usize)> = [in_section, body, Some(whole)]
+            .into_iter()
+            .flatten()
+            .filter(|&(start, end)| text.is_char_boundary(start) && text.is_char_boundary(end))
+            .collect();
+
+        let found = regions.iter().find_map(|&region| {
+            let hits = find_hits(
+                text,
+                region,
+                &terms,
+                self.tokenizer(),
+                self.transliterated(),
+            );
+            (!hits.is_empty()).then_some((region, hits))
+        });
+        let (region, hits) = found.unwrap_or((regions[0], Vec::new()));
+
+        let (start, end) = choose_window(text, region, &hits, max_chars);
+        // Bigram hits overlap ("東京", "京都"), as do a code token and its
+        // parts ("vec::push", "vec"): highlight each stretch once
+        let mut highlights: Vec<(usize, usize)> = Vec::new();
+        for hit in hits.iter().filter(|h| h.start >= start && h.end <= end) {
+            match highlights.last_mut() {
+                Some(last) if hit.start < last.1 + start => last.1 = last.1.max(hit.end - start),
+                _ => highlights.push((hit.start - start, hit.end - start)),
+            }
+        }
+        Some(Snippet {
+            text: text[start..end].to_string(),
+            highlights,
+            clipped_start: !text[..start].trim().is_empty(),
+            clipped_end: !text[end..].trim().is_empty(),
+        })
+    }
+
+    /// Vocabulary words to highlight, mapped to the query term they match.
+    ///
+    /// Each positive query term contributes itself and the terms sharing its
+    /// stem, plus its prefix expansions for tier 2+ results, its fuzzy
+    /// expansions for tier 3+ and its phonetic matches for tier 4.
+    /// Quoted words, phrases and `NEAR` terms only ever match exactly.
+    fn highlight_terms(&self, query: &str, result: &SearchResult) -> HashMap<String, usize> {
+        let mut words = Vec::new();
+        if let Some(ast) = self.parse_query(query) {
+            collect_terms(&ast, &mut words);
+        }
+
+        let vocabulary = self.vocabulary();
+        // Fuzzy expansions are highlighted within the default Tier 3 budget
+        let budget = self.edit_budget(&SearchOptions::default());
+        let mut terms: HashMap<String, usize> = HashMap::new();
+        for (term, (word, _)) in words.iter().enumerate() {
+            terms.entry(word.clone()).or_insert(term);
+        }
+        for (term, (word, exact)) in words.iter().enumerate() {
+            if *exact {
+                continue;
+            }
+            let mut expansions: Vec<usize> = self
+                .stem_terms(word, None)
+                .into_iter()
+                .map(|idx| idx as usize)
+                .collect();
+            if result.tier >= 2 {
+                expansions.extend(prefix_search_vocabulary(
+                    self.suffix_array(),
+                    vocabulary,
+                    word,
+                ));
+            }
+            if result.tier >= 3 {
+                expansions.extend(
+                    self.fuzzy_terms(word, budget.max_distance, budget.fuzziness, false)
+                        .into_iter()
+                        .map(|m| m.term_idx),
+                );
+            }
+            if result.tier >= 4 {
+                expansions.extend(self.phonetic_terms(word).iter().map(|&idx| idx as usize));
+            }
+            for vocab_idx in expansions {
+                if let Some(expanded) = vocabulary.get(vocab_idx) {
+                    terms.entry(expanded.clone()).or_insert(term);
+                }
+            }
+        }
+
+        // The term the result reports always counts, even if it came from
+        // an expansion the rules above didn't reach
+        if let Some(matched) = result
+            .matched_term
+            .and_then(|idx| vocabulary.get(idx as usize))
+        {
+            terms.entry(matched.clone()).or_insert(words.len());
+        }
+        terms
+    }
+}
+
+/// Positive query words as `(word, exact)`, skipping anything under `NOT`.
+fn collect_terms(node: &QueryNode, out: &mut Vec<(String, bool)>) {
+    match node {
+        QueryNode::Term { text, exact } => out.push((text.clone(), *exact)),
+        QueryNode::Phrase(words) => out.extend(
+            words
+                .iter()
+                .filter(|w| !is_stop_word(w))
+                .map(|w| (w.clone(), true)),
+        ),
+        QueryNode::Near { terms, .. } => out.extend(terms.iter().map(|t| (t.clone(), true))),
+        QueryNode::And(children) | QueryNode::Or(children) => {
+            for child in children {
+                collect_terms(child, out);
+            }
+        }
+        QueryNode::Not(_) => {}
+        QueryNode::Field { node, .. } => collect_terms(node, out),
+    }
+}
+
+/// Byte range covering every span that passes `keep`, clamped to the text.
+fn span_range(
+    doc: &DocText,
+    keep: impl Fn(&crate::binary::TextSpan) -> bool,
+) -> Option<(usize, usize)> {
+    let len = doc.text.len();
+    doc.spans
+        .iter()
+        .filter(|s| keep(s))
+        .map(|s| ((s.start as usize).min(len), (s.end as usize).min(len)))
+        .filter(|(start, end)| start < end)
+        .reduce(|(a, b), (c, d)| (a.min(c), b.max(d)))
+}
+
+/// Highlighted words inside `region`, in text order, cut like the index
+/// (and romanized too, if it was).
+fn find_hits(
+    text: &str,
+    region: (usize, usize),
+    terms: &HashMap<String, usize>,
+    tokenizer: Tokenizer,
+    transliterated: bool,
+) -> Vec<Hit> {
+    let region_text = &text[region.0..region.1];
+    let mut words = word_spans_with(region_text, tokenizer);
+    if transliterated {
+        words.extend(romanized_spans(region_text));
+        words.sort_by_key(|&(_, start, _)| start);
+    }
+    words
+        .into_iter()
+        .filter_map(|(word, start, end)| {
+            let &term = terms.get(&word)?;
+            Some(Hit {
+                start: region.0 + start,
+                end: region.0 + end,
+                term,
+            })
+        })
+        .collect()
+}
+
+/// Byte range of the snippet window within `region`.
+fn choose_window(
+    text: &str,
+    region: (usize, usize),
+    hits: &[Hit],
+    max_chars: usize,
+) -> (usize, usize) {
+    // Byte offset of every char (plus the end), for char <-> byte conversion
+    let offsets: Vec<usize> = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+        .collect();
+    let char_at = |byte: usize| offsets.partition_point(|&b| b < byte);
+
+    // Best run of hits that fits: most distinct terms, then most hits
+    let (mut covered_start, mut covered_end) = (region.0, region.0);
+    let mut best = (0, 0);
+    for i in 0..hits.len() {
+        let mut seen = HashSet::new();
+        for j in i..hits.len() {
+            if j > i && char_at(hits[j].end) - char_at(hits[i].start) > max_chars {
+                break;
+            }
+            seen.insert(hits[j].term);
+            if (seen.len(), j - i + 1) > best {
+                best = (seen.len(), j - i + 1);
+                (covered_start, covered_end) = (hits[i].start, hits[j].end);
+            }
+        }
+    }
+
+    // Spread the spare room: about a third before, the rest after
+    let (region_start, region_end) = (char_at(region.0), char_at(region.1));
+    let (first, last) = (char_at(covered_start), char_at(covered_end));
+    let spare = max_chars.saturating_sub(last - first);
+    let mut start = first.saturating_sub(spare / 3).max(region_start);
+    let end = (start + max_chars).max(last).min(region_end);
+    start = start.min(end.saturating_sub(max_chars)).max(region_start);
+
+    snap_to_words(
+        text,
+        (offsets[start], offsets[end]),
+        region,
+        (covered_start, covered_end),
+    )
+}
+
+/// Move window edges off mid-word positions and trim whitespace, never
+/// cutting into the covered hits.
+fn snap_to_words(
+    text: &str,
+    (mut start, mut end): (usize, usize),
+    region: (usize, usize),
+    (keep_start, keep_end): (usize, usize),
+) -> (usize, usize) {
+    let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+
+    if start > region.0 && is_word(text[..start].chars().next_back()) {
+        while start < keep_start && is_word(text[start..].chars().next()) {
+            start += text[start..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    if end < region.1 && is_word(text[end..].chars().next()) {
+        while end > keep_end.max(start) && is_word(text[..end].chars().next_back()) {
+            end -= text[..end].chars().next_back().map_or(1, char::len_utf8);
+        }
+    }
+
+    let trimmed = text[start..end].trim_start();
+    start = end - trimmed.len();
+    end = start + trimmed.trim_end().len();
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary::TextSpan;
+    use crate::binary::{encode_docs_binary, BinaryLayer, DocMetaInput, LoadedLayer, PostingEntry};
+    use crate::fuzzy::dfa::ParametricDFA;
+    use crate::index::word_spans;
+    use crate::types::MatchType;
+
+    /// Build a searcher with stored text. Each doc is a list of
+    /// `(text, section_idx, heading_level)` spans, joined with spaces.
+    fn build_text_searcher(docs: &[&[(&str, u32, u8)]]) -> TierSearcher {
+        let mut terms: HashMap<String, Vec<PostingEntry>> = HashMap::new();
+        let mut doc_texts = Vec::new();
+        let mut max_section = 0;
+
+        for (doc_id, spans) in docs.iter().enumerate() {
+            let mut doc = DocText::default();
+            for &(part, section_idx, heading_level) in spans.iter() {
+                if !doc.text.is_empty() {
+                    doc.text.push(' ');
+                }
+                let start = doc.text.len();
+                doc.text.push_str(part);
+                doc.spans.push(TextSpan {
+                    start: start as u32,
+                    end: doc.text.len() as u32,
+                    section_idx,
+                    heading_level,
+                });
+                max_section = max_section.max(section_idx);
+
+                for (word, _, _) in word_spans(part) {
+                    if is_stop_word(&word) {
+                        continue;
+                    }
+                    let entry = PostingEntry {
+                        doc_id: doc_id as u32,
+                        section_idx,
+                        heading_level,
+                        score: 10,
+                    };
+                    let list = terms.entry(word).or_default();
+                    let repeat = list.last().is_some_and(|e| {
+                        (e.doc_id, e.section_idx, e.heading_level)
+                            == (entry.doc_id, entry.section_idx, entry.heading_level)
+                    });
+                    if !repeat {
+                        list.push(entry);
+                    }
+                }
+            }
+            doc_texts.push(doc);
+        }
+
+        let mut vocabulary: Vec<String> = terms.keys().cloned().collect();
+        vocabulary.sort();
+        let postings: Vec<Vec<PostingEntry>> =
+            vocabulary.iter().map(|t| terms[t].clone()).collect();
+        let suffix_array: Vec<(u32, u32)> = (0..vocabulary.len()).map(|i| (i as u32, 0)).collect();
+        let section_table: Vec<String> = (1..=max_section).map(|i| format!("s{}", i)).collect();
+
+        let metas: Vec<DocMetaInput> = (0..docs.len())
+            .map(|i| DocMetaInput {
+                title: format!("Doc {}", i),
+                excerpt: String::new(),
+                href: format!("/doc{}", i),
+                doc_type: "page".to_string(),
+                section_id: None,
+                category: None,
+                author: None,
+                tags: vec![],
+            })
+            .collect();
+
+        let mut layer = BinaryLayer::build_v7(
+            &vocabulary,
+            &suffix_array,
+            &postings,
+            None,
+            &section_table,
+            docs.len(),
+            ParametricDFA::build(true).to_bytes(),
+            encode_docs_binary(&metas),
+            Vec::new(),
+        )
+        .unwrap();
+        layer.set_text(&doc_texts);
+        let bytes = layer.to_bytes().unwrap();
+        TierSearcher::from_layer(LoadedLayer::from_bytes(&bytes).unwrap()).unwrap()
+    }
+
+    fn marked(snippet: &Snippet) -> String {
+        let mut out = String::new();
+        let mut pos = 0;
+        for &(start, end) in &snippet.highlights {
+            out.push_str(&snippet.text[pos..start]);
+            out.push('[');
+            out.push_str(&snippet.text[start..end]);
+            out.push(']');
+            pos = end;
+        }
+        out.push_str(&snippet.text[pos..]);
+        out
+    }
+
+    fn first_snippet(searcher: &TierSearcher, query: &str, max_chars: usize) -> Snippet {
+        let results = searcher.search(query, 10);
+        searcher
+            .snippet(&results[0], query, max_chars)
+            .expect("index has stored text")
+    }
+
+    #[test]
+    fn test_without_text_no_snippet() {
+        let searcher = crate::search::phrase::tests::build_searcher(&["memory safety"], true);
+        assert!(!searcher.has_text());
+        let results = searcher.search("memory", 10);
+        assert!(searcher.snippet(&results[0], "memory", 80).is_none());
+    }
+
+    #[test]
+    fn test_snippet_highlights_exact_terms() {
+        let searcher = build_text_searcher(&[&[
+            ("Rust Guide", 0, 0),
+            ("Ownership and Memory Safety", 1, 2),
+            (
+                "The borrow checker enforces memory safety without a garbage collector.",
+                1,
+                5,
+            ),
+        ]]);
+        assert!(searcher.has_text());
+
+        let snippet = first_snippet(&searcher, "memory safety", 200);
+        assert_eq!(
+            marked(&snippet),
+            "Ownership and [Memory] [Safety] The borrow checker enforces [memory] [safety] without a garbage collector."
+        );
+        assert!(snippet.clipped_start);
+        assert!(!snippet.clipped_end);
+    }
+
+    #[test]
+    fn test_snippet_uses_matched_section() {
+        let searcher = build_text_searcher(&[&[
+            ("Guide", 0, 0),
+            ("Installation", 1, 2),
+            ("Download the installer and run it.", 1, 5),
+            ("Configuration", 2, 2),
+            ("Set the tokenizer option in the config file.", 2, 5),
+        ]]);
+
+        let snippet = first_snippet(&searcher, "tokenizer", 200);
+        assert_eq!(
+            marked(&snippet),
+            "Configuration Set the [tokenizer] option in the config file."
+        );
+    }
+
+    #[test]
+    fn test_snippet_highlights_prefix_and_fuzzy_expansions() {
+        let searcher = build_text_searcher(&[
+            &[
+                ("Notes", 0, 0),
+                ("Tips for optimization and optimizing loops.", 1, 5),
+            ],
+            &[
+                ("More", 0, 0),
+                ("Python tricks for python programmers.", 2, 5),
+            ],
+        ]);
+
+        let results = searcher.search("optim", 10);
+        assert_eq!(results[0].tier, 2);
+        let snippet = searcher.snippet(&results[0], "optim", 200).unwrap();
+        assert_eq!(
+            marked(&snippet),
+            "Tips for [optimization] and [optimizing] loops."
+        );
+
+        let results = searcher.search("pyhton", 10);
+        assert_eq!(results[0].tier, 3);
+        let snippet = searcher.snippet(&results[0], "pyhton", 200).unwrap();
+        assert_eq!(
+            marked(&snippet),
+            "[Python] tricks for [python] programmers."
+        );
+    }
+
+    #[test]
+    fn test_exact_match_skips_fuzzy_neighbours() {
+        let searcher = build_text_searcher(&[&[
+            ("Trust", 0, 0),
+            ("We trust rust because rust is fast.", 1, 5),
+        ]]);
+        let snippet = first_snippet(&searcher, "rust", 200);
+        assert_eq!(marked(&snippet), "We trust [rust] because [rust] is fast.");
+    }
+
+    #[test]
+    fn test_snippet_window_covers_most_terms() {
+        let filler = "lorem ipsum dolor sit amet ".repeat(8);
+        let body = format!(
+            "memory leaks happen. {}The memory safety story is good. {}end",
+            filler, filler
+        );
+        let searcher = build_text_searcher(&[&[("Title", 0, 0), (&body, 1, 5)]]);
+
+        let snippet = first_snippet(&searcher, "memory safety", 60);
+        assert!(snippet.text.chars().count() <= 60, "{:?}", snippet.text);
+        assert!(
+            marked(&snippet).contains("[memory] [safety]"),
+            "{:?}",
+            snippet
+        );
+        assert!(snippet.clipped_start && snippet.clipped_end);
+        // Edges fall on word boundaries
+        assert!(body.contains(&format!(" {} ", snippet.text)));
+    }
+
+    #[test]
+    fn test_snippet_ignores_excluded_terms_and_counts_chars() {
+        let searcher = build_text_searcher(&[&[
+            ("Café", 0, 0),
+            ("Crème brûlée at the café, never tea.", 1, 5),
+        ]]);
+
+        let snippet = first_snippet(&searcher, "cafe -coffee", 200);
+        assert_eq!(marked(&snippet), "Crème brûlée at the [café], never tea.");
+
+        let short = first_snippet(&searcher, "creme", 12);
+        assert_eq!(marked(&short), "[Crème] brûlée");
+    }
+
+    #[test]
+    fn test_title_match_prefers_body_text() {
+        let searcher = build_text_searcher(&[&[
+            ("Tokenizer", 0, 0),
+            ("Overview", 1, 2),
+            ("How the tokenizer splits words.", 1, 5),
+        ]]);
+        let results = searcher.search("tokenizer", 10);
+        assert_eq!(results[0].match_type, MatchType::Title);
+        let snippet = searcher.snippet(&results[0], "tokenizer", 200).unwrap();
+        assert_eq!(
+            marked(&snippet),
+            "Overview How the [tokenizer] splits words."
+        );
+    }
+
+    #[test]
+    fn test_overlapping_bigrams_highlight_once() {
+        let searcher = build_text_searcher(&[&[("東京", 0, 0), ("東京都庁は新宿にある。", 1, 5)]]);
+        let snippet = first_snippet(&searcher, "東京都", 200);
+        assert_eq!(marked(&snippet), "[東京都]庁は新宿にある。");
+    }
+}
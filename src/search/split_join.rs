@@ -0,0 +1,289 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Split and joined words: "data base" finds "database", and back.
+//!
+//! The indexer splits on every non-alphanumeric character and queries split
+//! on whitespace, so "e-mail", "email" and "e mail" are three different
+//! searches. None of them is a typo, so the fuzzy tier is the wrong fix:
+//! "data base" is nowhere near "database" in edit distance per term.
+//!
+//! Instead, right after Tier 1 the searcher retries the query with one word
+//! boundary moved:
+//!
+//! - **Join** two adjacent query words into one vocabulary term
+//!   ("data base" → "database").
+//! - **Join** a hyphenated word ("auto-scale" → "autoscale").
+//! - **Split** a query word into two vocabulary terms that appear next to
+//!   each other in the text ("database" → "data base"), and a hyphenated
+//!   word into its parts ("auto-scale" → "auto scale"). Adjacency needs word
+//!   positions; indexes without them only join.
+//!
+//! Every other word must match exactly. The matches are exact words, just
+//! not the ones typed, so they count as Tier 1 but score
+//! [`SPLIT_JOIN_PENALTY`] of an exact match.
+
+use super::tiered::{finalize_results, MultiTermAccumulator, SearchResult, TierSearcher};
+use crate::index::is_stop_word_in;
+use crate::index::language::Language;
+use crate::scoring::ranking::compare_results;
+use crate::types::MatchType;
+use crate::util::normalize::fold;
+use std::collections::HashSet;
+
+/// Score multiplier for split/join matches, so the words as typed rank first.
+pub const SPLIT_JOIN_PENALTY: f64 = 0.8;
+
+/// One query slot of a rewritten query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Slot {
+    /// A vocabulary term
+    Term(usize),
+    /// Words that must appear next to each other
+    Adjacent(Vec<String>),
+}
+
+impl TierSearcher {
+    /// Documents matching `query` with one word boundary moved, scored at
+    /// [`SPLIT_JOIN_PENALTY`] and reported as Tier 1.
+    ///
+    /// Skips `exclude_ids` (the exact matches) and documents the filter
+    /// mask doesn't allow. Split words skip `language`'s stop words (any
+    /// language's without one).
+    pub(crate) fn split_join_matches(
+        &self,
+        query: &str,
+        exclude_ids: &HashSet<usize>,
+        limit: usize,
+        dedup_sections: bool,
+        allowed: Option<&[bool]>,
+        language: Option<Language>,
+    ) -> Vec<SearchResult> {
+        let query = fold(query);
+        let parts: Vec<&str> = query.split_whitespace().collect();
+
+        let mut results = Vec::new();
+        for slots in self.rewrites(&parts) {
+            results.extend(self.match_slots(
+                &slots,
+                exclude_ids,
+                allowed,
+                language,
+                SPLIT_JOIN_PENALTY,
+            ));
+        }
+
+        // The same section can match several rewrites: keep its best
+        results.sort_by(|a, b| compare_results(a, b, self.docs()));
+        let mut seen = HashSet::new();
+        results.retain(|r| seen.insert((r.doc_id, r.section_idx)));
+        finalize_results(results, limit, self.docs(), dedup_sections)
+    }
+
+    /// Every rewrite of `parts` with exactly one boundary moved, as slots.
+    ///
+    /// Words left alone must be vocabulary terms, or no rewrite can match.
+    fn rewrites(&self, parts: &[&str]) -> Vec<Vec<Slot>> {
+        let exact: Vec<Option<usize>> = parts.iter().map(|p| self.term_index(p)).collect();
+        let mut rewrites: Vec<Vec<Slot>> = Vec::new();
+        let mut rewrite = |start: usize, len: usize, replacement: Slot| {
+            let mut unchanged = (0..parts.len()).filter(|i| !(start..start + len).contains(i));
+            if unchanged.any(|i| exact[i].is_none()) {
+                return;
+            }
+            let mut slots: Vec<Slot> = exact[..start]
+                .iter()
+                .flatten()
+                .map(|&t| Slot::Term(t))
+                .collect();
+            slots.push(replacement);
+            slots.extend(
+                exact[start + len..]
+                    .iter()
+                    .flatten()
+                    .map(|&t| Slot::Term(t)),
+            );
+            if !rewrites.contains(&slots) {
+                rewrites.push(slots);
+            }
+        };
+
+        for (i, part) in parts.iter().enumerate() {
+            let pieces: Vec<&str> = part
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|p| !p.is_empty())
+                .collect();
+            if pieces.len() > 1 {
+                // "auto-scale": "autoscale" or "auto scale"
+                if let Some(joined) = self.term_index(&pieces.concat()) {
+                    rewrite(i, 1, Slot::Term(joined));
+                }
+                if self.has_positions() {
+                    rewrite(
+                        i,
+                        1,
+                        Slot::Adjacent(pieces.iter().map(|p| p.to_string()).collect()),
+                    );
+                }
+                continue;
+            }
+
+            // "data base" → "database"
+            if let Some(next) = parts.get(i + 1) {
+                if let Some(joined) = self.term_index(&format!("{}{}", part, next)) {
+                    rewrite(i, 2, Slot::Term(joined));
+                }
+            }
+
+            // "database" → "data base"
+            if self.has_positions() {
+                for (split, _) in part.char_indices().skip(1) {
+                    let (head, tail) = part.split_at(split);
+                    if self.term_index(head).is_some() && self.term_index(tail).is_some() {
+                        rewrite(
+                            i,
+                            1,
+                            Slot::Adjacent(vec![head.to_string(), tail.to_string()]),
+                        );
+                    }
+                }
+            }
+        }
+        rewrites
+    }
+
+    /// Sections matching every slot, with scores multiplied by `penalty`.
+    pub(super) fn match_slots(
+        &self,
+        slots: &[Slot],
+        exclude_ids: &HashSet<usize>,
+        allowed: Option<&[bool]>,
+        language: Option<Language>,
+        penalty: f64,
+    ) -> Vec<SearchResult> {
+        let admitted =
+            |doc_id: usize| !exclude_ids.contains(&doc_id) && self.admits(doc_id, allowed);
+
+        let mut acc = MultiTermAccumulator::new(slots.len());
+        for (slot_idx, slot) in slots.iter().enumerate() {
+            match slot {
+                Slot::Term(vocab_idx) => {
+                    let postings = self.postings().get(*vocab_idx).map_or(&[][..], |p| p);
+                    for (entry_idx, entry) in postings.iter().enumerate() {
+                        if !admitted(entry.doc_id as usize) {
+                            continue;
+                        }
+                        acc.add_match(
+                            slot_idx,
+                            entry.doc_id as usize,
+                            entry.section_idx,
+                            MatchType::from_heading_level(entry.heading_level),
+                            entry.score as f64 * penalty,
+                            *vocab_idx as u32,
+                            self.position_of(*vocab_idx, entry_idx),
+                        );
+                    }
+                }
+                Slot::Adjacent(words) => {
+                    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+                    // A hit sums its words' scores; one slot gets one word's worth
+                    let indexed = words
+                        .iter()
+                        .filter(|w| !is_stop_word_in(w, language))
+                        .count()
+                        .max(1);
+                    for hit in self.phrase_hits(&words, None, language).unwrap_or_default() {
+                        if !admitted(hit.doc_id) {
+                            continue;
+                        }
+                        acc.add_match(
+                            slot_idx,
+                            hit.doc_id,
+                            hit.section_idx,
+                            hit.match_type,
+                            hit.score / indexed as f64 * penalty,
+                            hit.matched_term,
+                            None,
+                        );
+                    }
+                }
+            }
+        }
+
+        // Section-level results; the caller dedups across rewrites
+        acc.into_results(1, usize::MAX, self.docs(), false)
+    }
+
+    /// Vocabulary index of an exact term.
+    pub(super) fn term_index(&self, term: &str) -> Option<usize> {
+        self.vocabulary()
+            .binary_search_by(|t| t.as_str().cmp(term))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::phrase::tests::build_searcher;
+    use crate::search::tiered::SearchOptions;
+
+    fn search(searcher: &TierSearcher, query: &str) -> Vec<(usize, u8)> {
+        let mut hits: Vec<(usize, u8)> = searcher
+            .search_with_options(query, 10, SearchOptions::default())
+            .iter()
+            .map(|r| (r.doc_id, r.tier))
+            .collect();
+        hits.sort_unstable();
+        hits
+    }
+
+    #[test]
+    fn test_joins_adjacent_query_words() {
+        let searcher = build_searcher(&["database tuning", "data lake base camp", "rust"], true);
+        // Doc 1 has both words, so it's an exact match; doc 0 joins them
+        assert_eq!(search(&searcher, "data base"), vec![(0, 1), (1, 1)]);
+        assert_eq!(search(&searcher, "data base tuning"), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn test_splits_into_adjacent_terms() {
+        let searcher = build_searcher(&["data base tuning", "base data", "database"], true);
+        // "base data" has both words, but not next to each other
+        assert_eq!(search(&searcher, "database"), vec![(0, 1), (2, 1)]);
+        assert_eq!(search(&searcher, "database tuning"), vec![(0, 1)]);
+
+        // Without positions, adjacency can't be checked, so no split
+        let searcher = build_searcher(&["data base tuning", "database"], false);
+        assert_eq!(search(&searcher, "database"), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_hyphenated_words() {
+        let searcher = build_searcher(&["autoscale groups", "auto scale policy", "rust"], true);
+        assert_eq!(search(&searcher, "auto-scale"), vec![(0, 1), (1, 1)]);
+        assert_eq!(search(&searcher, "autoscale"), vec![(0, 1), (1, 1)]);
+        assert_eq!(search(&searcher, "auto scale"), vec![(0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn test_scores_below_exact() {
+        let searcher = build_searcher(&["database", "data base"], true);
+        let results = searcher.search("database", 10);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].doc_id, 0);
+        assert!(results[1].score < results[0].score);
+
+        // Filters and the exact matches' exclusion both apply
+        let exclude: HashSet<usize> = [0].into_iter().collect();
+        let results = searcher.split_join_matches("database", &exclude, 10, true, None, None);
+        assert_eq!(
+            results.iter().map(|r| r.doc_id).collect::<Vec<_>>(),
+            vec![1]
+        );
+        let mask = [true, false];
+        assert!(searcher
+            .split_join_matches("database", &exclude, 10, true, Some(&mask), None)
+            .is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stemmed matches: "optimizing" finds "optimized".
+//!
+//! Indexes built with a stemmer know which vocabulary terms share a stem
+//! (see [`StemIndex`]). Right after Tier 1, the searcher retries the query
+//! with each word standing for every term sharing its stem. The matches are
+//! the same word inflected differently, not a typo or a guess, so they count
+//! as Tier 1, but each term other than the one typed scores
+//! [`STEM_PENALTY`] of an exact match: "optimizing" ranks a document saying
+//! "optimizing" above one saying "optimized".
+//!
+//! [`StemIndex`]: crate::index::stem::StemIndex
+
+use super::tiered::{ResolvedTerm, SearchResult, TierSearcher};
+use crate::index::language::Language;
+use crate::util::normalize::fold;
+use std::collections::HashSet;
+
+/// Score multiplier for a term that shares the query word's stem but isn't
+/// the word itself.
+pub const STEM_PENALTY: f64 = 0.9;
+
+impl TierSearcher {
+    /// Documents matching `query` with every word widened to the terms
+    /// sharing its stem, reported as Tier 1. Empty without stems, or when no
+    /// word has another inflection in the vocabulary.
+    ///
+    /// Skips `exclude_ids` (the exact matches) and documents the filter
+    /// mask doesn't allow. With a `language`, only its stems count.
+    pub(crate) fn stem_matches(
+        &self,
+        query: &str,
+        exclude_ids: &HashSet<usize>,
+        limit: usize,
+        dedup_sections: bool,
+        allowed: Option<&[bool]>,
+        language: Option<Language>,
+    ) -> Vec<SearchResult> {
+        let query = fold(query);
+        let vocabulary = self.vocabulary();
+
+        let mut resolved = Vec::new();
+        let mut inflected = false;
+        for word in query.split_whitespace() {
+            let matches: Vec<(usize, f64)> = self
+                .stem_terms(word, language)
+                .into_iter()
+                .map(|vocab_idx| {
+                    let vocab_idx = vocab_idx as usize;
+                    if vocabulary[vocab_idx] == word {
+                        (vocab_idx, 1.0)
+                    } else {
+                        inflected = true;
+                        (vocab_idx, STEM_PENALTY)
+                    }
+                })
+                .collect();
+            if matches.is_empty() {
+                return Vec::new();
+            }
+            resolved.push(ResolvedTerm { tier: 1, matches });
+        }
+        // Only the words as typed: Tier 1 already has those documents
+        if !inflected {
+            return Vec::new();
+        }
+
+        self.accumulate_resolved(&resolved, exclude_ids, allowed)
+            .into_results(1, limit, self.docs(), dedup_sections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::phrase::tests::{
+        build_multilingual_searcher, build_searcher, build_stemmed_searcher,
+    };
+    use crate::search::tiered::SearchOptions;
+
+    fn search(searcher: &TierSearcher, query: &str) -> Vec<(usize, u8)> {
+        searcher
+            .search_with_options(query, 10, SearchOptions::default())
+            .iter()
+            .map(|r| (r.doc_id, r.tier))
+            .collect()
+    }
+
+    #[test]
+    fn test_inflections_match_at_tier_1() {
+        let texts = [
+            "optimized builds",
+            "optimizing queries",
+            "optimization guide",
+            "optical lens",
+        ];
+        let searcher = build_stemmed_searcher(&texts, &[Language::English]);
+        // The word as typed first, then its inflections; "optical" is
+        // another stem
+        let hits = search(&searcher, "optimizing");
+        assert_eq!(hits[0], (1, 1));
+        let mut rest = hits[1..].to_vec();
+        rest.sort_unstable();
+        assert_eq!(rest, vec![(0, 1), (2, 1)]);
+
+        // Every word widens, and every word must still match
+        assert_eq!(search(&searcher, "optimize build"), vec![(0, 1)]);
+        assert!(search(&searcher, "optimize rust").is_empty());
+
+        // Without stems, the inflections are only prefix or fuzzy matches
+        let plain = build_searcher(&texts, true);
+        assert!(search(&plain, "optimizing")
+            .iter()
+            .skip(1)
+            .all(|&(_, tier)| tier > 1));
+    }
+
+    #[test]
+    fn test_scores_below_exact() {
+        let searcher = build_stemmed_searcher(&["connected", "connection"], &[Language::English]);
+        let results = searcher.search("connection", 10);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].doc_id, 1);
+        assert!(results[1].score < results[0].score);
+        // The result names the inflection it matched
+        let matched = results[1].matched_term.unwrap() as usize;
+        assert_eq!(searcher.vocabulary()[matched], "connected");
+
+        // Exact matches and filtered documents are skipped
+        let exclude: HashSet<usize> = [1].into_iter().collect();
+        let results = searcher.stem_matches("connection", &exclude, 10, true, None, None);
+        assert_eq!(
+            results.iter().map(|r| r.doc_id).collect::<Vec<_>>(),
+            vec![0]
+        );
+        let mask = [false, true];
+        assert!(searcher
+            .stem_matches("connection", &exclude, 10, true, Some(&mask), None)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_languages() {
+        let texts = ["versicherungen und beitrage", "programacion en rust"];
+        let german = build_stemmed_searcher(&texts, &[Language::German]);
+        assert_eq!(search(&german, "versicherung"), vec![(0, 1)]);
+        assert!(search(&german, "programar")
+            .iter()
+            .all(|&(_, tier)| tier > 1));
+
+        let both = build_stemmed_searcher(&texts, &[Language::German, Language::Spanish]);
+        assert_eq!(search(&both, "versicherung"), vec![(0, 1)]);
+        assert_eq!(search(&both, "programar"), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_terms_stem_in_their_documents_language() {
+        let searcher = build_multilingual_searcher(&[
+            ("optimized builds", Language::English),
+            ("kategorien und listen", Language::German),
+        ]);
+        let search_in = |query: &str, language: Option<Language>| -> Vec<(usize, u8)> {
+            let options = SearchOptions {
+                language,
+                ..SearchOptions::default()
+            };
+            searcher
+                .search_with_options(query, 10, options)
+                .iter()
+                .map(|r| (r.doc_id, r.tier))
+                .filter(|&(_, tier)| tier == 1)
+                .collect()
+        };
+        assert_eq!(search_in("optimizing", None), vec![(0, 1)]);
+        assert_eq!(search_in("kategorie", None), vec![(1, 1)]);
+        // A language hint only applies its own stemmer
+        assert_eq!(search_in("kategorie", Some(Language::German)), vec![(1, 1)]);
+        assert!(search_in("kategorie", Some(Language::English)).is_empty());
+        assert!(search_in("optimizing", Some(Language::German)).is_empty());
+        // Languages without a stemmer get no inflections
+        assert!(search_in("optimizing", Some(Language::Italian)).is_empty());
+    }
+
+    fn tier_1_ids(results: &[SearchResult]) -> Vec<usize> {
+        let mut ids: Vec<usize> = results
+            .iter()
+            .filter(|r| r.tier == 1)
+            .map(|r| r.doc_id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_boolean_terms_stem_in_the_query_language() {
+        let texts = ["optimized builds", "optimizing queries", "optimizing rust"];
+        let searcher = build_stemmed_searcher(&texts, &[Language::English]);
+        let search_in = |query: &str, language: Option<Language>| {
+            let options = SearchOptions {
+                language,
+                ..SearchOptions::default()
+            };
+            tier_1_ids(&searcher.search_with_options(query, 10, options))
+        };
+        assert_eq!(search_in("optimize -rust", Some(Language::English)), [0, 1]);
+        assert_eq!(
+            search_in("optimize -rust", Some(Language::German)),
+            Vec::<usize>::new()
+        );
+    }
+}
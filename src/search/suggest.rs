@@ -0,0 +1,382 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! "Did you mean": a corrected query for searches that came up short.
+//!
+//! Tier 3 already forgives typos, but only within its edit budget and only
+//! by widening the results. When a query finds little or nothing, the better
+//! answer is often a different query: "pyhton tutoral" → "python tutorial".
+//!
+//! Each word that isn't in the vocabulary (exactly or as a prefix) gets
+//! candidate corrections: vocabulary terms within edit distance, ranked by
+//! how many documents use them and how far they are from what was typed.
+//! Common words beat rare ones, and close words beat far ones, with distance
+//! counting double: ln(1 + doc_freq) / (1 + distance)².
+//!
+//! Multi-word queries need more than the best correction per word. "rust
+//! borow" could become "rust borrow" or "rust barrow", and only one of those
+//! appears in any document. Combinations are built word by word, keeping a
+//! small beam of the best-scoring ones whose words still co-occur in at
+//! least one document. A suggestion is never a query that finds nothing.
+
+use super::tiered::{Fuzziness, TierSearcher};
+use crate::index::is_stop_word;
+use crate::util::normalize::fold;
+
+/// Corrections considered per word.
+const MAX_CANDIDATES: usize = 5;
+
+/// Partial corrections kept while combining words.
+const BEAM_WIDTH: usize = 8;
+
+/// Edit budget for suggestions, by word length: 1 edit from 3 chars, 2 from
+/// 4, 3 from 10 (capped by the index's DFA).
+///
+/// Looser than search's adaptive table. A suggestion is only offered, never
+/// applied, so a swapped pair of letters in a short word ("rsut", two edits)
+/// is worth proposing a fix for.
+const SUGGEST_FUZZINESS: Fuzziness = Fuzziness::ByLength([3, 4, 10]);
+
+/// A corrected query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The corrected query, normalized (lowercase, diacritics folded)
+    pub query: String,
+    /// Byte ranges of the corrected words within `query`, in order
+    pub corrected: Vec<(usize, usize)>,
+    /// Documents containing every word of the corrected query
+    pub doc_count: usize,
+}
+
+/// One way to spell a query word.
+struct Candidate {
+    word: String,
+    /// Sorted doc ids containing the word (or, for a prefix, a word it starts)
+    docs: Vec<u32>,
+    score: f64,
+    corrected: bool,
+}
+
+/// A correction for the words so far.
+struct Partial {
+    /// Chosen candidate per word (`None` for words left out of the check)
+    picks: Vec<Option<usize>>,
+    /// Documents containing every checked word (`None` until one is checked)
+    docs: Option<Vec<u32>>,
+    score: f64,
+}
+
+impl TierSearcher {
+    /// Suggest a corrected query, or `None` if there's nothing to correct.
+    ///
+    /// Words already in the vocabulary (or a prefix of a vocabulary term)
+    /// and stop words stay as typed; each other word is replaced by a
+    /// vocabulary term within edit distance. The corrected words must all
+    /// appear together in at least one document. `None` when every word is
+    /// fine as typed, when some word has nothing close, or when no
+    /// combination of corrections co-occurs.
+    ///
+    /// Meant for searches that return few or no results. The query is read
+    /// as plain words: boolean syntax isn't interpreted.
+    pub fn suggest(&self, query: &str) -> Option<Suggestion> {
+        let query = fold(query);
+        let words: Vec<&str> = query.split_whitespace().collect();
+
+        let mut candidates: Vec<Vec<Candidate>> = Vec::with_capacity(words.len());
+        for &word in &words {
+            candidates.push(self.spellings(word)?);
+        }
+        if !candidates.iter().flatten().any(|c| c.corrected) {
+            return None;
+        }
+
+        let mut beam = vec![Partial {
+            picks: Vec::new(),
+            docs: None,
+            score: 0.0,
+        }];
+        for word_candidates in &candidates {
+            if word_candidates.is_empty() {
+                for partial in &mut beam {
+                    partial.picks.push(None);
+                }
+                continue;
+            }
+
+            let mut next = Vec::new();
+            for partial in &beam {
+                for (i, candidate) in word_candidates.iter().enumerate() {
+                    let docs = match &partial.docs {
+                        Some(docs) => intersect(docs, &candidate.docs),
+                        None => candidate.docs.clone(),
+                    };
+                    if docs.is_empty() {
+                        continue;
+                    }
+                    let mut picks = partial.picks.clone();
+                    picks.push(Some(i));
+                    next.push(Partial {
+                        picks,
+                        docs: Some(docs),
+                        score: partial.score + candidate.score,
+                    });
+                }
+            }
+            next.sort_by(|a, b| b.score.total_cmp(&a.score));
+            next.truncate(BEAM_WIDTH);
+            beam = next;
+        }
+
+        let best = beam.into_iter().max_by(|a, b| {
+            a.score
+                .total_cmp(&b.score)
+                .then_with(|| doc_count(a).cmp(&doc_count(b)))
+        })?;
+
+        let mut suggestion = Suggestion {
+            query: String::new(),
+            corrected: Vec::new(),
+            doc_count: doc_count(&best),
+        };
+        for ((word, pick), word_candidates) in words.iter().zip(&best.picks).zip(&candidates) {
+            if !suggestion.query.is_empty() {
+                suggestion.query.push(' ');
+            }
+            let start = suggestion.query.len();
+            match pick.map(|i| &word_candidates[i]) {
+                Some(candidate) => {
+                    suggestion.query.push_str(&candidate.word);
+                    if candidate.corrected {
+                        suggestion.corrected.push((start, suggestion.query.len()));
+                    }
+                }
+                None => suggestion.query.push_str(word),
+            }
+        }
+        Some(suggestion)
+    }
+
+    /// Ways to spell one query word, best first.
+    ///
+    /// A word the vocabulary already has (exactly or as a prefix) keeps its
+    /// spelling; a stop word gets no candidates and is left out of the
+    /// co-occurrence check. `None` if the word has nothing close.
+    fn spellings(&self, word: &str) -> Option<Vec<Candidate>> {
+        if let Some(resolved) = self.resolve_term_within(word, 0) {
+            let mut docs: Vec<u32> = resolved
+                .matches
+                .iter()
+                .flat_map(|&(vocab_idx, _)| self.term_docs(vocab_idx))
+                .collect();
+            docs.sort_unstable();
+            docs.dedup();
+            return Some(vec![Candidate {
+                word: word.to_string(),
+                docs,
+                score: 0.0,
+                corrected: false,
+            }]);
+        }
+        if is_stop_word(word) {
+            return Some(Vec::new());
+        }
+
+        let max_distance = self.max_edit_distance();
+        let mut candidates: Vec<Candidate> = self
+            .fuzzy_terms(word, max_distance, SUGGEST_FUZZINESS, false)
+            .into_iter()
+            .filter(|m| m.distance > 0)
+            .filter_map(|m| {
+                let docs = self.term_docs(m.term_idx);
+                let penalty = (1.0 + m.distance as f64).powi(2);
+                (!docs.is_empty()).then(|| Candidate {
+                    word: self.vocabulary()[m.term_idx].clone(),
+                    score: (docs.len() as f64).ln_1p() / penalty,
+                    docs,
+                    corrected: true,
+                })
+            })
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.word.cmp(&b.word)));
+        candidates.truncate(MAX_CANDIDATES);
+        Some(candidates)
+    }
+
+    /// Sorted, distinct ids of loaded documents containing a vocabulary term.
+    fn term_docs(&self, vocab_idx: usize) -> Vec<u32> {
+        let doc_count = self.docs().len();
+        let mut docs: Vec<u32> = self
+            .postings()
+            .get(vocab_idx)
+            .into_iter()
+            .flatten()
+            .map(|e| e.doc_id)
+            .filter(|&id| (id as usize) < doc_count)
+            .collect();
+        docs.sort_unstable();
+        docs.dedup();
+        docs
+    }
+}
+
+fn doc_count(partial: &Partial) -> usize {
+    partial.docs.as_ref().map_or(0, Vec::len)
+}
+
+/// Intersection of two sorted id lists.
+fn intersect(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                out.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary::{encode_docs_binary, BinaryLayer, DocMetaInput, LoadedLayer, PostingEntry};
+    use crate::fuzzy::dfa::ParametricDFA;
+    use crate::fuzzy::trie::VocabTrie;
+    use std::collections::BTreeMap;
+
+    /// Searcher over one document per text, with a fuzzy index.
+    fn build_searcher(texts: &[&str]) -> TierSearcher {
+        let mut terms: BTreeMap<String, Vec<PostingEntry>> = BTreeMap::new();
+        for (doc_id, text) in texts.iter().enumerate() {
+            for word in text.split_whitespace().filter(|w| !is_stop_word(w)) {
+                terms
+                    .entry(word.to_string())
+                    .or_default()
+                    .push(PostingEntry {
+                        doc_id: doc_id as u32,
+                        section_idx: 0,
+                        heading_level: 5,
+                        score: 10,
+                    });
+            }
+        }
+        let vocabulary: Vec<String> = terms.keys().cloned().collect();
+        let postings: Vec<Vec<PostingEntry>> = terms.into_values().collect();
+        let suffix_array: Vec<(u32, u32)> = (0..vocabulary.len()).map(|i| (i as u32, 0)).collect();
+        let metas: Vec<DocMetaInput> = (0..texts.len())
+            .map(|i| DocMetaInput {
+                title: format!("Doc {}", i),
+                excerpt: String::new(),
+                href: format!("/doc{}", i),
+                doc_type: "page".to_string(),
+                section_id: None,
+                category: None,
+                author: None,
+                tags: vec![],
+            })
+            .collect();
+
+        let mut layer = BinaryLayer::build_v7(
+            &vocabulary,
+            &suffix_array,
+            &postings,
+            None,
+            &[],
+            texts.len(),
+            ParametricDFA::build(true).to_bytes(),
+            encode_docs_binary(&metas),
+            Vec::new(),
+        )
+        .unwrap();
+        layer.set_vocab_trie(&VocabTrie::build(&vocabulary));
+        let bytes = layer.to_bytes().unwrap();
+        TierSearcher::from_layer(LoadedLayer::from_bytes(&bytes).unwrap()).unwrap()
+    }
+
+    /// The suggestion with corrected words in brackets.
+    fn marked(suggestion: &Suggestion) -> String {
+        let mut out = String::new();
+        let mut pos = 0;
+        for &(start, end) in &suggestion.corrected {
+            out.push_str(&suggestion.query[pos..start]);
+            out.push('[');
+            out.push_str(&suggestion.query[start..end]);
+            out.push(']');
+            pos = end;
+        }
+        out.push_str(&suggestion.query[pos..]);
+        out
+    }
+
+    #[test]
+    fn test_corrects_misspelled_word() {
+        let searcher = build_searcher(&["python tutorial", "python guide", "rust tutorial"]);
+        let suggestion = searcher.suggest("Pyhton").unwrap();
+        assert_eq!(marked(&suggestion), "[python]");
+        assert_eq!(suggestion.doc_count, 2);
+
+        let suggestion = searcher.suggest("pyhton tutoral").unwrap();
+        assert_eq!(marked(&suggestion), "[python] [tutorial]");
+        assert_eq!(suggestion.doc_count, 1);
+    }
+
+    #[test]
+    fn test_nothing_to_correct() {
+        let searcher = build_searcher(&["python tutorial", "rust guide"]);
+        // Known words, prefixes of known words, and nothing at all
+        assert!(searcher.suggest("python tutorial").is_none());
+        assert!(searcher.suggest("pyth").is_none());
+        assert!(searcher.suggest("").is_none());
+        // Too far from anything
+        assert!(searcher.suggest("zyxwvut").is_none());
+        assert!(searcher.suggest("python zyxwvut").is_none());
+    }
+
+    #[test]
+    fn test_keeps_known_words_and_stop_words() {
+        let searcher = build_searcher(&["python tutorial", "rust guide"]);
+        let suggestion = searcher.suggest("the pyth tutoral").unwrap();
+        assert_eq!(marked(&suggestion), "the pyth [tutorial]");
+        assert_eq!(suggestion.doc_count, 1);
+    }
+
+    #[test]
+    fn test_prefers_common_words() {
+        // "borow" is one edit from both; "borrow" is in more documents
+        let searcher =
+            build_searcher(&["borrow checker", "borrow rules", "borrow", "wheel barrow"]);
+        assert_eq!(marked(&searcher.suggest("borow").unwrap()), "[borrow]");
+
+        // Fewer edits beat a few more documents
+        let searcher = build_searcher(&["cargo", "cargo", "carts", "carts", "carts"]);
+        assert_eq!(marked(&searcher.suggest("cargp").unwrap()), "[cargo]");
+    }
+
+    #[test]
+    fn test_corrections_must_co_occur() {
+        // "barrow" is the more common word, but never appears with "rust"
+        let searcher = build_searcher(&[
+            "rust borrow checker",
+            "garden barrow",
+            "barrow wheel",
+            "barrow repair",
+            "barrow sale",
+        ]);
+        assert_eq!(marked(&searcher.suggest("barow").unwrap()), "[barrow]");
+        let suggestion = searcher.suggest("rust barow").unwrap();
+        assert_eq!(marked(&suggestion), "rust [borrow]");
+        assert_eq!(suggestion.doc_count, 1);
+
+        // No pair of corrections shares a document
+        let searcher = build_searcher(&["apple pie", "banana bread"]);
+        assert!(searcher.suggest("aple banan").is_none());
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Synonym matches: "k8s" finds "kubernetes".
+//!
+//! Indexes built with synonym rules carry them (see [`Synonyms`]). Right
+//! after Tier 1, the searcher retries the query with one phrase replaced by
+//! each of its expansions:
+//!
+//! - A one-word expansion must be a vocabulary term ("k8s" → "kubernetes").
+//! - A longer one must appear as a phrase ("sso" → "single sign on"), or,
+//!   without word positions, as all of its words anywhere in the section.
+//!
+//! Every other word must match exactly, and one rule applies at a time. The
+//! matches are what the query means, not what it says, so they count as
+//! Tier 1 but score [`SYNONYM_PENALTY`] of an exact match, or whatever
+//! `SearchOptions::synonym_penalty` says. A result's `matched_term` is the
+//! expansion's word, so "k8s" reports "kubernetes".
+//!
+//! [`Synonyms`]: crate::index::synonyms::Synonyms
+
+use super::split_join::Slot;
+use super::tiered::{finalize_results, SearchResult, TierSearcher};
+use crate::index::is_stop_word_in;
+use crate::index::language::Language;
+use crate::scoring::ranking::compare_results;
+use crate::util::normalize::fold;
+use std::collections::HashSet;
+
+/// Default score multiplier for synonym matches, so the words as typed rank
+/// first.
+pub const SYNONYM_PENALTY: f64 = 0.8;
+
+impl TierSearcher {
+    /// Documents matching `query` with one phrase replaced by a synonym,
+    /// scored at `penalty` and reported as Tier 1. Empty without synonyms,
+    /// or with a `penalty` of zero.
+    ///
+    /// Skips `exclude_ids` (the matches so far) and documents the filter
+    /// mask doesn't allow. Phrases skip `language`'s stop words (any
+    /// language's without one).
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn synonym_matches(
+        &self,
+        query: &str,
+        exclude_ids: &HashSet<usize>,
+        limit: usize,
+        dedup_sections: bool,
+        allowed: Option<&[bool]>,
+        language: Option<Language>,
+        penalty: f64,
+    ) -> Vec<SearchResult> {
+        let Some(synonyms) = self.synonyms() else {
+            return Vec::new();
+        };
+        if penalty <= 0.0 {
+            return Vec::new();
+        }
+        let query = fold(query);
+        let parts: Vec<&str> = query.split_whitespace().collect();
+
+        let mut results = Vec::new();
+        for start in 0..parts.len() {
+            let longest = synonyms.max_words().min(parts.len() - start);
+            for len in 1..=longest {
+                let phrase = parts[start..start + len].join(" ");
+                for expansion in synonyms.expansions(&phrase) {
+                    let Some(slots) = self.synonym_rewrite(&parts, start, len, expansion, language)
+                    else {
+                        continue;
+                    };
+                    results.extend(self.match_slots(
+                        &slots,
+                        exclude_ids,
+                        allowed,
+                        language,
+                        penalty,
+                    ));
+                }
+            }
+        }
+
+        // The same section can match several expansions: keep its best
+        results.sort_by(|a, b| compare_results(a, b, self.docs()));
+        let mut seen = HashSet::new();
+        results.retain(|r| seen.insert((r.doc_id, r.section_idx)));
+        finalize_results(results, limit, self.docs(), dedup_sections)
+    }
+
+    /// `parts` with `parts[start..start + len]` replaced by `expansion`, as
+    /// slots. `None` if a word can't match: the expansion's words or the
+    /// words left alone aren't in the vocabulary (stop words left alone
+    /// drop out instead).
+    fn synonym_rewrite(
+        &self,
+        parts: &[&str],
+        start: usize,
+        len: usize,
+        expansion: &str,
+        language: Option<Language>,
+    ) -> Option<Vec<Slot>> {
+        let term = |word: &str| -> Option<Option<Slot>> {
+            match self.term_index(word) {
+                Some(idx) => Some(Some(Slot::Term(idx))),
+                None if is_stop_word_in(word, language) => Some(None),
+                None => None,
+            }
+        };
+
+        let words: Vec<&str> = expansion.split(' ').collect();
+        let replacement: Vec<Slot> = if words.len() == 1 {
+            vec![Slot::Term(self.term_index(expansion)?)]
+        } else if self.has_positions() {
+            vec![Slot::Adjacent(
+                words.iter().map(|w| w.to_string()).collect(),
+            )]
+        } else {
+            let slots: Vec<Option<Slot>> = words.iter().map(|w| term(w)).collect::<Option<_>>()?;
+            slots.into_iter().flatten().collect()
+        };
+        if replacement.is_empty() {
+            return None; // only stop words
+        }
+
+        let before: Vec<Option<Slot>> = parts[..start]
+            .iter()
+            .map(|w| term(w))
+            .collect::<Option<_>>()?;
+        let after: Vec<Option<Slot>> = parts[start + len..]
+            .iter()
+            .map(|w| term(w))
+            .collect::<Option<_>>()?;
+        Some(
+            before
+                .into_iter()
+                .flatten()
+                .chain(replacement)
+                .chain(after.into_iter().flatten())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::phrase::tests::build_synonym_searcher;
+    use crate::search::tiered::SearchOptions;
+
+    fn search(searcher: &TierSearcher, query: &str, options: SearchOptions) -> Vec<(usize, u8)> {
+        let mut hits: Vec<(usize, u8)> = searcher
+            .search_with_options(query, 10, options)
+            .iter()
+            .map(|r| (r.doc_id, r.tier))
+            .collect();
+        hits.sort_unstable();
+        hits
+    }
+
+    #[test]
+    fn test_two_way_rules() {
+        let searcher = build_synonym_searcher(
+            &["kubernetes cluster", "k8s cluster", "docker"],
+            &["k8s, kubernetes"],
+            true,
+        );
+        let both = vec![(0, 1), (1, 1)];
+        assert_eq!(search(&searcher, "k8s", SearchOptions::default()), both);
+        assert_eq!(
+            search(&searcher, "kubernetes", SearchOptions::default()),
+            both
+        );
+        assert_eq!(
+            search(&searcher, "k8s cluster", SearchOptions::default()),
+            both
+        );
+
+        // The expansion ranks below the word as typed, and says what it matched
+        let results = searcher.search("k8s", 10);
+        assert_eq!(results[0].doc_id, 1);
+        assert!(results[1].score < results[0].score);
+        let matched = results[1].matched_term.unwrap() as usize;
+        assert_eq!(searcher.vocabulary()[matched], "kubernetes");
+    }
+
+    #[test]
+    fn test_one_way_rules() {
+        let searcher = build_synonym_searcher(
+            &["authentication tokens", "auth header"],
+            &["auth => authentication"],
+            true,
+        );
+        assert_eq!(
+            search(&searcher, "auth", SearchOptions::default()),
+            vec![(0, 1), (1, 1)]
+        );
+        assert_eq!(
+            search(&searcher, "authentication", SearchOptions::default()),
+            vec![(0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_multi_word_rules() {
+        let rules = ["single sign on, sso"];
+        let texts = [
+            "configure single sign on",
+            "sign on once, single user",
+            "sso",
+        ];
+        let searcher = build_synonym_searcher(&texts, &rules, true);
+        // The phrase has to be a phrase
+        assert_eq!(
+            search(&searcher, "sso", SearchOptions::default()),
+            vec![(0, 1), (2, 1)]
+        );
+        assert!(search(&searcher, "single sign on", SearchOptions::default()).contains(&(2, 1)));
+
+        // Without positions, the phrase's words anywhere will do
+        let searcher = build_synonym_searcher(&texts, &rules, false);
+        assert_eq!(
+            search(&searcher, "sso", SearchOptions::default()),
+            vec![(0, 1), (1, 1), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_penalty() {
+        let searcher = build_synonym_searcher(&["kubernetes", "k8s"], &["k8s, kubernetes"], true);
+        let off = SearchOptions::default().with_synonym_penalty(0.0);
+        assert_eq!(search(&searcher, "k8s", off), vec![(1, 1)]);
+
+        let score = |penalty: f64| {
+            let options = SearchOptions::default().with_synonym_penalty(penalty);
+            let exclude: HashSet<usize> = [1].into_iter().collect();
+            let results = searcher.synonym_matches("k8s", &exclude, 10, true, None, None, penalty);
+            assert_eq!(results.len(), 1);
+            assert_eq!(search(&searcher, "k8s", options), vec![(0, 1), (1, 1)]);
+            results[0].score
+        };
+        assert!(score(0.5) < score(0.9));
+    }
+
+    #[test]
+    fn test_zero_penalty_turns_off_boolean_expansion() {
+        let searcher = build_synonym_searcher(
+            &["kubernetes cluster", "k8s cluster", "kubernetes docker"],
+            &["k8s, kubernetes"],
+            true,
+        );
+        let off = SearchOptions::default().with_synonym_penalty(0.0);
+        assert_eq!(search(&searcher, "k8s -docker", off), vec![(1, 1)]);
+    }
+}
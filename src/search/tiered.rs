@@ -24,19 +24,38 @@
 //! - A dedup worker maintains an ordered heap and forwards unique results
 //! - Results are emitted in ranked order (T1 > T2 > T3 by score)
 //! - Caller receives `Receiver<UIMessage>` for platform-specific handling
-
-use crate::binary::{LoadedLayer, PostingEntry};
-use crate::fuzzy::dfa::{ParametricDFA, QueryMatcher};
+//!
+//! ## Phonetic Tier
+//!
+//! Indexes built with phonetic keys support an opt-in Tier 4
+//! ([`SearchOptions::phonetic`]): terms no other tier matches fall back to
+//! vocabulary terms that sound the same, scored below any fuzzy match.
+
+pub use super::filter::SearchFilter;
+use super::proximity::{min_span, proximity_boost};
+use super::query::QueryNode;
+pub use super::synonyms::SYNONYM_PENALTY;
+use crate::binary::{LoadedLayer, PostingEntry, StoredText};
+use crate::fuzzy::dfa::{ParametricDFA, QueryMatcher, DEFAULT_K, MAX_K};
+use crate::fuzzy::phonetic::PhoneticIndex;
+pub use crate::fuzzy::policy::Fuzziness;
+use crate::fuzzy::trie::VocabTrie;
+use crate::index::language::Language;
+use crate::index::stem::StemIndex;
+use crate::index::synonyms::Synonyms;
+use crate::index::tokenizer::Tokenizer;
+use crate::index::translit::is_transliterable;
 use crate::scoring::ranking::compare_results;
-use crate::types::{MatchType, SearchDoc};
-use crate::util::simd::{starts_with_simd, to_lowercase_ascii_simd};
+use crate::types::{FieldType, MatchType, SearchDoc};
+use crate::util::normalize::fold;
+use crate::util::simd::starts_with_simd;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// Options for configuring search behavior.
 ///
 /// Used by `TierSearcher::search_with_options()` to customize result handling.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct SearchOptions {
     /// Whether to deduplicate sections within a document (default: true).
     ///
@@ -47,12 +66,89 @@ pub struct SearchOptions {
     /// match. Each section appears as a separate result, useful for showing
     /// all matching locations within a document.
     pub dedup_sections: bool,
+
+    /// Restrict the whole query to one field (default: `None`, all fields).
+    ///
+    /// Same as wrapping the query in `title:(...)`, `heading:(...)` or
+    /// `content:(...)`. Field operators inside the query still win.
+    pub field: Option<FieldType>,
+
+    /// Metadata conditions results must meet (default: none).
+    ///
+    /// Applied while collecting postings, before `limit`, so a filtered query
+    /// still fills its page.
+    pub filter: SearchFilter,
+
+    /// Skip this many ranked results (default: 0).
+    ///
+    /// Every page, the first included, is cut from one ranking of the whole
+    /// match set, so stepping the offset by the limit never overlaps or
+    /// skips a result. See [`TierSearcher::search_page`] for the total hit
+    /// count.
+    pub offset: usize,
+
+    /// Maximum edit distance for fuzzy matches (default: `None`, the
+    /// index's own k, usually 2).
+    ///
+    /// `Some(0)` turns fuzzy matching off. Values above [`MAX_K`] are
+    /// clamped. Long technical terms can take 3; short queries are better
+    /// off with 1.
+    pub max_edit_distance: Option<u8>,
+
+    /// How each term's length sets its edit budget (default:
+    /// [`Fuzziness::ADAPTIVE`]).
+    ///
+    /// Capped by `max_edit_distance`. `Fuzziness::Uniform` gives every term
+    /// the full budget, so "cat" gets two typos like "programming" does.
+    pub fuzziness: Fuzziness,
+
+    /// Treat the last query term as half-typed (default: false).
+    ///
+    /// For search-as-you-type: Tier 3 also matches the last term against
+    /// vocabulary *prefixes* within the edit budget, so "optimz" finds
+    /// "optimization" before the word is finished. Applies to plain word
+    /// queries; boolean syntax ignores it.
+    pub as_you_type: bool,
+
+    /// Also match terms that sound like the query's (default: false).
+    ///
+    /// Adds Tier 4 after fuzzy: a query term nothing else matches falls back
+    /// to vocabulary terms with the same phonetic key (see
+    /// [`PhoneticIndex`]), so "kathryn" finds "Catherine". Needs an index
+    /// built with phonetic keys; others ignore it, as does boolean syntax.
+    pub phonetic: bool,
+
+    /// Language the query is written in (default: `None`, unknown).
+    ///
+    /// Picks the stop words that phrases and `NEAR` skip, and the stemmer that
+    /// widens query words to their inflections, so a German "die" is
+    /// skipped while an English one is searched for. Without it, every
+    /// language's stop words are skipped and every stemmer in the index
+    /// applies.
+    pub language: Option<Language>,
+
+    /// Score multiplier for matches through the index's synonyms (default:
+    /// [`SYNONYM_PENALTY`]).
+    ///
+    /// A query word with a synonym rule also matches the rule's other side
+    /// at Tier 1, scored this fraction of an exact match. `0.0` turns
+    /// synonyms off; indexes built without them ignore it.
+    pub synonym_penalty: f64,
 }
 
 impl Default for SearchOptions {
     fn default() -> Self {
         Self {
             dedup_sections: true,
+            field: None,
+            filter: SearchFilter::default(),
+            offset: 0,
+            max_edit_distance: None,
+            fuzziness: Fuzziness::default(),
+            as_you_type: false,
+            phonetic: false,
+            language: None,
+            synonym_penalty: SYNONYM_PENALTY,
         } // Section dedup ON by default
     }
 }
@@ -67,10 +163,101 @@ impl SearchOptions {
     pub fn without_section_dedup() -> Self {
         Self {
             dedup_sections: false,
+            ..Default::default()
+        }
+    }
+
+    /// Restrict matches to one field.
+    pub fn in_field(self, field: FieldType) -> Self {
+        Self {
+            field: Some(field),
+            ..self
+        }
+    }
+
+    /// Only return documents matching `filter`.
+    pub fn with_filter(self, filter: SearchFilter) -> Self {
+        Self { filter, ..self }
+    }
+
+    /// Start the page `offset` results into the ranking.
+    pub fn starting_at(self, offset: usize) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Allow fuzzy matches up to `k` edits instead of the index default.
+    pub fn with_max_edit_distance(self, k: u8) -> Self {
+        Self {
+            max_edit_distance: Some(k),
+            ..self
+        }
+    }
+
+    /// Size each term's edit budget with `fuzziness`.
+    pub fn with_fuzziness(self, fuzziness: Fuzziness) -> Self {
+        Self { fuzziness, ..self }
+    }
+
+    /// Fuzzy-match the last term as a prefix, for search-as-you-type.
+    pub fn as_you_type(self) -> Self {
+        Self {
+            as_you_type: true,
+            ..self
+        }
+    }
+
+    /// Fall back to phonetic matches for terms nothing else matches.
+    pub fn with_phonetic(self) -> Self {
+        Self {
+            phonetic: true,
+            ..self
+        }
+    }
+
+    /// Analyze the query as `language`: its stop words and its stemmer.
+    pub fn in_language(self, language: Language) -> Self {
+        Self {
+            language: Some(language),
+            ..self
+        }
+    }
+
+    /// Score synonym matches at `penalty` of an exact match (`0.0`: off).
+    pub fn with_synonym_penalty(self, penalty: f64) -> Self {
+        Self {
+            synonym_penalty: penalty,
+            ..self
         }
     }
 }
 
+/// Fuzzy edit budget for one search: the ceiling, how each term's length
+/// sizes its share of it, and whether the last term is still being typed.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct EditBudget {
+    pub max_distance: u8,
+    pub fuzziness: Fuzziness,
+    pub last_term_prefix: bool,
+}
+
+impl EditBudget {
+    /// No fuzzy matching at all.
+    pub const EXACT: Self = Self {
+        max_distance: 0,
+        fuzziness: Fuzziness::Uniform,
+        last_term_prefix: false,
+    };
+
+    /// Edit distance allowed for `term`.
+    pub fn for_term(self, term: &str) -> u8 {
+        self.fuzziness.distance_for(term, self.max_distance)
+    }
+}
+
+/// Score multiplier for Tier 4 (phonetic) matches: below the 1 / (1 + 3)
+/// of a fuzzy match at the most edits any query allows.
+const PHONETIC_PENALTY: f64 = 0.2;
+
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 #[cfg(feature = "rayon")]
@@ -138,23 +325,34 @@ pub struct SearchResult {
     pub doc_id: usize,
     pub score: f64,
     pub section_idx: u32,          // 0 = no section, >0 = section_table[idx-1]
-    pub tier: u8,                  // 1=exact, 2=prefix, 3=fuzzy
+    pub tier: u8,                  // 1=exact, 2=prefix, 3=fuzzy, 4=phonetic
     pub match_type: MatchType,     // Primary sort key: Title > Section > ... > Content
     pub matched_term: Option<u32>, // Vocabulary index of matched term (for display)
 }
 
 /// Match found by fuzzy search with edit distance.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FuzzyMatch {
     pub distance: u8,
     pub term_idx: usize,
 }
 
+/// A query term resolved against the vocabulary at its best tier.
+///
+/// Used by multi-term search so each term can match at a different tier
+/// (e.g. one exact term plus one typo). `matches` holds
+/// `(vocab_idx, score_multiplier)` pairs for the winning tier only.
+#[derive(Debug, Clone)]
+pub struct ResolvedTerm {
+    pub tier: u8,
+    pub matches: Vec<(usize, f64)>,
+}
+
 /// Accumulator for multi-term search score summing.
 ///
 /// Tracks cumulative scores per (doc_id, section_idx) pair, best match_type,
 /// and which query terms hit each document for AND semantics filtering.
-struct MultiTermAccumulator {
+pub(super) struct MultiTermAccumulator {
     /// Cumulative score per (doc_id, section_idx) pair
     doc_scores: HashMap<(usize, u32), f64>,
     /// Best match_type per (doc_id, section_idx) pair
@@ -163,18 +361,22 @@ struct MultiTermAccumulator {
     doc_matched_terms: HashMap<(usize, u32), u32>,
     /// Which query term indices hit each doc_id (for AND semantics)
     doc_term_hits: HashMap<usize, HashSet<usize>>,
+    /// (word position, query term index) per (doc_id, section_idx) pair,
+    /// for proximity scoring. Empty when the index has no positions.
+    doc_occurrences: HashMap<(usize, u32), Vec<(u32, usize)>>,
     /// Total number of query terms (for AND filtering)
     num_terms: usize,
 }
 
 impl MultiTermAccumulator {
     /// Create a new accumulator for a query with the given number of terms.
-    fn new(num_terms: usize) -> Self {
+    pub(super) fn new(num_terms: usize) -> Self {
         Self {
             doc_scores: HashMap::new(),
             doc_match_types: HashMap::new(),
             doc_matched_terms: HashMap::new(),
             doc_term_hits: HashMap::new(),
+            doc_occurrences: HashMap::new(),
             num_terms,
         }
     }
@@ -188,8 +390,10 @@ impl MultiTermAccumulator {
     /// * `match_type` - Match type (Title, Heading, Content)
     /// * `score` - Score to add for this match
     /// * `vocab_idx` - Vocabulary index of the matched term
+    /// * `position` - Word position of this occurrence, if the index has them
     #[inline]
-    fn add_match(
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn add_match(
         &mut self,
         term_idx: usize,
         doc_id: usize,
@@ -197,9 +401,17 @@ impl MultiTermAccumulator {
         match_type: MatchType,
         score: f64,
         vocab_idx: u32,
+        position: Option<u32>,
     ) {
         let key = (doc_id, section_idx);
 
+        if let Some(position) = position {
+            self.doc_occurrences
+                .entry(key)
+                .or_default()
+                .push((position, term_idx));
+        }
+
         // Sum scores across terms
         *self.doc_scores.entry(key).or_insert(0.0) += score;
 
@@ -228,21 +440,32 @@ impl MultiTermAccumulator {
     /// Build search results from accumulated scores.
     ///
     /// Filters to documents matching ALL query terms (AND semantics),
-    /// optionally deduplicates by doc_id (keeping best match_type/score per doc),
-    /// sorts by score descending and truncates to limit.
+    /// boosts sections where the terms occur close together (see
+    /// `proximity.rs`), optionally deduplicates by doc_id (keeping best
+    /// match_type/score per doc), sorts by score descending and truncates.
     ///
     /// # Arguments
     /// * `tier` - Search tier (1=exact, 2=prefix, 3=fuzzy)
     /// * `limit` - Maximum results to return
     /// * `docs` - Document metadata for ranking
     /// * `dedup_sections` - If true, keep only best section per doc
-    fn into_results(
-        self,
+    pub(super) fn into_results(
+        mut self,
         tier: u8,
         limit: usize,
         docs: &[SearchDoc],
         dedup_sections: bool,
     ) -> Vec<SearchResult> {
+        // Proximity: a score multiplier only, so match_type buckets are untouched
+        for (key, occurrences) in &mut self.doc_occurrences {
+            if let (Some(span), Some(score)) = (
+                min_span(occurrences, self.num_terms),
+                self.doc_scores.get_mut(key),
+            ) {
+                *score *= proximity_boost(span, self.num_terms);
+            }
+        }
+
         // First pass: collect all (doc_id, section_idx) matches
         let section_results: Vec<SearchResult> = self
             .doc_scores
@@ -266,64 +489,67 @@ impl MultiTermAccumulator {
             })
             .collect();
 
-        if !dedup_sections {
-            // No dedup: return all section matches
-            let mut results = section_results;
-            results.sort_by(|a, b| compare_results(a, b, docs));
-            results.truncate(limit);
-            return results;
-        }
-
-        // Second pass: deduplicate by doc_id
-        // Track: (total_score, best_section_idx, best_section_score, best_match_type, best_matched_term)
-        // - total_score: sum of all section scores (for ranking)
-        // - best_section_*: the section with best match_type, then highest score (for deep linking)
-        let mut best_per_doc: HashMap<usize, (f64, u32, f64, MatchType, Option<u32>)> =
-            HashMap::new();
-        for result in section_results {
-            best_per_doc
-                .entry(result.doc_id)
-                .and_modify(|(total, best_idx, best_score, best_type, best_term)| {
-                    // Always sum scores for ranking
-                    *total += result.score;
-
-                    // Update best section if this one is better (for deep linking)
-                    // Best = lowest match_type (Title < Section < Content), then highest score
-                    let is_better = result.match_type < *best_type
-                        || (result.match_type == *best_type && result.score > *best_score);
-                    if is_better {
-                        *best_idx = result.section_idx;
-                        *best_score = result.score;
-                        *best_type = result.match_type;
-                        *best_term = result.matched_term;
-                    }
-                })
-                .or_insert((
-                    result.score,
-                    result.section_idx,
-                    result.score,
-                    result.match_type,
-                    result.matched_term,
-                ));
-        }
+        finalize_results(section_results, limit, docs, dedup_sections)
+    }
+}
 
-        let mut results: Vec<SearchResult> = best_per_doc
-            .into_iter()
-            .map(
-                |(doc_id, (total_score, section_idx, _, match_type, matched_term))| SearchResult {
-                    doc_id,
-                    score: total_score, // Use summed score for ranking
-                    section_idx,        // Use best section for deep linking
-                    tier,
-                    match_type,
-                    matched_term,
-                },
-            )
-            .collect();
+/// Rank per-section matches into final results.
+///
+/// Optionally deduplicates by doc_id (summing section scores for ranking,
+/// keeping the best section for deep linking), then sorts and truncates.
+///
+/// # Arguments
+/// * `section_results` - One result per matching (doc_id, section_idx) pair
+/// * `limit` - Maximum results to return
+/// * `docs` - Document metadata for ranking
+/// * `dedup_sections` - If true, keep only best section per doc
+pub(crate) fn finalize_results(
+    section_results: Vec<SearchResult>,
+    limit: usize,
+    docs: &[SearchDoc],
+    dedup_sections: bool,
+) -> Vec<SearchResult> {
+    if !dedup_sections {
+        // No dedup: return all section matches
+        let mut results = section_results;
         results.sort_by(|a, b| compare_results(a, b, docs));
         results.truncate(limit);
-        results
+        return results;
+    }
+
+    // Second pass: deduplicate by doc_id
+    // Track: (total_score, best_section)
+    // - total_score: sum of all section scores (for ranking)
+    // - best_section: the section with best match_type, then highest score (for deep linking)
+    let mut best_per_doc: HashMap<usize, (f64, SearchResult)> = HashMap::new();
+    for result in section_results {
+        best_per_doc
+            .entry(result.doc_id)
+            .and_modify(|(total, best)| {
+                // Always sum scores for ranking
+                *total += result.score;
+
+                // Update best section if this one is better (for deep linking)
+                // Best = lowest match_type (Title < Section < Content), then highest score
+                let is_better = result.match_type < best.match_type
+                    || (result.match_type == best.match_type && result.score > best.score);
+                if is_better {
+                    *best = result.clone();
+                }
+            })
+            .or_insert((result.score, result));
     }
+
+    let mut results: Vec<SearchResult> = best_per_doc
+        .into_values()
+        .map(|(total_score, best)| SearchResult {
+            score: total_score, // Use summed score for ranking
+            ..best              // Use best section for deep linking
+        })
+        .collect();
+    results.sort_by(|a, b| compare_results(a, b, docs));
+    results.truncate(limit);
+    results
 }
 
 /// Thread-safe inner data for the three-tier searcher.
@@ -336,8 +562,27 @@ pub struct TierSearcherInner {
     pub vocabulary: Vec<String>,
     pub suffix_array: Vec<(u32, u32)>,
     pub postings: Vec<Vec<PostingEntry>>,
+    /// Word position per posting entry, parallel to `postings` (empty if the
+    /// index was built without positions)
+    pub positions: Vec<Vec<u32>>,
     pub inverted_index: HashMap<String, Vec<PostingEntry>>,
     pub lev_dfa: Option<ParametricDFA>,
+    /// Skip table for walking the vocabulary in fuzzy search (`None` falls
+    /// back to scanning every term)
+    pub vocab_trie: Option<VocabTrie>,
+    /// Phonetic key per vocabulary term (`None` unless built with them)
+    pub phonetic: Option<PhoneticIndex>,
+    /// Vocabulary terms grouped by stem (`None` unless built with a stemmer)
+    pub stems: Option<StemIndex>,
+    /// Synonym rules (`None` unless built with them)
+    pub synonyms: Option<Synonyms>,
+    /// How the index cut its words, so queries are cut the same way
+    pub tokenizer: Tokenizer,
+    /// Cyrillic, Greek and Devanagari words were indexed romanized too, so
+    /// queries in those scripts are romanized
+    pub transliterated: bool,
+    /// Stored document text for snippets (`None` unless built with text)
+    pub text: Option<StoredText>,
 }
 
 /// Pure Rust three-tier searcher (exact → prefix → fuzzy).
@@ -383,6 +628,27 @@ impl TierSearcher {
         &self.inner.postings
     }
 
+    /// Access word positions (parallel to postings; empty without positions).
+    #[inline]
+    pub fn positions(&self) -> &[Vec<u32>] {
+        &self.inner.positions
+    }
+
+    /// Word position of the `entry_idx`-th posting of a vocabulary term.
+    #[inline]
+    pub(super) fn position_of(&self, vocab_idx: usize, entry_idx: usize) -> Option<u32> {
+        self.inner.positions.get(vocab_idx)?.get(entry_idx).copied()
+    }
+
+    /// Is `doc_id` a loaded document that passes the query's filter mask?
+    #[inline]
+    pub(super) fn admits(&self, doc_id: usize, allowed: Option<&[bool]>) -> bool {
+        match allowed {
+            Some(mask) => mask.get(doc_id).copied().unwrap_or(false),
+            None => doc_id < self.inner.docs.len(),
+        }
+    }
+
     /// Access inverted index.
     #[inline]
     pub fn inverted_index(&self) -> &HashMap<String, Vec<PostingEntry>> {
@@ -394,6 +660,124 @@ impl TierSearcher {
     pub fn lev_dfa(&self) -> Option<&ParametricDFA> {
         self.inner.lev_dfa.as_ref()
     }
+
+    /// Vocabulary terms within the edit budget of `query`, closest first.
+    ///
+    /// Walks the vocabulary trie when the index has one, touching only terms
+    /// whose prefixes stay within budget; otherwise scans every term. Both
+    /// give the same matches in the same order. `prefix` matches the query
+    /// against the start of each term (see [`fuzzy_prefix_search_vocabulary`]).
+    pub(crate) fn fuzzy_terms(
+        &self,
+        query: &str,
+        max_distance: u8,
+        fuzziness: Fuzziness,
+        prefix: bool,
+    ) -> Vec<FuzzyMatch> {
+        let vocabulary = &self.inner.vocabulary;
+        let dfa = self.inner.lev_dfa.as_ref();
+        let (Some(trie), Some(_)) = (&self.inner.vocab_trie, dfa) else {
+            return if prefix {
+                fuzzy_prefix_search_vocabulary(vocabulary, dfa, query, max_distance, fuzziness)
+            } else {
+                fuzzy_search_vocabulary(vocabulary, dfa, query, max_distance, fuzziness)
+            };
+        };
+
+        let max_distance = fuzziness.distance_for(query, max_distance);
+        let mut matches: Vec<FuzzyMatch> = trie
+            .fuzzy_matches(vocabulary, query, max_distance, prefix)
+            .into_iter()
+            .map(|(term_idx, distance)| FuzzyMatch { distance, term_idx })
+            .collect();
+        matches.sort_by_key(|m| m.distance);
+        matches
+    }
+
+    /// Vocabulary terms that sound like `word` (empty without phonetic keys).
+    pub(crate) fn phonetic_terms(&self, word: &str) -> &[u32] {
+        self.inner
+            .phonetic
+            .as_ref()
+            .map_or(&[], |phonetic| phonetic.matches(word))
+    }
+
+    /// Vocabulary terms sharing a stem with `word` in `language`, or in any
+    /// language without one (empty without stems).
+    pub(crate) fn stem_terms(&self, word: &str, language: Option<Language>) -> Vec<u32> {
+        let Some(stems) = &self.inner.stems else {
+            return Vec::new();
+        };
+        match language {
+            Some(language) => stems.matches_in(word, language),
+            None => stems.matches(word),
+        }
+    }
+
+    /// Default fuzzy edit distance: the k the index's DFA was built for.
+    #[inline]
+    pub fn max_edit_distance(&self) -> u8 {
+        self.inner
+            .lev_dfa
+            .as_ref()
+            .map_or(DEFAULT_K, |dfa| dfa.max_distance)
+    }
+
+    /// Edit budget a search with `options` allows.
+    pub(crate) fn edit_budget(&self, options: &SearchOptions) -> EditBudget {
+        EditBudget {
+            max_distance: options
+                .max_edit_distance
+                .map_or(self.max_edit_distance(), |k| k.min(MAX_K)),
+            fuzziness: options.fuzziness,
+            last_term_prefix: options.as_you_type,
+        }
+    }
+
+    /// Access stored document text (`None` unless the index was built with it).
+    #[inline]
+    pub fn stored_text(&self) -> Option<&StoredText> {
+        self.inner.text.as_ref()
+    }
+
+    /// How the index cut its words (and so how queries are cut).
+    #[inline]
+    pub fn tokenizer(&self) -> Tokenizer {
+        self.inner.tokenizer
+    }
+
+    /// Whether the index also holds Cyrillic, Greek and Devanagari words
+    /// romanized (and so romanizes queries in those scripts).
+    #[inline]
+    pub fn transliterated(&self) -> bool {
+        self.inner.transliterated
+    }
+
+    /// The synonym rules the index was built with, if any.
+    #[inline]
+    pub fn synonyms(&self) -> Option<&Synonyms> {
+        self.inner.synonyms.as_ref()
+    }
+
+    /// Parse `query` the way this index cut its words.
+    pub(crate) fn parse_query(&self, query: &str) -> Option<QueryNode> {
+        if self.inner.transliterated {
+            QueryNode::parse_transliterated(query, self.inner.tokenizer)
+        } else {
+            QueryNode::parse_with(query, self.inner.tokenizer)
+        }
+    }
+
+    /// True if `query` is evaluated in one pass rather than tier by tier:
+    /// boolean syntax, any query against a code-tokenized index, whose
+    /// identifiers only the parser knows how to cut, and Cyrillic, Greek or
+    /// Devanagari against a transliterated one, which only the parser
+    /// romanizes.
+    pub fn evaluates_in_one_pass(&self, query: &str) -> bool {
+        self.inner.tokenizer == Tokenizer::Code
+            || (self.inner.transliterated && query.chars().any(is_transliterable))
+            || QueryNode::uses_boolean_syntax(query)
+    }
 }
 
 impl TierSearcher {
@@ -445,8 +829,16 @@ impl TierSearcher {
             vocabulary: layer.vocabulary,
             suffix_array: layer.suffix_array,
             postings: layer.postings,
+            positions: layer.positions,
             inverted_index,
             lev_dfa,
+            vocab_trie: layer.vocab_trie,
+            phonetic: layer.phonetic,
+            stems: layer.stems,
+            synonyms: layer.synonyms,
+            tokenizer: layer.tokenizer,
+            transliterated: layer.transliterated,
+            text: layer.text,
         };
 
         let searcher = TierSearcher {
@@ -496,6 +888,19 @@ impl TierSearcher {
             }
         }
 
+        // Positions (if present) must line up with postings entry for entry
+        if !self.inner.positions.is_empty()
+            && (self.inner.positions.len() != self.inner.postings.len()
+                || self
+                    .inner
+                    .positions
+                    .iter()
+                    .zip(&self.inner.postings)
+                    .any(|(p, e)| p.len() != e.len()))
+        {
+            return Some("Positions do not match postings".to_string());
+        }
+
         // Validate postings
         for (term_idx, postings) in self.inner.postings.iter().enumerate() {
             if term_idx >= self.inner.vocabulary.len() {
@@ -535,6 +940,13 @@ impl TierSearcher {
 
     /// Full three-tier search with configurable options.
     ///
+    /// Queries using boolean syntax (`OR`, `-term`, `"exact"`, parentheses;
+    /// see [`QueryNode`]) are evaluated by [`TierSearcher::search_query`].
+    ///
+    /// Returns the results of [`TierSearcher::search_page`]: every call cuts
+    /// its page from the same ranking of the whole match set, so stepping
+    /// `options.offset` by `limit` never repeats or skips a result.
+    ///
     /// # Arguments
     /// * `query` - Search query (whitespace-separated terms use AND semantics)
     /// * `limit` - Maximum results to return
@@ -549,36 +961,80 @@ impl TierSearcher {
         if query.is_empty() || limit == 0 {
             return Vec::new();
         }
+        self.search_page(query, limit, options).results
+    }
 
+    /// Every match for `query`, best first: the ranking that pages are cut
+    /// from. Ignores `options.offset`.
+    pub(super) fn rank_all(&self, query: &str, options: SearchOptions) -> Vec<SearchResult> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let limit = usize::MAX;
         let dedup = options.dedup_sections;
+        let mask = options.filter.mask(&self.inner.docs);
+        let allowed = mask.as_deref();
+
+        // Boolean syntax (OR, -term, quotes, parens, fields) goes through the
+        // AST evaluator. Plain word lists keep the tier pipeline below.
+        let Some(ast) = self.parse_query(query) else {
+            return Vec::new();
+        };
+        if let Some(field) = options.field {
+            let ast = QueryNode::Field {
+                field,
+                node: Box::new(ast),
+            };
+            return self.search_query(&ast, limit, options);
+        }
+        let plain_query = match ast.plain_terms() {
+            Some(terms) => terms.join(" "),
+            None => return self.search_query(&ast, limit, options),
+        };
+        let query = plain_query.as_str();
 
-        // Tier 1: Exact match (handles multi-term with AND semantics)
-        let t1_results = self.search_tier1_exact_with_options(query, limit, dedup);
+        // Tier 1: Exact match (handles multi-term with AND semantics, other
+        // inflections of the same words, the words split or joined
+        // differently, and synonyms)
+        let t1_results = self.tier1_exact(query, limit, allowed, &options);
         let t1_ids: HashSet<usize> = t1_results.iter().map(|r| r.doc_id).collect();
 
         // Tier 2: Prefix match (exclude T1 results)
-        let t2_results = self.search_tier2_prefix_with_options(query, &t1_ids, limit, dedup);
+        let t2_results = self.tier2_prefix(query, &t1_ids, limit, dedup, allowed);
         let t2_ids: HashSet<usize> = t2_results.iter().map(|r| r.doc_id).collect();
 
         // Tier 3: Fuzzy match (exclude T1 and T2 results)
         let mut exclude_ids = t1_ids;
         exclude_ids.extend(t2_ids);
-        let t3_results = self.search_tier3_fuzzy_with_options(query, &exclude_ids, limit, dedup);
+        let budget = self.edit_budget(&options);
+        let t3_results = self.tier3_fuzzy(query, &exclude_ids, limit, dedup, allowed, budget);
+
+        // Tier 4: Phonetic match, only when asked for (exclude T1-T3 results)
+        let t4_results = if options.phonetic {
+            exclude_ids.extend(t3_results.iter().map(|r| r.doc_id));
+            self.tier4_phonetic(query, &exclude_ids, limit, dedup, allowed, budget)
+        } else {
+            Vec::new()
+        };
 
         // Merge and sort results
         let mut results: Vec<_> = t1_results
             .into_iter()
             .chain(t2_results)
             .chain(t3_results)
+            .chain(t4_results)
             .collect();
         results.sort_by(|a, b| compare_results(a, b, &self.inner.docs));
-        results.truncate(limit);
         results
     }
 
-    /// Tier 1: Exact word match only (O(1) inverted index lookup).
+    /// Tier 1: Exact word match (O(1) inverted index lookup).
     ///
-    /// Returns doc IDs for exact matches only. Fast path for progressive search.
+    /// Returns doc IDs for exact matches, and for other inflections of the
+    /// words ("optimizing" → "optimized"), the words split or joined
+    /// differently ("data base" ↔ "database") or the index's synonyms for
+    /// them ("k8s" → "kubernetes"), all scored lower. Fast path for
+    /// progressive search.
     /// Results are bucketed by match type (Title > Section > Subsection > etc.)
     /// to ensure structural field hierarchy is respected in ranking.
     ///
@@ -599,11 +1055,86 @@ impl TierSearcher {
         limit: usize,
         dedup_sections: bool,
     ) -> Vec<SearchResult> {
-        let query_lower = to_lowercase_ascii_simd(query);
+        let options = SearchOptions {
+            dedup_sections,
+            ..SearchOptions::default()
+        };
+        self.tier1_exact(query, limit, None, &options)
+    }
+
+    /// Tier 1, returning only documents `allowed` by a filter mask: the
+    /// words as typed, then [`TierSearcher::tier1_expansions`] of them.
+    fn tier1_exact(
+        &self,
+        query: &str,
+        limit: usize,
+        allowed: Option<&[bool]>,
+        options: &SearchOptions,
+    ) -> Vec<SearchResult> {
+        let mut results = self.tier1_typed(query, limit, options.dedup_sections, allowed);
+        let exact_ids: HashSet<usize> = results.iter().map(|r| r.doc_id).collect();
+        let expansions = self.tier1_expansions(query, &exact_ids, limit, allowed, options);
+        if !expansions.is_empty() {
+            results.extend(expansions);
+            results.sort_by(|a, b| compare_results(a, b, &self.inner.docs));
+            results.truncate(limit);
+        }
+        results
+    }
+
+    /// Tier 1 matches for other forms of the query's words: other
+    /// inflections of them ("optimizing" → "optimized"), the words split or
+    /// joined differently ("data base" ↔ "database"), then the words the
+    /// index's synonym rules put in their place ("k8s" → "kubernetes").
+    ///
+    /// Shared by the tier pipeline and boolean terms, so every search path
+    /// widens words the same way. Skips `exclude_ids` (the exact matches),
+    /// each form's documents for the next, and documents the filter mask
+    /// doesn't allow.
+    pub(super) fn tier1_expansions(
+        &self,
+        query: &str,
+        exclude_ids: &HashSet<usize>,
+        limit: usize,
+        allowed: Option<&[bool]>,
+        options: &SearchOptions,
+    ) -> Vec<SearchResult> {
+        let dedup = options.dedup_sections;
+        let language = options.language;
+        let mut exclude_ids = exclude_ids.clone();
+
+        let mut results = self.stem_matches(query, &exclude_ids, limit, dedup, allowed, language);
+        exclude_ids.extend(results.iter().map(|r| r.doc_id));
+        let split_join =
+            self.split_join_matches(query, &exclude_ids, limit, dedup, allowed, language);
+        exclude_ids.extend(split_join.iter().map(|r| r.doc_id));
+        results.extend(split_join);
+        let penalty = options.synonym_penalty;
+        results.extend(self.synonym_matches(
+            query,
+            &exclude_ids,
+            limit,
+            dedup,
+            allowed,
+            language,
+            penalty,
+        ));
+        results
+    }
+
+    /// Tier 1 for the words exactly as typed.
+    fn tier1_typed(
+        &self,
+        query: &str,
+        limit: usize,
+        dedup_sections: bool,
+        allowed: Option<&[bool]>,
+    ) -> Vec<SearchResult> {
+        let query_lower = fold(query);
 
         // Fast path: single word (no whitespace) - skip split/collect
         if !query_lower.contains(' ') && !query_lower.is_empty() {
-            return self.search_tier1_single_term(&query_lower, limit);
+            return self.search_tier1_single_term(&query_lower, limit, allowed);
         }
 
         let parts: Vec<&str> = query_lower
@@ -613,7 +1144,7 @@ impl TierSearcher {
 
         // Single-term optimization: leverage presorted posting list
         if parts.len() == 1 {
-            return self.search_tier1_single_term(parts[0], limit);
+            return self.search_tier1_single_term(parts[0], limit, allowed);
         }
 
         // Multi-term: sum scores across matching terms (AND semantics)
@@ -630,9 +1161,9 @@ impl TierSearcher {
                 .map(|i| i as u32);
 
             if let Some(postings) = self.inner.inverted_index.get(*part) {
-                for entry in postings {
+                for (entry_idx, entry) in postings.iter().enumerate() {
                     let doc_id = entry.doc_id as usize;
-                    if self.inner.docs.get(doc_id).is_none() {
+                    if !self.admits(doc_id, allowed) {
                         continue;
                     }
 
@@ -645,6 +1176,7 @@ impl TierSearcher {
                         match_type,
                         entry.score as f64,
                         vocab_idx.unwrap_or(u32::MAX),
+                        vocab_idx.and_then(|v| self.position_of(v as usize, entry_idx)),
                     );
                 }
             }
@@ -662,9 +1194,15 @@ impl TierSearcher {
     /// Uses pre-computed scores from posting entries (set at index time by
     /// user-defined ranking function or default scoring).
     #[inline]
-    fn search_tier1_single_term(&self, term: &str, limit: usize) -> Vec<SearchResult> {
-        let mut results = Vec::with_capacity(limit);
-        let mut seen_docs = HashSet::with_capacity(limit);
+    fn search_tier1_single_term(
+        &self,
+        term: &str,
+        limit: usize,
+        allowed: Option<&[bool]>,
+    ) -> Vec<SearchResult> {
+        let capacity = limit.min(self.inner.docs.len());
+        let mut results = Vec::with_capacity(capacity);
+        let mut seen_docs = HashSet::with_capacity(capacity);
 
         // Find vocabulary index for this term (for matched_term tracking)
         let vocab_idx = self
@@ -677,7 +1215,7 @@ impl TierSearcher {
         if let Some(postings) = self.inner.inverted_index.get(term) {
             for entry in postings {
                 let doc_id = entry.doc_id as usize;
-                if self.inner.docs.get(doc_id).is_none() {
+                if !self.admits(doc_id, allowed) {
                     continue;
                 }
 
@@ -726,7 +1264,19 @@ impl TierSearcher {
         limit: usize,
         dedup_sections: bool,
     ) -> Vec<SearchResult> {
-        let query_lower = to_lowercase_ascii_simd(query);
+        self.tier2_prefix(query, exclude_ids, limit, dedup_sections, None)
+    }
+
+    /// Tier 2, returning only documents `allowed` by a filter mask.
+    fn tier2_prefix(
+        &self,
+        query: &str,
+        exclude_ids: &HashSet<usize>,
+        limit: usize,
+        dedup_sections: bool,
+        allowed: Option<&[bool]>,
+    ) -> Vec<SearchResult> {
+        let query_lower = fold(query);
 
         // Split query into parts for multi-term handling
         let parts: Vec<&str> = query_lower
@@ -740,7 +1290,7 @@ impl TierSearcher {
 
         // Single-term fast path
         if parts.len() == 1 {
-            return self.search_tier2_single_term(parts[0], exclude_ids, limit);
+            return self.search_tier2_single_term(parts[0], exclude_ids, limit, allowed);
         }
 
         // Multi-term: sum scores across matching prefix terms (AND semantics)
@@ -760,9 +1310,9 @@ impl TierSearcher {
                     .unwrap_or(1);
 
                 if let Some(postings) = self.inner.postings.get(vocab_idx) {
-                    for entry in postings {
+                    for (entry_idx, entry) in postings.iter().enumerate() {
                         let doc_id = entry.doc_id as usize;
-                        if exclude_ids.contains(&doc_id) || self.inner.docs.get(doc_id).is_none() {
+                        if exclude_ids.contains(&doc_id) || !self.admits(doc_id, allowed) {
                             continue;
                         }
 
@@ -780,6 +1330,7 @@ impl TierSearcher {
                             match_type,
                             penalized_score,
                             vocab_idx as u32,
+                            self.position_of(vocab_idx, entry_idx),
                         );
                     }
                 }
@@ -798,6 +1349,7 @@ impl TierSearcher {
         prefix: &str,
         exclude_ids: &HashSet<usize>,
         limit: usize,
+        allowed: Option<&[bool]>,
     ) -> Vec<SearchResult> {
         let mut results_by_doc: HashMap<usize, SearchResult> = HashMap::new();
 
@@ -816,7 +1368,7 @@ impl TierSearcher {
             if let Some(postings) = self.inner.postings.get(vocab_idx) {
                 for entry in postings {
                     let doc_id = entry.doc_id as usize;
-                    if exclude_ids.contains(&doc_id) || self.inner.docs.get(doc_id).is_none() {
+                    if exclude_ids.contains(&doc_id) || !self.admits(doc_id, allowed) {
                         continue;
                     }
 
@@ -863,7 +1415,11 @@ impl TierSearcher {
     /// NOTE: Only returns matches at distance > 0 (exact matches are T1's job)
     ///
     /// Applies edit distance penalty: 20% per edit distance.
-    /// Multi-term queries sum scores across matching fuzzy terms.
+    ///
+    /// Multi-term queries resolve each term at its best tier (exact → prefix →
+    /// fuzzy) before the AND, so "rust optimzer" pairs an exact "rust" with a
+    /// fuzzy "optimizer". Only queries where at least one term needed fuzzy
+    /// matching return results here; the result tier is the worst tier used.
     pub fn search_tier3_fuzzy(
         &self,
         query: &str,
@@ -881,7 +1437,28 @@ impl TierSearcher {
         limit: usize,
         dedup_sections: bool,
     ) -> Vec<SearchResult> {
-        let query_lower = to_lowercase_ascii_simd(query);
+        self.tier3_fuzzy(
+            query,
+            exclude_ids,
+            limit,
+            dedup_sections,
+            None,
+            self.edit_budget(&SearchOptions::default()),
+        )
+    }
+
+    /// Tier 3, returning only documents `allowed` by a filter mask, with
+    /// fuzzy matches within `budget`.
+    fn tier3_fuzzy(
+        &self,
+        query: &str,
+        exclude_ids: &HashSet<usize>,
+        limit: usize,
+        dedup_sections: bool,
+        allowed: Option<&[bool]>,
+        budget: EditBudget,
+    ) -> Vec<SearchResult> {
+        let query_lower = fold(query);
 
         // Split query into parts for multi-term handling
         let parts: Vec<&str> = query_lower
@@ -895,109 +1472,245 @@ impl TierSearcher {
 
         // Single-term fast path
         if parts.len() == 1 {
-            return self.search_tier3_single_term(parts[0], exclude_ids, limit);
+            return self.search_tier3_single_term(parts[0], exclude_ids, limit, allowed, budget);
         }
 
-        // Multi-term: sum scores across matching fuzzy terms (AND semantics)
-        let mut acc = MultiTermAccumulator::new(parts.len());
+        // Multi-term: resolve each term independently at its best tier, so
+        // "rust optimzer" pairs the exact "rust" with the fuzzy "optimizer".
+        // A term with no match at any tier means no document can satisfy AND.
+        let mut resolved = Vec::with_capacity(parts.len());
+        for (i, part) in parts.iter().enumerate() {
+            let typing = budget.last_term_prefix && i + 1 == parts.len();
+            match self.resolve_part(part, budget, typing) {
+                Some(term) => resolved.push(term),
+                None => return vec![],
+            }
+        }
 
-        // Maximum edit distance for T3 fuzzy search
-        const MAX_EDIT_DISTANCE: u8 = 2;
+        // All terms resolved at T1/T2: those documents belong to earlier tiers
+        let tier = resolved.iter().map(|r| r.tier).max().unwrap_or(3);
+        if tier < 3 {
+            return vec![];
+        }
 
-        for (term_idx, part) in parts.iter().enumerate() {
-            let fuzzy_matches = fuzzy_search_vocabulary(
-                &self.inner.vocabulary,
-                self.inner.lev_dfa.as_ref(),
-                part,
-                MAX_EDIT_DISTANCE,
-            );
+        // Result tier reflects the worst tier any term needed
+        self.accumulate_resolved(&resolved, exclude_ids, allowed)
+            .into_results(tier, limit, &self.inner.docs, dedup_sections)
+    }
 
-            for FuzzyMatch {
-                term_idx: vocab_idx,
-                distance,
-            } in fuzzy_matches
-            {
-                // Skip exact matches (distance 0) - those are T1's responsibility
-                if distance == 0 {
-                    continue;
-                }
+    /// Tier 4: phonetic match, for names spelled by ear.
+    ///
+    /// Each query term resolves at its best tier as in Tier 3; a term
+    /// nothing matches falls back to the vocabulary terms sharing its
+    /// phonetic key. At least one term must need that fallback, or its
+    /// documents were Tier 3's to find. Empty without phonetic keys.
+    fn tier4_phonetic(
+        &self,
+        query: &str,
+        exclude_ids: &HashSet<usize>,
+        limit: usize,
+        dedup_sections: bool,
+        allowed: Option<&[bool]>,
+        budget: EditBudget,
+    ) -> Vec<SearchResult> {
+        if self.inner.phonetic.is_none() {
+            return vec![];
+        }
+        let query_lower = fold(query);
+        let parts: Vec<&str> = query_lower.split_whitespace().collect();
+
+        let mut resolved = Vec::with_capacity(parts.len());
+        for (i, part) in parts.iter().enumerate() {
+            let typing = budget.last_term_prefix && i + 1 == parts.len();
+            let term = self.resolve_part(part, budget, typing).or_else(|| {
+                let matches: Vec<(usize, f64)> = self
+                    .phonetic_terms(part)
+                    .iter()
+                    .map(|&vocab_idx| (vocab_idx as usize, PHONETIC_PENALTY))
+                    .collect();
+                (!matches.is_empty()).then_some(ResolvedTerm { tier: 4, matches })
+            });
+            match term {
+                Some(term) => resolved.push(term),
+                None => return vec![],
+            }
+        }
+        if resolved.iter().all(|r| r.tier < 4) {
+            return vec![];
+        }
+
+        self.accumulate_resolved(&resolved, exclude_ids, allowed)
+            .into_results(4, limit, &self.inner.docs, dedup_sections)
+    }
 
+    /// Sum the postings of each resolved query term, scaled by its matches'
+    /// multipliers, skipping `exclude_ids` and documents the filter rejects.
+    pub(super) fn accumulate_resolved(
+        &self,
+        resolved: &[ResolvedTerm],
+        exclude_ids: &HashSet<usize>,
+        allowed: Option<&[bool]>,
+    ) -> MultiTermAccumulator {
+        let mut acc = MultiTermAccumulator::new(resolved.len());
+
+        for (term_idx, term) in resolved.iter().enumerate() {
+            for &(vocab_idx, penalty) in &term.matches {
                 if let Some(postings) = self.inner.postings.get(vocab_idx) {
-                    for entry in postings {
+                    for (entry_idx, entry) in postings.iter().enumerate() {
                         let doc_id = entry.doc_id as usize;
-                        if exclude_ids.contains(&doc_id) || self.inner.docs.get(doc_id).is_none() {
+                        if exclude_ids.contains(&doc_id) || !self.admits(doc_id, allowed) {
                             continue;
                         }
 
                         let match_type = MatchType::from_heading_level(entry.heading_level);
 
-                        // Apply T3 penalty: score * (1 - edit_dist / max_dist)
-                        // Closer matches (lower edit distance) get higher scores
-                        let penalty = 1.0 / (1.0 + distance as f64);
-                        let penalized_score = entry.score as f64 * penalty;
-
                         acc.add_match(
                             term_idx,
                             doc_id,
                             entry.section_idx,
                             match_type,
-                            penalized_score,
+                            entry.score as f64 * penalty,
                             vocab_idx as u32,
+                            self.position_of(vocab_idx, entry_idx),
                         );
                     }
                 }
             }
         }
 
-        acc.into_results(3, limit, &self.inner.docs, dedup_sections)
+        acc
     }
 
-    /// Single-term T3 search optimized for single fuzzy query.
+    /// Resolve a single query term against the vocabulary at its best tier.
     ///
-    /// Uses pre-computed scores with T3 penalty: score * (1 - edit_dist / max_dist)
-    #[inline]
-    fn search_tier3_single_term(
-        &self,
-        term: &str,
-        exclude_ids: &HashSet<usize>,
-        limit: usize,
-    ) -> Vec<SearchResult> {
-        let mut doc_scores: HashMap<usize, f64> = HashMap::new();
-        let mut doc_section_idxs: HashMap<usize, u32> = HashMap::new();
-        let mut doc_match_types: HashMap<usize, MatchType> = HashMap::new();
-        let mut doc_matched_terms: HashMap<usize, u32> = HashMap::new();
+    /// Tries exact → prefix → fuzzy and stops at the first tier with matches.
+    /// Each match carries the score multiplier for its tier:
+    /// - T1 (exact): 1.0
+    /// - T2 (prefix): query.len / term.len
+    /// - T3 (fuzzy): 1 / (1 + edit_distance), distance > 0 only
+    ///
+    /// Returns `None` if the term matches nothing at any tier.
+    pub fn resolve_term(&self, term: &str) -> Option<ResolvedTerm> {
+        let budget = self.edit_budget(&SearchOptions::default());
+        self.resolve_term_within(term, budget.for_term(term))
+    }
 
-        // Maximum edit distance for T3 fuzzy search
-        const MAX_EDIT_DISTANCE: u8 = 2;
+    /// Like [`TierSearcher::resolve_term`], with fuzzy matches up to
+    /// `max_distance` edits.
+    pub fn resolve_term_within(&self, term: &str, max_distance: u8) -> Option<ResolvedTerm> {
+        let budget = EditBudget {
+            max_distance,
+            fuzziness: Fuzziness::Uniform,
+            last_term_prefix: false,
+        };
+        self.resolve_part(term, budget, false)
+    }
 
-        let fuzzy_matches = fuzzy_search_vocabulary(
-            &self.inner.vocabulary,
-            self.inner.lev_dfa.as_ref(),
-            term,
-            MAX_EDIT_DISTANCE,
-        );
-
-        for FuzzyMatch {
-            term_idx: vocab_idx,
-            distance,
-        } in fuzzy_matches
-        {
-            // Skip exact matches (distance 0) - those are T1's responsibility
-            if distance == 0 {
-                continue;
+    /// Resolve one query term within `budget`. A term still being `typing`
+    /// also fuzzy-matches vocabulary prefixes at Tier 3.
+    fn resolve_part(&self, term: &str, budget: EditBudget, typing: bool) -> Option<ResolvedTerm> {
+        // Tier 1: exact vocabulary hit
+        if self.inner.inverted_index.contains_key(term) {
+            if let Some(vocab_idx) = self.inner.vocabulary.iter().position(|t| t == term) {
+                return Some(ResolvedTerm {
+                    tier: 1,
+                    matches: vec![(vocab_idx, 1.0)],
+                });
             }
+        }
+
+        // Tier 2: prefix expansion via vocabulary suffix array
+        let prefix_matches: Vec<(usize, f64)> =
+            prefix_search_vocabulary(&self.inner.suffix_array, &self.inner.vocabulary, term)
+                .into_iter()
+                .map(|vocab_idx| {
+                    let term_len = self
+                        .inner
+                        .vocabulary
+                        .get(vocab_idx)
+                        .map(|t| t.len())
+                        .unwrap_or(1);
+                    (vocab_idx, term.len() as f64 / term_len.max(1) as f64)
+                })
+                .collect();
+        if !prefix_matches.is_empty() {
+            return Some(ResolvedTerm {
+                tier: 2,
+                matches: prefix_matches,
+            });
+        }
+
+        // Tier 3: fuzzy via Levenshtein DFA (distance 0 would have been caught above)
+        let fuzzy_matches = self.tier3_candidates(term, budget, typing);
+        if !fuzzy_matches.is_empty() {
+            return Some(ResolvedTerm {
+                tier: 3,
+                matches: fuzzy_matches,
+            });
+        }
+
+        None
+    }
+
+    /// Tier 3 vocabulary matches for one term, with their score multipliers.
+    ///
+    /// Whole-word fuzzy matches get 1 / (1 + edit_distance). A term still
+    /// being `typing` also matches terms whose prefix is within the budget,
+    /// scaled like Tier 2 by query.len / term.len, so "optimz" reaches
+    /// "optimization" without outranking a finished word. Distance 0 is left
+    /// to Tiers 1 and 2.
+    fn tier3_candidates(&self, term: &str, budget: EditBudget, typing: bool) -> Vec<(usize, f64)> {
+        let vocabulary = &self.inner.vocabulary;
+        let mut candidates: Vec<(usize, f64)> = self
+            .fuzzy_terms(term, budget.max_distance, budget.fuzziness, false)
+            .into_iter()
+            .filter(|m| m.distance > 0)
+            .map(|m| (m.term_idx, 1.0 / (1.0 + m.distance as f64)))
+            .collect();
 
+        if typing {
+            let whole: HashSet<usize> = candidates.iter().map(|&(idx, _)| idx).collect();
+            candidates.extend(
+                self.fuzzy_terms(term, budget.max_distance, budget.fuzziness, true)
+                    .into_iter()
+                    .filter(|m| m.distance > 0 && !whole.contains(&m.term_idx))
+                    .map(|m| {
+                        let term_len = vocabulary[m.term_idx].len().max(1);
+                        let coverage = (term.len() as f64 / term_len as f64).min(1.0);
+                        (m.term_idx, coverage / (1.0 + m.distance as f64))
+                    }),
+            );
+        }
+        candidates
+    }
+
+    /// Single-term T3 search optimized for single fuzzy query.
+    ///
+    /// Uses pre-computed scores with T3 penalty: score * (1 - edit_dist / max_dist)
+    #[inline]
+    fn search_tier3_single_term(
+        &self,
+        term: &str,
+        exclude_ids: &HashSet<usize>,
+        limit: usize,
+        allowed: Option<&[bool]>,
+        budget: EditBudget,
+    ) -> Vec<SearchResult> {
+        let mut doc_scores: HashMap<usize, f64> = HashMap::new();
+        let mut doc_section_idxs: HashMap<usize, u32> = HashMap::new();
+        let mut doc_match_types: HashMap<usize, MatchType> = HashMap::new();
+        let mut doc_matched_terms: HashMap<usize, u32> = HashMap::new();
+
+        // Exact matches (distance 0) are T1's responsibility
+        for (vocab_idx, penalty) in self.tier3_candidates(term, budget, budget.last_term_prefix) {
             if let Some(postings) = self.inner.postings.get(vocab_idx) {
                 for entry in postings {
                     let doc_id = entry.doc_id as usize;
-                    if exclude_ids.contains(&doc_id) || self.inner.docs.get(doc_id).is_none() {
+                    if exclude_ids.contains(&doc_id) || !self.admits(doc_id, allowed) {
                         continue;
                     }
 
                     let match_type = MatchType::from_heading_level(entry.heading_level);
-
-                    // Apply T3 penalty: score * (1 - edit_dist / max_dist)
-                    let penalty = 1.0 / (1.0 + distance as f64);
                     let final_score = entry.score as f64 * penalty;
 
                     // Keep best score and match_type per doc
@@ -1085,10 +1798,21 @@ impl TierSearcher {
             return ui_rx;
         }
 
+        // Boolean and code queries have no per-tier split: evaluate once and
+        // emit in order
+        if self.evaluates_in_one_pass(query) {
+            let results = self.search(query, limit);
+            for result in &results {
+                let _ = ui_tx.send(UIMessage::Result(result.clone()));
+            }
+            let _ = ui_tx.send(UIMessage::Finished(results));
+            return ui_rx;
+        }
+
         // Channel from tier workers → dedup worker
         let (raw_tx, raw_rx) = channel::<RawResult>();
 
-        let query_lower = to_lowercase_ascii_simd(query);
+        let query_lower = fold(query);
 
         // Spawn T1 worker
         let tx1 = raw_tx.clone();
@@ -1131,41 +1855,15 @@ impl TierSearcher {
     /// Uses pre-computed scores from posting entries.
     #[cfg(feature = "rayon")]
     fn stream_tier1(&self, query: &str, limit: usize, tx: Sender<RawResult>) {
-        // Find vocabulary index for this term
-        let vocab_idx = self
-            .inner
-            .vocabulary
-            .iter()
-            .position(|t| t == query)
-            .map(|i| i as u32);
-
-        if let Some(postings) = self.inner.inverted_index.get(query) {
-            let mut count = 0;
-            for entry in postings {
-                if count >= limit {
-                    break;
-                }
-                if self.inner.docs.get(entry.doc_id as usize).is_none() {
-                    continue;
-                }
-                let result = SearchResult {
-                    doc_id: entry.doc_id as usize,
-                    score: entry.score as f64,
-                    section_idx: entry.section_idx,
-                    tier: 1,
-                    match_type: MatchType::from_heading_level(entry.heading_level),
-                    matched_term: vocab_idx,
-                };
-                if tx
-                    .send(RawResult {
-                        result,
-                        tier_done: None,
-                    })
-                    .is_err()
-                {
-                    return;
-                }
-                count += 1;
+        for result in self.tier1_exact(query, limit, None, &SearchOptions::default()) {
+            if tx
+                .send(RawResult {
+                    result,
+                    tier_done: None,
+                })
+                .is_err()
+            {
+                return;
             }
         }
         // Signal tier completion
@@ -1257,8 +1955,7 @@ impl TierSearcher {
     /// Uses pre-computed scores with T3 penalty: score * (1 - edit_dist / max_dist)
     #[cfg(feature = "rayon")]
     fn stream_tier3(&self, query: &str, limit: usize, tx: Sender<RawResult>) {
-        // Maximum edit distance for T3 fuzzy search
-        const MAX_EDIT_DISTANCE: u8 = 2;
+        let max_distance = self.edit_budget(&SearchOptions::default()).for_term(query);
 
         let dfa = match &self.inner.lev_dfa {
             Some(dfa) => dfa,
@@ -1279,25 +1976,34 @@ impl TierSearcher {
             }
         };
 
-        let matcher = QueryMatcher::new(dfa, query);
+        // Only include fuzzy matches (distance > 0), not exact (T1's job)
+        let fuzzy_matches: Vec<FuzzyMatch> = match &self.inner.vocab_trie {
+            Some(trie) => trie
+                .fuzzy_matches(&self.inner.vocabulary, query, max_distance, false)
+                .into_iter()
+                .filter(|&(_, distance)| distance > 0)
+                .map(|(term_idx, distance)| FuzzyMatch { term_idx, distance })
+                .collect(),
+            None => {
+                let matcher = QueryMatcher::with_distance(dfa, query, max_distance);
 
-        // Parallel vocabulary scan using rayon
-        let fuzzy_matches: Vec<FuzzyMatch> = self
-            .inner
-            .vocabulary
-            .par_iter()
-            .enumerate()
-            .filter_map(|(term_idx, term)| {
-                matcher.matches(term).and_then(|distance| {
-                    // Only include fuzzy matches (distance > 0), not exact (T1's job)
-                    if distance <= MAX_EDIT_DISTANCE && distance > 0 {
-                        Some(FuzzyMatch { term_idx, distance })
-                    } else {
-                        None
-                    }
-                })
-            })
-            .collect();
+                // Parallel vocabulary scan using rayon
+                self.inner
+                    .vocabulary
+                    .par_iter()
+                    .enumerate()
+                    .filter_map(|(term_idx, term)| {
+                        matcher.matches(term).and_then(|distance| {
+                            if distance <= max_distance && distance > 0 {
+                                Some(FuzzyMatch { term_idx, distance })
+                            } else {
+                                None
+                            }
+                        })
+                    })
+                    .collect()
+            }
+        };
 
         let mut count = 0;
         for FuzzyMatch { term_idx, distance } in fuzzy_matches {
@@ -1510,6 +2216,30 @@ pub fn prefix_search_vocabulary(
     suffix_array: &[(u32, u32)],
     vocabulary: &[String],
     prefix: &str,
+) -> Vec<usize> {
+    suffix_search_vocabulary(suffix_array, vocabulary, prefix, true)
+}
+
+/// Infix search over the vocabulary suffix array: terms containing
+/// `fragment` anywhere, not just at the start ("auth" finds "oauth").
+///
+/// Same binary search as [`prefix_search_vocabulary`], keeping matches at
+/// every offset instead of only offset 0.
+pub fn infix_search_vocabulary(
+    suffix_array: &[(u32, u32)],
+    vocabulary: &[String],
+    fragment: &str,
+) -> Vec<usize> {
+    suffix_search_vocabulary(suffix_array, vocabulary, fragment, false)
+}
+
+/// Terms with a suffix starting with `prefix`, optionally only the suffix
+/// at offset 0 (the whole term).
+fn suffix_search_vocabulary(
+    suffix_array: &[(u32, u32)],
+    vocabulary: &[String],
+    prefix: &str,
+    word_start_only: bool,
 ) -> Vec<usize> {
     if suffix_array.is_empty() || prefix.is_empty() {
         return Vec::new();
@@ -1573,8 +2303,8 @@ pub fn prefix_search_vocabulary(
 
         // Use SIMD-accelerated prefix check for faster matching
         if starts_with_simd(suffix.as_bytes(), prefix.as_bytes()) {
-            // Only count if prefix matches at word start (offset == 0)
-            if offset == 0 {
+            // Prefix search only counts matches at word start (offset == 0)
+            if offset == 0 || !word_start_only {
                 matches.insert(term_idx);
             }
         } else {
@@ -1598,6 +2328,7 @@ pub fn prefix_search_vocabulary(
 /// * `lev_dfa` - Precomputed Levenshtein DFA (if None, returns empty results)
 /// * `query` - Search query (assumed lowercase)
 /// * `max_distance` - Maximum edit distance (typically 2)
+/// * `fuzziness` - How much of `max_distance` the query gets for its length
 ///
 /// # Returns
 /// Vector of FuzzyMatch results (term_idx, distance), sorted by distance ascending.
@@ -1606,6 +2337,7 @@ pub fn fuzzy_search_vocabulary(
     lev_dfa: Option<&ParametricDFA>,
     query: &str,
     max_distance: u8,
+    fuzziness: Fuzziness,
 ) -> Vec<FuzzyMatch> {
     // Need precomputed DFA for fuzzy search
     let dfa = match lev_dfa {
@@ -1617,8 +2349,10 @@ pub fn fuzzy_search_vocabulary(
         return Vec::new();
     }
 
+    let max_distance = fuzziness.distance_for(query, max_distance);
+
     // Build query-specific matcher from precomputed DFA tables (~1μs)
-    let matcher = QueryMatcher::new(dfa, query);
+    let matcher = QueryMatcher::with_distance(dfa, query, max_distance);
 
     let mut matches = Vec::new();
 
@@ -1638,15 +2372,55 @@ pub fn fuzzy_search_vocabulary(
     matches
 }
 
+/// Fuzzy prefix search: vocabulary terms with a prefix within edit distance.
+///
+/// The search-as-you-type counterpart of [`fuzzy_search_vocabulary`]: the
+/// query is taken as the start of a word, so "optimz" matches "optimization"
+/// (one edit from "optim"). Distance 0 means the query is an exact prefix.
+///
+/// # Returns
+/// Vector of FuzzyMatch results (term_idx, distance to the closest prefix),
+/// sorted by distance ascending.
+pub fn fuzzy_prefix_search_vocabulary(
+    vocabulary: &[String],
+    lev_dfa: Option<&ParametricDFA>,
+    query: &str,
+    max_distance: u8,
+    fuzziness: Fuzziness,
+) -> Vec<FuzzyMatch> {
+    let Some(dfa) = lev_dfa else {
+        return Vec::new();
+    };
+
+    let max_distance = fuzziness.distance_for(query, max_distance);
+    let matcher = QueryMatcher::with_distance(dfa, query, max_distance);
+
+    let mut matches: Vec<FuzzyMatch> = vocabulary
+        .iter()
+        .enumerate()
+        .filter_map(|(term_idx, term)| {
+            matcher
+                .matches_prefix(term)
+                .map(|distance| FuzzyMatch { distance, term_idx })
+        })
+        .collect();
+    matches.sort_by_key(|m| m.distance);
+    matches
+}
+
 // ============================================================================
 // MULTI-TERM QUERY TESTS
 // ============================================================================
 
 #[cfg(test)]
-mod multi_term_tests {
+pub(super) mod multi_term_tests {
     use super::*;
 
     fn create_test_searcher() -> TierSearcher {
+        build_test_searcher(None)
+    }
+
+    fn build_test_searcher(lev_dfa: Option<ParametricDFA>) -> TierSearcher {
         // Create a simple index with known documents for testing multi-term queries
         let docs = vec![
             SearchDoc {
@@ -1806,14 +2580,24 @@ mod multi_term_tests {
             .map(|term| inverted_index.get(term).cloned().unwrap_or_default())
             .collect();
 
+        let vocab_trie = lev_dfa.as_ref().map(|_| VocabTrie::build(&vocabulary));
+        let phonetic = Some(PhoneticIndex::build(&vocabulary));
         let inner = TierSearcherInner {
             docs,
             vocabulary,
             inverted_index,
             suffix_array,
             postings,
+            positions: vec![],
             section_table: vec![],
-            lev_dfa: None,
+            lev_dfa,
+            vocab_trie,
+            phonetic,
+            stems: None,
+            synonyms: None,
+            tokenizer: Tokenizer::Text,
+            transliterated: false,
+            text: None,
         };
 
         TierSearcher {
@@ -1821,6 +2605,11 @@ mod multi_term_tests {
         }
     }
 
+    /// Same index as `create_test_searcher`, with a Levenshtein DFA for T3.
+    fn create_test_searcher_with_dfa() -> TierSearcher {
+        build_test_searcher(Some(ParametricDFA::build(true)))
+    }
+
     #[test]
     fn test_t1_multiterm_score_summing() {
         let searcher = create_test_searcher();
@@ -1985,4 +2774,664 @@ mod multi_term_tests {
             "T3 without DFA should return empty results"
         );
     }
+
+    #[test]
+    fn test_t3_mixed_exact_and_typo_terms() {
+        let searcher = create_test_searcher_with_dfa();
+        let exclude = HashSet::new();
+
+        // "rust" is exact, "optimizaton" is one edit from "optimization"
+        let results = searcher.search_tier3_fuzzy("rust optimizaton", &exclude, 10);
+        assert_eq!(results.len(), 1, "Only doc2 has both terms");
+        assert_eq!(results[0].doc_id, 2);
+        assert_eq!(results[0].tier, 3, "Worst tier used was fuzzy");
+        assert!(results[0].matched_term.is_some());
+    }
+
+    #[test]
+    fn test_search_mixed_exact_and_typo_terms() {
+        let searcher = create_test_searcher_with_dfa();
+
+        let results = searcher.search("rust optimizaton", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].doc_id, 2);
+        assert_eq!(results[0].tier, 3);
+    }
+
+    #[test]
+    fn test_t3_mixed_terms_keep_and_semantics() {
+        let searcher = create_test_searcher_with_dfa();
+        let exclude = HashSet::new();
+
+        // No doc has both "python" and "optimization"
+        let results = searcher.search_tier3_fuzzy("python optimizaton", &exclude, 10);
+        assert!(results.is_empty(), "AND must hold across tiers");
+
+        // A term that resolves nowhere empties the result
+        let results = searcher.search_tier3_fuzzy("rust zzzzzzzz", &exclude, 10);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_as_you_type_fuzzy_prefix() {
+        let searcher = create_test_searcher_with_dfa();
+        let typing = SearchOptions::default().as_you_type();
+        let ids = |query: &str, options: SearchOptions| -> Vec<usize> {
+            let mut ids: Vec<usize> = searcher
+                .search_with_options(query, 10, options)
+                .iter()
+                .map(|r| r.doc_id)
+                .collect();
+            ids.sort_unstable();
+            ids
+        };
+
+        // Half-typed misspelling: too far from any whole word
+        assert!(ids("optimz", SearchOptions::default()).is_empty());
+        assert_eq!(ids("optimz", typing.clone()), vec![1, 2]);
+        let results = searcher.search_with_options("optimz", 10, typing.clone());
+        assert!(results.iter().all(|r| r.tier == 3));
+
+        // Only the last term is still being typed
+        assert_eq!(ids("rust optimz", typing.clone()), vec![2]);
+        assert!(ids("optimz rust", typing.clone()).is_empty());
+
+        // Exact prefixes stay in Tier 2
+        let results = searcher.search_with_options("optim", 10, typing);
+        assert!(results.iter().all(|r| r.tier == 2));
+    }
+
+    #[test]
+    fn test_vocab_trie_matches_linear_scan() {
+        let searcher = create_test_searcher_with_dfa();
+        assert!(searcher.inner.vocab_trie.is_some());
+        let vocabulary = searcher.vocabulary();
+        let dfa = searcher.lev_dfa();
+
+        for query in [
+            "rust",
+            "ruts",
+            "pyhton",
+            "optimz",
+            "programing",
+            "tpyes",
+            "x",
+        ] {
+            for k in 0..=MAX_K {
+                for fuzziness in [Fuzziness::Uniform, Fuzziness::ADAPTIVE] {
+                    assert_eq!(
+                        searcher.fuzzy_terms(query, k, fuzziness, false),
+                        fuzzy_search_vocabulary(vocabulary, dfa, query, k, fuzziness),
+                        "{:?} k={}",
+                        query,
+                        k
+                    );
+                    assert_eq!(
+                        searcher.fuzzy_terms(query, k, fuzziness, true),
+                        fuzzy_prefix_search_vocabulary(vocabulary, dfa, query, k, fuzziness),
+                        "{:?} k={} (prefix)",
+                        query,
+                        k
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_phonetic_tier() {
+        let searcher = create_test_searcher_with_dfa();
+        let phonetic = SearchOptions::default().with_phonetic();
+        let search = |searcher: &TierSearcher, query: &str, options: SearchOptions| {
+            searcher.search_with_options(query, 10, options)
+        };
+
+        // Three edits from "python": past the fuzzy budget, but it sounds the same
+        assert!(search(&searcher, "paithonn", SearchOptions::default()).is_empty());
+        let results = search(&searcher, "paithonn", phonetic.clone());
+        assert_eq!(results.len(), 1);
+        assert_eq!((results[0].doc_id, results[0].tier), (3, 4));
+
+        // Other terms still match at their own tier
+        let results = search(&searcher, "programming paithonn", phonetic.clone());
+        assert_eq!(results.len(), 1);
+        assert_eq!((results[0].doc_id, results[0].tier), (3, 4));
+
+        // Typos the fuzzy tier finds stay there
+        let results = search(&searcher, "pyhton", phonetic.clone());
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.tier == 3));
+
+        assert!(search(&searcher, "zzzzzzzz", phonetic.clone()).is_empty());
+        // Indexes built without phonetic keys ignore the option
+        assert!(search(&create_filter_searcher(), "paithonn", phonetic).is_empty());
+    }
+
+    #[test]
+    fn test_fuzziness_by_term_length() {
+        let searcher = create_test_searcher_with_dfa();
+        let ids = |query: &str, options: SearchOptions| -> Vec<usize> {
+            let mut ids: Vec<usize> = searcher
+                .search_with_options(query, 10, options)
+                .iter()
+                .map(|r| r.doc_id)
+                .collect();
+            ids.sort_unstable();
+            ids
+        };
+
+        // Three letters get no typos by default, four get one
+        assert!(ids("rut", SearchOptions::default()).is_empty());
+        assert_eq!(ids("rusr", SearchOptions::default()), vec![0, 2]);
+        assert!(ids("rsr", SearchOptions::default()).is_empty());
+
+        // Uniform restores the full budget for short terms
+        let uniform = SearchOptions::default().with_fuzziness(Fuzziness::Uniform);
+        assert_eq!(ids("rut", uniform.clone()), vec![0, 2]);
+
+        // A custom table, still capped by max_edit_distance
+        let lenient = SearchOptions::default().with_fuzziness(Fuzziness::ByLength([1, 3, 5]));
+        assert_eq!(ids("rsr", lenient.clone()), vec![0, 2]);
+        assert!(ids("rsr", lenient.with_max_edit_distance(1)).is_empty());
+
+        // Each term of a multi-term query is sized on its own
+        assert!(ids("rut optimizaton", SearchOptions::default()).is_empty());
+        assert_eq!(ids("rut optimizaton", uniform), vec![2]);
+    }
+
+    #[test]
+    fn test_t3_skips_queries_resolved_without_fuzzy() {
+        let searcher = create_test_searcher_with_dfa();
+        let exclude = HashSet::new();
+
+        // Exact + prefix only: belongs to T1/T2, not T3
+        let results = searcher.search_tier3_fuzzy("rust optim", &exclude, 10);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_term_picks_best_tier() {
+        let searcher = create_test_searcher_with_dfa();
+
+        assert_eq!(searcher.resolve_term("rust").map(|r| r.tier), Some(1));
+        assert_eq!(searcher.resolve_term("optim").map(|r| r.tier), Some(2));
+        assert_eq!(searcher.resolve_term("pythn").map(|r| r.tier), Some(3));
+        assert!(searcher.resolve_term("zzzzzzzz").is_none());
+    }
+
+    // ------------------------------------------------------------------------
+    // Boolean queries
+    // ------------------------------------------------------------------------
+
+    fn doc_ids(results: &[SearchResult]) -> Vec<usize> {
+        let mut ids: Vec<usize> = results.iter().map(|r| r.doc_id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_boolean_exclusion() {
+        let searcher = create_test_searcher();
+
+        let results = searcher.search("rust -optimization", 10);
+        assert_eq!(doc_ids(&results), vec![0]);
+        assert_eq!(results[0].tier, 1, "Exclusions don't lower the tier");
+
+        let results = searcher.search("rust NOT optimization", 10);
+        assert_eq!(doc_ids(&results), vec![0]);
+    }
+
+    #[test]
+    fn test_boolean_exclusion_is_exact() {
+        let searcher = create_test_searcher_with_dfa();
+
+        // "pyth" would be a prefix of "python", "pythn" a typo of it; only the
+        // word itself excludes
+        let results = searcher.search("programming -pyth", 10);
+        assert_eq!(doc_ids(&results), vec![0, 3]);
+        let results = searcher.search("programming -pythn", 10);
+        assert_eq!(doc_ids(&results), vec![0, 3]);
+        let results = searcher.search("programming -python", 10);
+        assert_eq!(doc_ids(&results), vec![0]);
+    }
+
+    #[test]
+    fn test_boolean_only_exclusions_is_empty() {
+        let searcher = create_test_searcher();
+        assert!(searcher.search("-rust", 10).is_empty());
+    }
+
+    #[test]
+    fn test_boolean_or() {
+        let searcher = create_test_searcher();
+
+        let results = searcher.search("python OR optimization", 10);
+        assert_eq!(doc_ids(&results), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_boolean_grouping() {
+        let searcher = create_test_searcher();
+
+        let results = searcher.search("(rust OR python) programming", 10);
+        assert_eq!(doc_ids(&results), vec![0, 3]);
+
+        let results = searcher.search("(rust OR python) -programming", 10);
+        assert_eq!(doc_ids(&results), vec![2]);
+    }
+
+    #[test]
+    fn test_boolean_quoted_disables_prefix() {
+        let searcher = create_test_searcher();
+
+        // Unquoted "optim" is a prefix match; quoted is exact-only
+        let results = searcher.search("optim", 10);
+        assert_eq!(doc_ids(&results), vec![1, 2]);
+        assert!(searcher.search("\"optim\"", 10).is_empty());
+
+        let results = searcher.search("\"optimization\"", 10);
+        assert_eq!(doc_ids(&results), vec![1, 2]);
+        assert!(results.iter().all(|r| r.tier == 1));
+    }
+
+    #[test]
+    fn test_boolean_quoted_disables_fuzzy() {
+        let searcher = create_test_searcher_with_dfa();
+
+        assert!(searcher.search("rust \"optimizaton\"", 10).is_empty());
+
+        // The unquoted typo still matches at T3 next to an exact quoted term
+        let results = searcher.search("\"rust\" optimizaton", 10);
+        assert_eq!(doc_ids(&results), vec![2]);
+        assert_eq!(results[0].tier, 3);
+        assert!(results[0].matched_term.is_some());
+    }
+
+    #[test]
+    fn test_boolean_prefix_inside_or() {
+        let searcher = create_test_searcher();
+
+        // "pyth" is a T2 prefix hit; doc2 has "rust" exactly
+        let results = searcher.search("pyth OR rust", 10);
+        assert_eq!(doc_ids(&results), vec![0, 2, 3]);
+        let doc3 = results.iter().find(|r| r.doc_id == 3).unwrap();
+        assert_eq!(doc3.tier, 2);
+    }
+
+    #[test]
+    fn test_boolean_respects_limit_and_section_dedup() {
+        let searcher = create_test_searcher();
+
+        let results = searcher.search("rust OR python OR optimization", 2);
+        assert_eq!(results.len(), 2);
+
+        let options = SearchOptions::without_section_dedup();
+        let results = searcher.search_with_options("rust -python", 10, options);
+        assert_eq!(doc_ids(&results), vec![0, 2]);
+    }
+
+    // ------------------------------------------------------------------------
+    // Tier 1 expansions
+    // ------------------------------------------------------------------------
+
+    fn tier_1_ids(results: &[SearchResult]) -> Vec<usize> {
+        let mut ids: Vec<usize> = results
+            .iter()
+            .filter(|r| r.tier == 1)
+            .map(|r| r.doc_id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[cfg(feature = "rayon")]
+    fn streamed(searcher: &TierSearcher, query: &str, limit: usize) -> Vec<SearchResult> {
+        // `Finished` leaves out what was already sent, so collect as it comes
+        searcher
+            .search_streaming(query, limit)
+            .iter()
+            .filter_map(|message| match message {
+                UIMessage::Result(result) => Some(result),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// One searcher per expansion: doc 0 matches `query` only through the
+    /// expansion, docs 1 and 2 as typed, and doc 2 also has `noise`.
+    fn expansion_searchers() -> Vec<(&'static str, TierSearcher, &'static str, &'static str)> {
+        use crate::index::language::Language;
+        use crate::search::phrase::tests::{
+            build_searcher, build_stemmed_searcher, build_synonym_searcher,
+        };
+
+        vec![
+            (
+                "split/join",
+                build_searcher(
+                    &["data base tuning", "database tuning", "database rust"],
+                    true,
+                ),
+                "database",
+                "rust",
+            ),
+            (
+                "stems",
+                build_stemmed_searcher(
+                    &["optimized builds", "optimizing queries", "optimizing rust"],
+                    &[Language::English],
+                ),
+                "optimizing",
+                "rust",
+            ),
+            (
+                "synonyms",
+                build_synonym_searcher(
+                    &["kubernetes cluster", "k8s cluster", "k8s docker"],
+                    &["k8s, kubernetes"],
+                    true,
+                ),
+                "k8s",
+                "docker",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_expansions_apply_at_every_entry_point() {
+        for (name, searcher, query, noise) in expansion_searchers() {
+            // What progressive search and the CLI's timed search run first
+            assert_eq!(
+                tier_1_ids(&searcher.search_tier1_exact(query, 10)),
+                [0, 1, 2],
+                "{}: tier 1 alone",
+                name
+            );
+
+            let excluded = format!("{} -{}", query, noise);
+            assert_eq!(
+                tier_1_ids(&searcher.search(&excluded, 10)),
+                [0, 1],
+                "{}: boolean term",
+                name
+            );
+            let either = format!("{} OR {}", query, noise);
+            assert_eq!(
+                tier_1_ids(&searcher.search(&either, 10)),
+                [0, 1, 2],
+                "{}: boolean OR",
+                name
+            );
+            // Quoted terms only match as typed
+            let quoted = format!("\"{}\" -{}", query, noise);
+            assert_eq!(
+                tier_1_ids(&searcher.search(&quoted, 10)),
+                [1],
+                "{}: quoted term",
+                name
+            );
+
+            #[cfg(feature = "rayon")]
+            assert_eq!(
+                tier_1_ids(&streamed(&searcher, query, 10)),
+                [0, 1, 2],
+                "{}: streaming",
+                name
+            );
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Field operators
+    // ------------------------------------------------------------------------
+
+    /// "install" as a title (doc 0), a subsection heading (doc 1), and body
+    /// text (doc 2); "installer" only in body text (doc 3).
+    fn create_field_searcher() -> TierSearcher {
+        crate::search::phrase::tests::build_searcher_with_levels(
+            &[
+                ("install guide", 0),
+                ("install steps", 3),
+                ("run install", 5),
+                ("installer notes", 5),
+            ],
+            true,
+        )
+    }
+
+    #[test]
+    fn test_field_exact() {
+        let searcher = create_field_searcher();
+
+        assert_eq!(doc_ids(&searcher.search("title:install", 10)), vec![0]);
+        assert_eq!(doc_ids(&searcher.search("heading:install", 10)), vec![1]);
+        assert_eq!(doc_ids(&searcher.search("content:install", 10)), vec![2, 3]);
+        assert_eq!(doc_ids(&searcher.search("install", 10)), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_field_prefix() {
+        let searcher = create_field_searcher();
+
+        let results = searcher.search("content:instal", 10);
+        assert_eq!(doc_ids(&results), vec![2, 3]);
+        assert!(results.iter().all(|r| r.tier == 2));
+        assert!(results.iter().all(|r| r.match_type == MatchType::Content));
+
+        // The title-only term has no body text to fall back to
+        assert!(searcher.search("content:guide", 10).is_empty());
+    }
+
+    #[test]
+    fn test_field_fuzzy() {
+        let searcher = create_test_searcher_with_dfa();
+
+        let results = searcher.search("title:pythn", 10);
+        assert_eq!(doc_ids(&results), vec![3]);
+        assert_eq!(results[0].tier, 3);
+        assert!(searcher.search("content:pythn", 10).is_empty());
+        assert!(searcher.search("heading:pythn", 10).is_empty());
+    }
+
+    #[test]
+    fn test_field_scopes_phrases_and_groups() {
+        let searcher = create_field_searcher();
+
+        assert_eq!(
+            doc_ids(&searcher.search("heading:\"install steps\"", 10)),
+            vec![1]
+        );
+        assert!(searcher.search("content:\"install steps\"", 10).is_empty());
+        assert_eq!(
+            doc_ids(&searcher.search("title:(steps OR guide)", 10)),
+            vec![0]
+        );
+        assert_eq!(
+            doc_ids(&searcher.search("install -title:install", 10)),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_field_search_option() {
+        let searcher = create_field_searcher();
+
+        let options = SearchOptions::default().in_field(FieldType::Heading);
+        let results = searcher.search_with_options("install", 10, options.clone());
+        assert_eq!(doc_ids(&results), vec![1]);
+
+        // An operator in the query overrides the option
+        let results = searcher.search_with_options("title:install", 10, options);
+        assert_eq!(doc_ids(&results), vec![0]);
+    }
+
+    // ------------------------------------------------------------------------
+    // Metadata filters
+    // ------------------------------------------------------------------------
+
+    /// `create_test_searcher_with_dfa` with varied metadata per doc.
+    pub(in crate::search) fn create_filter_searcher() -> TierSearcher {
+        let base = create_test_searcher_with_dfa();
+        let meta = [
+            ("tutorials", "ada", &["rust"][..], "/guide/rust", "post"),
+            ("programming", "bob", &[][..], "/api/optim", "post"),
+            (
+                "tutorials",
+                "bob",
+                &["rust", "perf"][..],
+                "/api/rust-opt",
+                "post",
+            ),
+            (
+                "programming",
+                "cy",
+                &["python"][..],
+                "/guide/python",
+                "page",
+            ),
+        ];
+        let docs = base
+            .docs()
+            .iter()
+            .zip(meta)
+            .map(|(doc, (category, author, tags, href, kind))| SearchDoc {
+                category: Some(category.to_string()),
+                author: Some(author.to_string()),
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                href: href.to_string(),
+                kind: kind.to_string(),
+                ..doc.clone()
+            })
+            .collect();
+
+        TierSearcher {
+            inner: Arc::new(TierSearcherInner {
+                docs,
+                section_table: vec![],
+                vocabulary: base.vocabulary().to_vec(),
+                suffix_array: base.suffix_array().to_vec(),
+                postings: base.postings().to_vec(),
+                positions: vec![],
+                inverted_index: base.inverted_index().clone(),
+                lev_dfa: Some(ParametricDFA::build(true)),
+                vocab_trie: None,
+                phonetic: None,
+                stems: None,
+                synonyms: None,
+                tokenizer: Tokenizer::Text,
+                transliterated: false,
+                text: None,
+            }),
+        }
+    }
+
+    fn filtered(
+        searcher: &TierSearcher,
+        query: &str,
+        limit: usize,
+        filter: SearchFilter,
+    ) -> Vec<usize> {
+        let options = SearchOptions::default().with_filter(filter);
+        doc_ids(&searcher.search_with_options(query, limit, options))
+    }
+
+    #[test]
+    fn test_filter_fills_page_before_limit() {
+        let searcher = create_filter_searcher();
+        let by_bob = SearchFilter {
+            author: Some("bob".to_string()),
+            ..Default::default()
+        };
+
+        // Doc 0 comes first in the "rust" postings, so T1's early exit at
+        // limit 1 would stop on it if the filter ran afterwards
+        let options = SearchOptions::default().with_filter(by_bob);
+        let results = searcher.search_with_options("rust", 1, options);
+        assert_eq!(doc_ids(&results), vec![2]);
+        assert_eq!(results[0].tier, 1);
+    }
+
+    #[test]
+    fn test_filter_each_tier() {
+        let searcher = create_filter_searcher();
+
+        let api = SearchFilter {
+            href_prefix: Some("/api/".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(filtered(&searcher, "rust guide", 10, api.clone()), vec![2]);
+        assert_eq!(filtered(&searcher, "optim", 10, api), vec![1, 2]);
+
+        let tutorials = SearchFilter {
+            categories: vec!["tutorials".to_string(), "news".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(filtered(&searcher, "optim", 10, tutorials), vec![2]);
+
+        let pages = SearchFilter {
+            kinds: vec!["page".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(filtered(&searcher, "pythn", 10, pages), vec![3]);
+        let posts = SearchFilter {
+            kinds: vec!["post".to_string()],
+            ..Default::default()
+        };
+        assert!(filtered(&searcher, "pythn", 10, posts).is_empty());
+    }
+
+    #[test]
+    fn test_filter_tags_and_boolean_queries() {
+        let searcher = create_filter_searcher();
+
+        let rust_perf = SearchFilter {
+            tags: vec!["rust".to_string(), "perf".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(filtered(&searcher, "rust", 10, rust_perf), vec![2]);
+
+        let programming = SearchFilter {
+            categories: vec!["programming".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            filtered(&searcher, "rust OR python", 10, programming.clone()),
+            vec![3]
+        );
+        assert_eq!(
+            filtered(&searcher, "programming -python", 10, programming),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn test_query_folds_like_index() {
+        use crate::search::phrase::tests::build_searcher;
+        use crate::util::normalize::normalize;
+
+        // Index words go through the same analyzer the indexer uses
+        let texts: Vec<String> = ["Café culture", "Ñandú sightings", "Straße names"]
+            .iter()
+            .map(|t| normalize(t))
+            .collect();
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        let searcher = build_searcher(&texts, true);
+
+        let ids = |query: &str| -> Vec<usize> {
+            searcher
+                .search(query, 10)
+                .iter()
+                .map(|r| r.doc_id)
+                .collect()
+        };
+        for query in ["cafe", "Café", "CAFÉ", "cafe\u{0301}", "\"café culture\""] {
+            assert_eq!(ids(query), vec![0], "query {:?}", query);
+        }
+        assert_eq!(ids("nandu"), vec![1]);
+        assert_eq!(ids("Ñandú"), vec![1]);
+        assert_eq!(ids("STRASSE"), vec![2]);
+        assert_eq!(ids("straße"), vec![2]);
+        // Prefix tier sees folded input too
+        assert_eq!(ids("Straß"), vec![2]);
+    }
 }
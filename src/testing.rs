@@ -7,6 +7,17 @@
 
 use crate::types::{FieldBoundary, FieldType, SearchDoc};
 
+#[cfg(test)]
+use crate::binary::{encode_docs_binary, BinaryLayer, DocMetaInput, LoadedLayer, PostingEntry};
+#[cfg(test)]
+use crate::index::language::Language;
+#[cfg(test)]
+use crate::index::{build_vocab_suffix_array_sais, is_stop_word_in};
+#[cfg(test)]
+use crate::search::tiered::TierSearcher;
+#[cfg(test)]
+use std::collections::HashMap;
+
 /// Create a simple test document with default fields.
 ///
 /// This is the canonical implementation used across all tests.
@@ -98,6 +109,94 @@ pub fn make_content_boundary(
     }
 }
 
+/// Build a binary layer from texts, one content section per doc, and return
+/// it with its vocabulary. Each text comes with its heading level (0 = title).
+///
+/// `words` cuts a text into `(word, start, end)` spans, like the indexer's
+/// tokenizers. A word's position is the rank of its start, so words sharing
+/// a start share a position. Stop words are skipped (in the doc's language,
+/// or every language for docs without an entry in `languages`).
+#[cfg(test)]
+pub(crate) fn build_layer(
+    texts: &[(&str, u8)],
+    with_positions: bool,
+    languages: &[Option<Language>],
+    words: impl Fn(&str) -> Vec<(String, usize, usize)>,
+) -> (BinaryLayer, Vec<String>) {
+    let mut terms: HashMap<String, Vec<(PostingEntry, u32)>> = HashMap::new();
+    for (doc_id, &(text, heading_level)) in texts.iter().enumerate() {
+        let language = languages.get(doc_id).copied().flatten();
+        let words = words(text);
+        let mut starts: Vec<usize> = words.iter().map(|&(_, start, _)| start).collect();
+        starts.sort_unstable();
+        starts.dedup();
+        for (word, start, _) in words {
+            if is_stop_word_in(&word, language) {
+                continue;
+            }
+            let pos = starts.binary_search(&start).expect("a word start");
+            terms.entry(word).or_default().push((
+                PostingEntry {
+                    doc_id: doc_id as u32,
+                    section_idx: 0,
+                    heading_level,
+                    score: 10,
+                },
+                pos as u32,
+            ));
+        }
+    }
+
+    let mut vocabulary: Vec<String> = terms.keys().cloned().collect();
+    vocabulary.sort();
+    let postings: Vec<Vec<PostingEntry>> = vocabulary
+        .iter()
+        .map(|t| terms[t].iter().map(|(e, _)| e.clone()).collect())
+        .collect();
+    let positions: Vec<Vec<u32>> = vocabulary
+        .iter()
+        .map(|t| terms[t].iter().map(|&(_, p)| p).collect())
+        .collect();
+    let suffix_array: Vec<(u32, u32)> = build_vocab_suffix_array_sais(&vocabulary)
+        .iter()
+        .map(|e| (e.term_idx as u32, e.offset as u32))
+        .collect();
+
+    let docs: Vec<DocMetaInput> = (0..texts.len())
+        .map(|i| DocMetaInput {
+            title: format!("Doc {}", i),
+            excerpt: String::new(),
+            href: format!("/doc{}", i),
+            doc_type: "page".to_string(),
+            section_id: None,
+            category: None,
+            author: None,
+            tags: vec![],
+        })
+        .collect();
+
+    let layer = BinaryLayer::build_v7(
+        &vocabulary,
+        &suffix_array,
+        &postings,
+        with_positions.then_some(positions.as_slice()),
+        &[],
+        texts.len(),
+        Vec::new(),
+        encode_docs_binary(&docs),
+        Vec::new(),
+    )
+    .unwrap();
+    (layer, vocabulary)
+}
+
+/// Write `layer` out and load a searcher from the bytes.
+#[cfg(test)]
+pub(crate) fn load_searcher(layer: BinaryLayer) -> TierSearcher {
+    let bytes = layer.to_bytes().unwrap();
+    TierSearcher::from_layer(LoadedLayer::from_bytes(&bytes).unwrap()).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
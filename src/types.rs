@@ -246,6 +246,37 @@ impl FieldType {
             FieldType::Content => "content",
         }
     }
+
+    /// Parse a field name as used by `title:`/`heading:`/`content:` queries.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "title" => Some(FieldType::Title),
+            "heading" => Some(FieldType::Heading),
+            "content" => Some(FieldType::Content),
+            _ => None,
+        }
+    }
+
+    /// Whether a posting with this heading level belongs to this field.
+    ///
+    /// Postings only keep their heading level, so this follows the ranking
+    /// buckets in [`MatchType::from_heading_level`]: 0 is the title, 1-4 are
+    /// headings, 5+ is content.
+    pub fn contains_heading_level(self, heading_level: u8) -> bool {
+        self.contains(MatchType::from_heading_level(heading_level))
+    }
+
+    /// Whether a match in this ranking bucket belongs to this field.
+    pub fn contains(self, match_type: MatchType) -> bool {
+        match (self, match_type) {
+            (FieldType::Title, MatchType::Title) => true,
+            (FieldType::Content, MatchType::Content) => true,
+            (FieldType::Heading, match_type) => {
+                !matches!(match_type, MatchType::Title | MatchType::Content)
+            }
+            _ => false,
+        }
+    }
 }
 
 /// Hierarchical bucket for ranking based on where in the document structure a match occurred.
@@ -482,6 +513,10 @@ pub struct Posting {
     /// Posting lists are sorted by score DESC for O(k) single-term queries.
     #[serde(default)]
     pub score: f64,
+    /// A part of a compound word rather than a word of its own ("versicherung"
+    /// in "krankenversicherung"), scored below whole words
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub compound_part: bool,
 }
 
 /// All occurrences of a single term across the corpus.
@@ -0,0 +1,249 @@
+// Copyright 2025-present Harīṣh Tummalachērla
+// SPDX-License-Identifier: Apache-2.0
+
+//! Built-in character folding table for [`super::normalize`].
+//!
+//! Generated from the Unicode Character Database: every character whose
+//! canonical decomposition (NFD) contains a combining mark maps to its base
+//! letters, lowercased. A few letters that have no decomposition but are
+//! still "a letter with a diacritic" to a reader (ø, ł, đ, ß, æ...) are
+//! folded by hand.
+//!
+//! Characters not listed fold with `char::to_lowercase`, which std ships in
+//! every build, WASM included. The `test_fold_table_matches_nfd` test checks
+//! this table against the unicode-normalization crate.
+//!
+//! Sorted by source character for binary search.
+
+/// Characters that fold to a single character.
+#[rustfmt::skip]
+pub(super) static FOLD_SINGLE: [(char, char); 854] = [
+    ('\u{00C0}', 'a'), ('\u{00C1}', 'a'), ('\u{00C2}', 'a'), ('\u{00C3}', 'a'),
+    ('\u{00C4}', 'a'), ('\u{00C5}', 'a'), ('\u{00C7}', 'c'), ('\u{00C8}', 'e'),
+    ('\u{00C9}', 'e'), ('\u{00CA}', 'e'), ('\u{00CB}', 'e'), ('\u{00CC}', 'i'),
+    ('\u{00CD}', 'i'), ('\u{00CE}', 'i'), ('\u{00CF}', 'i'), ('\u{00D1}', 'n'),
+    ('\u{00D2}', 'o'), ('\u{00D3}', 'o'), ('\u{00D4}', 'o'), ('\u{00D5}', 'o'),
+    ('\u{00D6}', 'o'), ('\u{00D8}', 'o'), ('\u{00D9}', 'u'), ('\u{00DA}', 'u'),
+    ('\u{00DB}', 'u'), ('\u{00DC}', 'u'), ('\u{00DD}', 'y'), ('\u{00E0}', 'a'),
+    ('\u{00E1}', 'a'), ('\u{00E2}', 'a'), ('\u{00E3}', 'a'), ('\u{00E4}', 'a'),
+    ('\u{00E5}', 'a'), ('\u{00E7}', 'c'), ('\u{00E8}', 'e'), ('\u{00E9}', 'e'),
+    ('\u{00EA}', 'e'), ('\u{00EB}', 'e'), ('\u{00EC}', 'i'), ('\u{00ED}', 'i'),
+    ('\u{00EE}', 'i'), ('\u{00EF}', 'i'), ('\u{00F1}', 'n'), ('\u{00F2}', 'o'),
+    ('\u{00F3}', 'o'), ('\u{00F4}', 'o'), ('\u{00F5}', 'o'), ('\u{00F6}', 'o'),
+    ('\u{00F8}', 'o'), ('\u{00F9}', 'u'), ('\u{00FA}', 'u'), ('\u{00FB}', 'u'),
+    ('\u{00FC}', 'u'), ('\u{00FD}', 'y'), ('\u{00FF}', 'y'), ('\u{0100}', 'a'),
+    ('\u{0101}', 'a'), ('\u{0102}', 'a'), ('\u{0103}', 'a'), ('\u{0104}', 'a'),
+    ('\u{0105}', 'a'), ('\u{0106}', 'c'), ('\u{0107}', 'c'), ('\u{0108}', 'c'),
+    ('\u{0109}', 'c'), ('\u{010A}', 'c'), ('\u{010B}', 'c'), ('\u{010C}', 'c'),
+    ('\u{010D}', 'c'), ('\u{010E}', 'd'), ('\u{010F}', 'd'), ('\u{0110}', 'd'),
+    ('\u{0111}', 'd'), ('\u{0112}', 'e'), ('\u{0113}', 'e'), ('\u{0114}', 'e'),
+    ('\u{0115}', 'e'), ('\u{0116}', 'e'), ('\u{0117}', 'e'), ('\u{0118}', 'e'),
+    ('\u{0119}', 'e'), ('\u{011A}', 'e'), ('\u{011B}', 'e'), ('\u{011C}', 'g'),
+    ('\u{011D}', 'g'), ('\u{011E}', 'g'), ('\u{011F}', 'g'), ('\u{0120}', 'g'),
+    ('\u{0121}', 'g'), ('\u{0122}', 'g'), ('\u{0123}', 'g'), ('\u{0124}', 'h'),
+    ('\u{0125}', 'h'), ('\u{0126}', 'h'), ('\u{0127}', 'h'), ('\u{0128}', 'i'),
+    ('\u{0129}', 'i'), ('\u{012A}', 'i'), ('\u{012B}', 'i'), ('\u{012C}', 'i'),
+    ('\u{012D}', 'i'), ('\u{012E}', 'i'), ('\u{012F}', 'i'), ('\u{0130}', 'i'),
+    ('\u{0131}', 'i'), ('\u{0134}', 'j'), ('\u{0135}', 'j'), ('\u{0136}', 'k'),
+    ('\u{0137}', 'k'), ('\u{0139}', 'l'), ('\u{013A}', 'l'), ('\u{013B}', 'l'),
+    ('\u{013C}', 'l'), ('\u{013D}', 'l'), ('\u{013E}', 'l'), ('\u{0141}', 'l'),
+    ('\u{0142}', 'l'), ('\u{0143}', 'n'), ('\u{0144}', 'n'), ('\u{0145}', 'n'),
+    ('\u{0146}', 'n'), ('\u{0147}', 'n'), ('\u{0148}', 'n'), ('\u{014C}', 'o'),
+    ('\u{014D}', 'o'), ('\u{014E}', 'o'), ('\u{014F}', 'o'), ('\u{0150}', 'o'),
+    ('\u{0151}', 'o'), ('\u{0154}', 'r'), ('\u{0155}', 'r'), ('\u{0156}', 'r'),
+    ('\u{0157}', 'r'), ('\u{0158}', 'r'), ('\u{0159}', 'r'), ('\u{015A}', 's'),
+    ('\u{015B}', 's'), ('\u{015C}', 's'), ('\u{015D}', 's'), ('\u{015E}', 's'),
+    ('\u{015F}', 's'), ('\u{0160}', 's'), ('\u{0161}', 's'), ('\u{0162}', 't'),
+    ('\u{0163}', 't'), ('\u{0164}', 't'), ('\u{0165}', 't'), ('\u{0166}', 't'),
+    ('\u{0167}', 't'), ('\u{0168}', 'u'), ('\u{0169}', 'u'), ('\u{016A}', 'u'),
+    ('\u{016B}', 'u'), ('\u{016C}', 'u'), ('\u{016D}', 'u'), ('\u{016E}', 'u'),
+    ('\u{016F}', 'u'), ('\u{0170}', 'u'), ('\u{0171}', 'u'), ('\u{0172}', 'u'),
+    ('\u{0173}', 'u'), ('\u{0174}', 'w'), ('\u{0175}', 'w'), ('\u{0176}', 'y'),
+    ('\u{0177}', 'y'), ('\u{0178}', 'y'), ('\u{0179}', 'z'), ('\u{017A}', 'z'),
+    ('\u{017B}', 'z'), ('\u{017C}', 'z'), ('\u{017D}', 'z'), ('\u{017E}', 'z'),
+    ('\u{01A0}', 'o'), ('\u{01A1}', 'o'), ('\u{01AF}', 'u'), ('\u{01B0}', 'u'),
+    ('\u{01CD}', 'a'), ('\u{01CE}', 'a'), ('\u{01CF}', 'i'), ('\u{01D0}', 'i'),
+    ('\u{01D1}', 'o'), ('\u{01D2}', 'o'), ('\u{01D3}', 'u'), ('\u{01D4}', 'u'),
+    ('\u{01D5}', 'u'), ('\u{01D6}', 'u'), ('\u{01D7}', 'u'), ('\u{01D8}', 'u'),
+    ('\u{01D9}', 'u'), ('\u{01DA}', 'u'), ('\u{01DB}', 'u'), ('\u{01DC}', 'u'),
+    ('\u{01DE}', 'a'), ('\u{01DF}', 'a'), ('\u{01E0}', 'a'), ('\u{01E1}', 'a'),
+    ('\u{01E6}', 'g'), ('\u{01E7}', 'g'), ('\u{01E8}', 'k'), ('\u{01E9}', 'k'),
+    ('\u{01EA}', 'o'), ('\u{01EB}', 'o'), ('\u{01EC}', 'o'), ('\u{01ED}', 'o'),
+    ('\u{01EE}', '\u{0292}'), ('\u{01EF}', '\u{0292}'), ('\u{01F0}', 'j'), ('\u{01F4}', 'g'),
+    ('\u{01F5}', 'g'), ('\u{01F8}', 'n'), ('\u{01F9}', 'n'), ('\u{01FA}', 'a'),
+    ('\u{01FB}', 'a'), ('\u{01FE}', 'o'), ('\u{01FF}', 'o'), ('\u{0200}', 'a'),
+    ('\u{0201}', 'a'), ('\u{0202}', 'a'), ('\u{0203}', 'a'), ('\u{0204}', 'e'),
+    ('\u{0205}', 'e'), ('\u{0206}', 'e'), ('\u{0207}', 'e'), ('\u{0208}', 'i'),
+    ('\u{0209}', 'i'), ('\u{020A}', 'i'), ('\u{020B}', 'i'), ('\u{020C}', 'o'),
+    ('\u{020D}', 'o'), ('\u{020E}', 'o'), ('\u{020F}', 'o'), ('\u{0210}', 'r'),
+    ('\u{0211}', 'r'), ('\u{0212}', 'r'), ('\u{0213}', 'r'), ('\u{0214}', 'u'),
+    ('\u{0215}', 'u'), ('\u{0216}', 'u'), ('\u{0217}', 'u'), ('\u{0218}', 's'),
+    ('\u{0219}', 's'), ('\u{021A}', 't'), ('\u{021B}', 't'), ('\u{021E}', 'h'),
+    ('\u{021F}', 'h'), ('\u{0226}', 'a'), ('\u{0227}', 'a'), ('\u{0228}', 'e'),
+    ('\u{0229}', 'e'), ('\u{022A}', 'o'), ('\u{022B}', 'o'), ('\u{022C}', 'o'),
+    ('\u{022D}', 'o'), ('\u{022E}', 'o'), ('\u{022F}', 'o'), ('\u{0230}', 'o'),
+    ('\u{0231}', 'o'), ('\u{0232}', 'y'), ('\u{0233}', 'y'), ('\u{0385}', '\u{00A8}'),
+    ('\u{0386}', '\u{03B1}'), ('\u{0388}', '\u{03B5}'), ('\u{0389}', '\u{03B7}'), ('\u{038A}', '\u{03B9}'),
+    ('\u{038C}', '\u{03BF}'), ('\u{038E}', '\u{03C5}'), ('\u{038F}', '\u{03C9}'), ('\u{0390}', '\u{03B9}'),
+    ('\u{03AA}', '\u{03B9}'), ('\u{03AB}', '\u{03C5}'), ('\u{03AC}', '\u{03B1}'), ('\u{03AD}', '\u{03B5}'),
+    ('\u{03AE}', '\u{03B7}'), ('\u{03AF}', '\u{03B9}'), ('\u{03B0}', '\u{03C5}'), ('\u{03C2}', '\u{03C3}'),
+    ('\u{03CA}', '\u{03B9}'), ('\u{03CB}', '\u{03C5}'), ('\u{03CC}', '\u{03BF}'), ('\u{03CD}', '\u{03C5}'),
+    ('\u{03CE}', '\u{03C9}'), ('\u{03D3}', '\u{03D2}'), ('\u{03D4}', '\u{03D2}'), ('\u{0400}', '\u{0435}'),
+    ('\u{0401}', '\u{0435}'), ('\u{0403}', '\u{0433}'), ('\u{0407}', '\u{0456}'), ('\u{040C}', '\u{043A}'),
+    ('\u{040D}', '\u{0438}'), ('\u{040E}', '\u{0443}'), ('\u{0419}', '\u{0438}'), ('\u{0439}', '\u{0438}'),
+    ('\u{0450}', '\u{0435}'), ('\u{0451}', '\u{0435}'), ('\u{0453}', '\u{0433}'), ('\u{0457}', '\u{0456}'),
+    ('\u{045C}', '\u{043A}'), ('\u{045D}', '\u{0438}'), ('\u{045E}', '\u{0443}'), ('\u{0476}', '\u{0475}'),
+    ('\u{0477}', '\u{0475}'), ('\u{04C1}', '\u{0436}'), ('\u{04C2}', '\u{0436}'), ('\u{04D0}', '\u{0430}'),
+    ('\u{04D1}', '\u{0430}'), ('\u{04D2}', '\u{0430}'), ('\u{04D3}', '\u{0430}'), ('\u{04D6}', '\u{0435}'),
+    ('\u{04D7}', '\u{0435}'), ('\u{04DA}', '\u{04D9}'), ('\u{04DB}', '\u{04D9}'), ('\u{04DC}', '\u{0436}'),
+    ('\u{04DD}', '\u{0436}'), ('\u{04DE}', '\u{0437}'), ('\u{04DF}', '\u{0437}'), ('\u{04E2}', '\u{0438}'),
+    ('\u{04E3}', '\u{0438}'), ('\u{04E4}', '\u{0438}'), ('\u{04E5}', '\u{0438}'), ('\u{04E6}', '\u{043E}'),
+    ('\u{04E7}', '\u{043E}'), ('\u{04EA}', '\u{04E9}'), ('\u{04EB}', '\u{04E9}'), ('\u{04EC}', '\u{044D}'),
+    ('\u{04ED}', '\u{044D}'), ('\u{04EE}', '\u{0443}'), ('\u{04EF}', '\u{0443}'), ('\u{04F0}', '\u{0443}'),
+    ('\u{04F1}', '\u{0443}'), ('\u{04F2}', '\u{0443}'), ('\u{04F3}', '\u{0443}'), ('\u{04F4}', '\u{0447}'),
+    ('\u{04F5}', '\u{0447}'), ('\u{04F8}', '\u{044B}'), ('\u{04F9}', '\u{044B}'), ('\u{1E00}', 'a'),
+    ('\u{1E01}', 'a'), ('\u{1E02}', 'b'), ('\u{1E03}', 'b'), ('\u{1E04}', 'b'),
+    ('\u{1E05}', 'b'), ('\u{1E06}', 'b'), ('\u{1E07}', 'b'), ('\u{1E08}', 'c'),
+    ('\u{1E09}', 'c'), ('\u{1E0A}', 'd'), ('\u{1E0B}', 'd'), ('\u{1E0C}', 'd'),
+    ('\u{1E0D}', 'd'), ('\u{1E0E}', 'd'), ('\u{1E0F}', 'd'), ('\u{1E10}', 'd'),
+    ('\u{1E11}', 'd'), ('\u{1E12}', 'd'), ('\u{1E13}', 'd'), ('\u{1E14}', 'e'),
+    ('\u{1E15}', 'e'), ('\u{1E16}', 'e'), ('\u{1E17}', 'e'), ('\u{1E18}', 'e'),
+    ('\u{1E19}', 'e'), ('\u{1E1A}', 'e'), ('\u{1E1B}', 'e'), ('\u{1E1C}', 'e'),
+    ('\u{1E1D}', 'e'), ('\u{1E1E}', 'f'), ('\u{1E1F}', 'f'), ('\u{1E20}', 'g'),
+    ('\u{1E21}', 'g'), ('\u{1E22}', 'h'), ('\u{1E23}', 'h'), ('\u{1E24}', 'h'),
+    ('\u{1E25}', 'h'), ('\u{1E26}', 'h'), ('\u{1E27}', 'h'), ('\u{1E28}', 'h'),
+    ('\u{1E29}', 'h'), ('\u{1E2A}', 'h'), ('\u{1E2B}', 'h'), ('\u{1E2C}', 'i'),
+    ('\u{1E2D}', 'i'), ('\u{1E2E}', 'i'), ('\u{1E2F}', 'i'), ('\u{1E30}', 'k'),
+    ('\u{1E31}', 'k'), ('\u{1E32}', 'k'), ('\u{1E33}', 'k'), ('\u{1E34}', 'k'),
+    ('\u{1E35}', 'k'), ('\u{1E36}', 'l'), ('\u{1E37}', 'l'), ('\u{1E38}', 'l'),
+    ('\u{1E39}', 'l'), ('\u{1E3A}', 'l'), ('\u{1E3B}', 'l'), ('\u{1E3C}', 'l'),
+    ('\u{1E3D}', 'l'), ('\u{1E3E}', 'm'), ('\u{1E3F}', 'm'), ('\u{1E40}', 'm'),
+    ('\u{1E41}', 'm'), ('\u{1E42}', 'm'), ('\u{1E43}', 'm'), ('\u{1E44}', 'n'),
+    ('\u{1E45}', 'n'), ('\u{1E46}', 'n'), ('\u{1E47}', 'n'), ('\u{1E48}', 'n'),
+    ('\u{1E49}', 'n'), ('\u{1E4A}', 'n'), ('\u{1E4B}', 'n'), ('\u{1E4C}', 'o'),
+    ('\u{1E4D}', 'o'), ('\u{1E4E}', 'o'), ('\u{1E4F}', 'o'), ('\u{1E50}', 'o'),
+    ('\u{1E51}', 'o'), ('\u{1E52}', 'o'), ('\u{1E53}', 'o'), ('\u{1E54}', 'p'),
+    ('\u{1E55}', 'p'), ('\u{1E56}', 'p'), ('\u{1E57}', 'p'), ('\u{1E58}', 'r'),
+    ('\u{1E59}', 'r'), ('\u{1E5A}', 'r'), ('\u{1E5B}', 'r'), ('\u{1E5C}', 'r'),
+    ('\u{1E5D}', 'r'), ('\u{1E5E}', 'r'), ('\u{1E5F}', 'r'), ('\u{1E60}', 's'),
+    ('\u{1E61}', 's'), ('\u{1E62}', 's'), ('\u{1E63}', 's'), ('\u{1E64}', 's'),
+    ('\u{1E65}', 's'), ('\u{1E66}', 's'), ('\u{1E67}', 's'), ('\u{1E68}', 's'),
+    ('\u{1E69}', 's'), ('\u{1E6A}', 't'), ('\u{1E6B}', 't'), ('\u{1E6C}', 't'),
+    ('\u{1E6D}', 't'), ('\u{1E6E}', 't'), ('\u{1E6F}', 't'), ('\u{1E70}', 't'),
+    ('\u{1E71}', 't'), ('\u{1E72}', 'u'), ('\u{1E73}', 'u'), ('\u{1E74}', 'u'),
+    ('\u{1E75}', 'u'), ('\u{1E76}', 'u'), ('\u{1E77}', 'u'), ('\u{1E78}', 'u'),
+    ('\u{1E79}', 'u'), ('\u{1E7A}', 'u'), ('\u{1E7B}', 'u'), ('\u{1E7C}', 'v'),
+    ('\u{1E7D}', 'v'), ('\u{1E7E}', 'v'), ('\u{1E7F}', 'v'), ('\u{1E80}', 'w'),
+    ('\u{1E81}', 'w'), ('\u{1E82}', 'w'), ('\u{1E83}', 'w'), ('\u{1E84}', 'w'),
+    ('\u{1E85}', 'w'), ('\u{1E86}', 'w'), ('\u{1E87}', 'w'), ('\u{1E88}', 'w'),
+    ('\u{1E89}', 'w'), ('\u{1E8A}', 'x'), ('\u{1E8B}', 'x'), ('\u{1E8C}', 'x'),
+    ('\u{1E8D}', 'x'), ('\u{1E8E}', 'y'), ('\u{1E8F}', 'y'), ('\u{1E90}', 'z'),
+    ('\u{1E91}', 'z'), ('\u{1E92}', 'z'), ('\u{1E93}', 'z'), ('\u{1E94}', 'z'),
+    ('\u{1E95}', 'z'), ('\u{1E96}', 'h'), ('\u{1E97}', 't'), ('\u{1E98}', 'w'),
+    ('\u{1E99}', 'y'), ('\u{1E9B}', '\u{017F}'), ('\u{1EA0}', 'a'), ('\u{1EA1}', 'a'),
+    ('\u{1EA2}', 'a'), ('\u{1EA3}', 'a'), ('\u{1EA4}', 'a'), ('\u{1EA5}', 'a'),
+    ('\u{1EA6}', 'a'), ('\u{1EA7}', 'a'), ('\u{1EA8}', 'a'), ('\u{1EA9}', 'a'),
+    ('\u{1EAA}', 'a'), ('\u{1EAB}', 'a'), ('\u{1EAC}', 'a'), ('\u{1EAD}', 'a'),
+    ('\u{1EAE}', 'a'), ('\u{1EAF}', 'a'), ('\u{1EB0}', 'a'), ('\u{1EB1}', 'a'),
+    ('\u{1EB2}', 'a'), ('\u{1EB3}', 'a'), ('\u{1EB4}', 'a'), ('\u{1EB5}', 'a'),
+    ('\u{1EB6}', 'a'), ('\u{1EB7}', 'a'), ('\u{1EB8}', 'e'), ('\u{1EB9}', 'e'),
+    ('\u{1EBA}', 'e'), ('\u{1EBB}', 'e'), ('\u{1EBC}', 'e'), ('\u{1EBD}', 'e'),
+    ('\u{1EBE}', 'e'), ('\u{1EBF}', 'e'), ('\u{1EC0}', 'e'), ('\u{1EC1}', 'e'),
+    ('\u{1EC2}', 'e'), ('\u{1EC3}', 'e'), ('\u{1EC4}', 'e'), ('\u{1EC5}', 'e'),
+    ('\u{1EC6}', 'e'), ('\u{1EC7}', 'e'), ('\u{1EC8}', 'i'), ('\u{1EC9}', 'i'),
+    ('\u{1ECA}', 'i'), ('\u{1ECB}', 'i'), ('\u{1ECC}', 'o'), ('\u{1ECD}', 'o'),
+    ('\u{1ECE}', 'o'), ('\u{1ECF}', 'o'), ('\u{1ED0}', 'o'), ('\u{1ED1}', 'o'),
+    ('\u{1ED2}', 'o'), ('\u{1ED3}', 'o'), ('\u{1ED4}', 'o'), ('\u{1ED5}', 'o'),
+    ('\u{1ED6}', 'o'), ('\u{1ED7}', 'o'), ('\u{1ED8}', 'o'), ('\u{1ED9}', 'o'),
+    ('\u{1EDA}', 'o'), ('\u{1EDB}', 'o'), ('\u{1EDC}', 'o'), ('\u{1EDD}', 'o'),
+    ('\u{1EDE}', 'o'), ('\u{1EDF}', 'o'), ('\u{1EE0}', 'o'), ('\u{1EE1}', 'o'),
+    ('\u{1EE2}', 'o'), ('\u{1EE3}', 'o'), ('\u{1EE4}', 'u'), ('\u{1EE5}', 'u'),
+    ('\u{1EE6}', 'u'), ('\u{1EE7}', 'u'), ('\u{1EE8}', 'u'), ('\u{1EE9}', 'u'),
+    ('\u{1EEA}', 'u'), ('\u{1EEB}', 'u'), ('\u{1EEC}', 'u'), ('\u{1EED}', 'u'),
+    ('\u{1EEE}', 'u'), ('\u{1EEF}', 'u'), ('\u{1EF0}', 'u'), ('\u{1EF1}', 'u'),
+    ('\u{1EF2}', 'y'), ('\u{1EF3}', 'y'), ('\u{1EF4}', 'y'), ('\u{1EF5}', 'y'),
+    ('\u{1EF6}', 'y'), ('\u{1EF7}', 'y'), ('\u{1EF8}', 'y'), ('\u{1EF9}', 'y'),
+    ('\u{1F00}', '\u{03B1}'), ('\u{1F01}', '\u{03B1}'), ('\u{1F02}', '\u{03B1}'), ('\u{1F03}', '\u{03B1}'),
+    ('\u{1F04}', '\u{03B1}'), ('\u{1F05}', '\u{03B1}'), ('\u{1F06}', '\u{03B1}'), ('\u{1F07}', '\u{03B1}'),
+    ('\u{1F08}', '\u{03B1}'), ('\u{1F09}', '\u{03B1}'), ('\u{1F0A}', '\u{03B1}'), ('\u{1F0B}', '\u{03B1}'),
+    ('\u{1F0C}', '\u{03B1}'), ('\u{1F0D}', '\u{03B1}'), ('\u{1F0E}', '\u{03B1}'), ('\u{1F0F}', '\u{03B1}'),
+    ('\u{1F10}', '\u{03B5}'), ('\u{1F11}', '\u{03B5}'), ('\u{1F12}', '\u{03B5}'), ('\u{1F13}', '\u{03B5}'),
+    ('\u{1F14}', '\u{03B5}'), ('\u{1F15}', '\u{03B5}'), ('\u{1F18}', '\u{03B5}'), ('\u{1F19}', '\u{03B5}'),
+    ('\u{1F1A}', '\u{03B5}'), ('\u{1F1B}', '\u{03B5}'), ('\u{1F1C}', '\u{03B5}'), ('\u{1F1D}', '\u{03B5}'),
+    ('\u{1F20}', '\u{03B7}'), ('\u{1F21}', '\u{03B7}'), ('\u{1F22}', '\u{03B7}'), ('\u{1F23}', '\u{03B7}'),
+    ('\u{1F24}', '\u{03B7}'), ('\u{1F25}', '\u{03B7}'), ('\u{1F26}', '\u{03B7}'), ('\u{1F27}', '\u{03B7}'),
+    ('\u{1F28}', '\u{03B7}'), ('\u{1F29}', '\u{03B7}'), ('\u{1F2A}', '\u{03B7}'), ('\u{1F2B}', '\u{03B7}'),
+    ('\u{1F2C}', '\u{03B7}'), ('\u{1F2D}', '\u{03B7}'), ('\u{1F2E}', '\u{03B7}'), ('\u{1F2F}', '\u{03B7}'),
+    ('\u{1F30}', '\u{03B9}'), ('\u{1F31}', '\u{03B9}'), ('\u{1F32}', '\u{03B9}'), ('\u{1F33}', '\u{03B9}'),
+    ('\u{1F34}', '\u{03B9}'), ('\u{1F35}', '\u{03B9}'), ('\u{1F36}', '\u{03B9}'), ('\u{1F37}', '\u{03B9}'),
+    ('\u{1F38}', '\u{03B9}'), ('\u{1F39}', '\u{03B9}'), ('\u{1F3A}', '\u{03B9}'), ('\u{1F3B}', '\u{03B9}'),
+    ('\u{1F3C}', '\u{03B9}'), ('\u{1F3D}', '\u{03B9}'), ('\u{1F3E}', '\u{03B9}'), ('\u{1F3F}', '\u{03B9}'),
+    ('\u{1F40}', '\u{03BF}'), ('\u{1F41}', '\u{03BF}'), ('\u{1F42}', '\u{03BF}'), ('\u{1F43}', '\u{03BF}'),
+    ('\u{1F44}', '\u{03BF}'), ('\u{1F45}', '\u{03BF}'), ('\u{1F48}', '\u{03BF}'), ('\u{1F49}', '\u{03BF}'),
+    ('\u{1F4A}', '\u{03BF}'), ('\u{1F4B}', '\u{03BF}'), ('\u{1F4C}', '\u{03BF}'), ('\u{1F4D}', '\u{03BF}'),
+    ('\u{1F50}', '\u{03C5}'), ('\u{1F51}', '\u{03C5}'), ('\u{1F52}', '\u{03C5}'), ('\u{1F53}', '\u{03C5}'),
+    ('\u{1F54}', '\u{03C5}'), ('\u{1F55}', '\u{03C5}'), ('\u{1F56}', '\u{03C5}'), ('\u{1F57}', '\u{03C5}'),
+    ('\u{1F59}', '\u{03C5}'), ('\u{1F5B}', '\u{03C5}'), ('\u{1F5D}', '\u{03C5}'), ('\u{1F5F}', '\u{03C5}'),
+    ('\u{1F60}', '\u{03C9}'), ('\u{1F61}', '\u{03C9}'), ('\u{1F62}', '\u{03C9}'), ('\u{1F63}', '\u{03C9}'),
+    ('\u{1F64}', '\u{03C9}'), ('\u{1F65}', '\u{03C9}'), ('\u{1F66}', '\u{03C9}'), ('\u{1F67}', '\u{03C9}'),
+    ('\u{1F68}', '\u{03C9}'), ('\u{1F69}', '\u{03C9}'), ('\u{1F6A}', '\u{03C9}'), ('\u{1F6B}', '\u{03C9}'),
+    ('\u{1F6C}', '\u{03C9}'), ('\u{1F6D}', '\u{03C9}'), ('\u{1F6E}', '\u{03C9}'), ('\u{1F6F}', '\u{03C9}'),
+    ('\u{1F70}', '\u{03B1}'), ('\u{1F71}', '\u{03B1}'), ('\u{1F72}', '\u{03B5}'), ('\u{1F73}', '\u{03B5}'),
+    ('\u{1F74}', '\u{03B7}'), ('\u{1F75}', '\u{03B7}'), ('\u{1F76}', '\u{03B9}'), ('\u{1F77}', '\u{03B9}'),
+    ('\u{1F78}', '\u{03BF}'), ('\u{1F79}', '\u{03BF}'), ('\u{1F7A}', '\u{03C5}'), ('\u{1F7B}', '\u{03C5}'),
+    ('\u{1F7C}', '\u{03C9}'), ('\u{1F7D}', '\u{03C9}'), ('\u{1F80}', '\u{03B1}'), ('\u{1F81}', '\u{03B1}'),
+    ('\u{1F82}', '\u{03B1}'), ('\u{1F83}', '\u{03B1}'), ('\u{1F84}', '\u{03B1}'), ('\u{1F85}', '\u{03B1}'),
+    ('\u{1F86}', '\u{03B1}'), ('\u{1F87}', '\u{03B1}'), ('\u{1F88}', '\u{03B1}'), ('\u{1F89}', '\u{03B1}'),
+    ('\u{1F8A}', '\u{03B1}'), ('\u{1F8B}', '\u{03B1}'), ('\u{1F8C}', '\u{03B1}'), ('\u{1F8D}', '\u{03B1}'),
+    ('\u{1F8E}', '\u{03B1}'), ('\u{1F8F}', '\u{03B1}'), ('\u{1F90}', '\u{03B7}'), ('\u{1F91}', '\u{03B7}'),
+    ('\u{1F92}', '\u{03B7}'), ('\u{1F93}', '\u{03B7}'), ('\u{1F94}', '\u{03B7}'), ('\u{1F95}', '\u{03B7}'),
+    ('\u{1F96}', '\u{03B7}'), ('\u{1F97}', '\u{03B7}'), ('\u{1F98}', '\u{03B7}'), ('\u{1F99}', '\u{03B7}'),
+    ('\u{1F9A}', '\u{03B7}'), ('\u{1F9B}', '\u{03B7}'), ('\u{1F9C}', '\u{03B7}'), ('\u{1F9D}', '\u{03B7}'),
+    ('\u{1F9E}', '\u{03B7}'), ('\u{1F9F}', '\u{03B7}'), ('\u{1FA0}', '\u{03C9}'), ('\u{1FA1}', '\u{03C9}'),
+    ('\u{1FA2}', '\u{03C9}'), ('\u{1FA3}', '\u{03C9}'), ('\u{1FA4}', '\u{03C9}'), ('\u{1FA5}', '\u{03C9}'),
+    ('\u{1FA6}', '\u{03C9}'), ('\u{1FA7}', '\u{03C9}'), ('\u{1FA8}', '\u{03C9}'), ('\u{1FA9}', '\u{03C9}'),
+    ('\u{1FAA}', '\u{03C9}'), ('\u{1FAB}', '\u{03C9}'), ('\u{1FAC}', '\u{03C9}'), ('\u{1FAD}', '\u{03C9}'),
+    ('\u{1FAE}', '\u{03C9}'), ('\u{1FAF}', '\u{03C9}'), ('\u{1FB0}', '\u{03B1}'), ('\u{1FB1}', '\u{03B1}'),
+    ('\u{1FB2}', '\u{03B1}'), ('\u{1FB3}', '\u{03B1}'), ('\u{1FB4}', '\u{03B1}'), ('\u{1FB6}', '\u{03B1}'),
+    ('\u{1FB7}', '\u{03B1}'), ('\u{1FB8}', '\u{03B1}'), ('\u{1FB9}', '\u{03B1}'), ('\u{1FBA}', '\u{03B1}'),
+    ('\u{1FBB}', '\u{03B1}'), ('\u{1FBC}', '\u{03B1}'), ('\u{1FC1}', '\u{00A8}'), ('\u{1FC2}', '\u{03B7}'),
+    ('\u{1FC3}', '\u{03B7}'), ('\u{1FC4}', '\u{03B7}'), ('\u{1FC6}', '\u{03B7}'), ('\u{1FC7}', '\u{03B7}'),
+    ('\u{1FC8}', '\u{03B5}'), ('\u{1FC9}', '\u{03B5}'), ('\u{1FCA}', '\u{03B7}'), ('\u{1FCB}', '\u{03B7}'),
+    ('\u{1FCC}', '\u{03B7}'), ('\u{1FCD}', '\u{1FBF}'), ('\u{1FCE}', '\u{1FBF}'), ('\u{1FCF}', '\u{1FBF}'),
+    ('\u{1FD0}', '\u{03B9}'), ('\u{1FD1}', '\u{03B9}'), ('\u{1FD2}', '\u{03B9}'), ('\u{1FD3}', '\u{03B9}'),
+    ('\u{1FD6}', '\u{03B9}'), ('\u{1FD7}', '\u{03B9}'), ('\u{1FD8}', '\u{03B9}'), ('\u{1FD9}', '\u{03B9}'),
+    ('\u{1FDA}', '\u{03B9}'), ('\u{1FDB}', '\u{03B9}'), ('\u{1FDD}', '\u{1FFE}'), ('\u{1FDE}', '\u{1FFE}'),
+    ('\u{1FDF}', '\u{1FFE}'), ('\u{1FE0}', '\u{03C5}'), ('\u{1FE1}', '\u{03C5}'), ('\u{1FE2}', '\u{03C5}'),
+    ('\u{1FE3}', '\u{03C5}'), ('\u{1FE4}', '\u{03C1}'), ('\u{1FE5}', '\u{03C1}'), ('\u{1FE6}', '\u{03C5}'),
+    ('\u{1FE7}', '\u{03C5}'), ('\u{1FE8}', '\u{03C5}'), ('\u{1FE9}', '\u{03C5}'), ('\u{1FEA}', '\u{03C5}'),
+    ('\u{1FEB}', '\u{03C5}'), ('\u{1FEC}', '\u{03C1}'), ('\u{1FED}', '\u{00A8}'), ('\u{1FEE}', '\u{00A8}'),
+    ('\u{1FF2}', '\u{03C9}'), ('\u{1FF3}', '\u{03C9}'), ('\u{1FF4}', '\u{03C9}'), ('\u{1FF6}', '\u{03C9}'),
+    ('\u{1FF7}', '\u{03C9}'), ('\u{1FF8}', '\u{03BF}'), ('\u{1FF9}', '\u{03BF}'), ('\u{1FFA}', '\u{03C9}'),
+    ('\u{1FFB}', '\u{03C9}'), ('\u{1FFC}', '\u{03C9}'), ('\u{212B}', 'a'), ('\u{219A}', '\u{2190}'),
+    ('\u{219B}', '\u{2192}'), ('\u{21AE}', '\u{2194}'), ('\u{21CD}', '\u{21D0}'), ('\u{21CE}', '\u{21D4}'),
+    ('\u{21CF}', '\u{21D2}'), ('\u{2204}', '\u{2203}'), ('\u{2209}', '\u{2208}'), ('\u{220C}', '\u{220B}'),
+    ('\u{2224}', '\u{2223}'), ('\u{2226}', '\u{2225}'), ('\u{2241}', '\u{223C}'), ('\u{2244}', '\u{2243}'),
+    ('\u{2247}', '\u{2245}'), ('\u{2249}', '\u{2248}'), ('\u{2260}', '\u{003D}'), ('\u{2262}', '\u{2261}'),
+    ('\u{226D}', '\u{224D}'), ('\u{226E}', '\u{003C}'), ('\u{226F}', '\u{003E}'), ('\u{2270}', '\u{2264}'),
+    ('\u{2271}', '\u{2265}'), ('\u{2274}', '\u{2272}'), ('\u{2275}', '\u{2273}'), ('\u{2278}', '\u{2276}'),
+    ('\u{2279}', '\u{2277}'), ('\u{2280}', '\u{227A}'), ('\u{2281}', '\u{227B}'), ('\u{2284}', '\u{2282}'),
+    ('\u{2285}', '\u{2283}'), ('\u{2288}', '\u{2286}'), ('\u{2289}', '\u{2287}'), ('\u{22AC}', '\u{22A2}'),
+    ('\u{22AD}', '\u{22A8}'), ('\u{22AE}', '\u{22A9}'), ('\u{22AF}', '\u{22AB}'), ('\u{22E0}', '\u{227C}'),
+    ('\u{22E1}', '\u{227D}'), ('\u{22E2}', '\u{2291}'), ('\u{22E3}', '\u{2292}'), ('\u{22EA}', '\u{22B2}'),
+    ('\u{22EB}', '\u{22B3}'), ('\u{22EC}', '\u{22B4}'), ('\u{22ED}', '\u{22B5}'), ('\u{2ADC}', '\u{2ADD}'),
+    ('\u{105C9}', '\u{105D2}'), ('\u{105E4}', '\u{105DA}'),
+];
+
+/// Characters that fold to more than one character.
+pub(super) static FOLD_MULTI: [(char, &str); 10] = [
+    ('\u{00C6}', "ae"),
+    ('\u{00DF}', "ss"),
+    ('\u{00E6}', "ae"),
+    ('\u{0152}', "oe"),
+    ('\u{0153}', "oe"),
+    ('\u{01E2}', "ae"),
+    ('\u{01E3}', "ae"),
+    ('\u{01FC}', "ae"),
+    ('\u{01FD}', "ae"),
+    ('\u{1E9E}', "ss"),
+];
@@ -7,6 +7,7 @@
 //! for accent-insensitive search, dictionary compression for repeated strings,
 //! and SIMD routines that make WASM search feel almost native.
 
+mod fold_table;
 pub mod normalize;
 pub mod simd;
 pub mod dict_table;
@@ -1,14 +1,26 @@
 // Copyright 2025-present Harīṣh Tummalachērla
 // SPDX-License-Identifier: Apache-2.0
 
-//! String normalization for accent-insensitive search.
+//! String normalization: the one analyzer both the index and queries use.
 //!
-//! Converts "café" → "cafe" and "tummalachērla" → "tummalacherla" so searches
-//! match regardless of diacritics. The trick is NFD decomposition: split "é"
-//! into "e" + "´", then drop the combining mark.
+//! Converts "café" → "cafe", "Ñandú" → "nandu" and "STRASSE"/"straße" →
+//! "strasse" so searches match regardless of diacritics and case. The index
+//! builder runs it on every word, and queries run the exact same function,
+//! so whatever a word turns into at build time, typing it turns into the
+//! same thing.
 //!
-//! WASM builds skip the unicode-normalization crate entirely for smaller
-//! bundle size, assuming input is pre-normalized or ASCII.
+//! That used to be NFD decomposition via the unicode-normalization crate:
+//! split "é" into "e" + "´", then drop the combining mark. WASM builds left
+//! the crate out for size and only lowercased, so "café" was indexed as
+//! "cafe" and searched as "café". Now the decomposition is precomputed into
+//! a small folding table (`fold_table.rs`, under 10KB) that ships in every
+//! build, and NFD is only used in tests to check the table.
+//!
+//! Folding per character:
+//! 1. ASCII: lowercase
+//! 2. Combining marks: dropped
+//! 3. In the folding table (accented letters, ß, æ, ø, ł...): the table entry
+//! 4. Anything else: `char::to_lowercase`
 //!
 //! # References
 //!
@@ -20,58 +32,61 @@
 //! - **Combining Marks**: Unicode Standard §3.11 "Normalization Forms" and
 //!   §4.3 "Combining Classes" define how diacritics are separated from base
 //!   characters during NFD decomposition.
+//!
+//! - **Case Folding**: Unicode Standard §5.18 "Case Mappings" covers why
+//!   "ß" folds to "ss" and final sigma "ς" to "σ".
 
-#[cfg(feature = "unicode-normalization")]
-use unicode_normalization::UnicodeNormalization;
+use super::fold_table::{FOLD_MULTI, FOLD_SINGLE};
+use super::simd::to_lowercase_ascii_simd;
 
-/// Normalize a string for search: lowercase, strip diacritics, and collapse whitespace.
+/// Normalize a string for search: fold case and diacritics, collapse whitespace.
 ///
-/// This enables fuzzy matching between ASCII and accented versions:
+/// This enables matching between ASCII and accented versions:
 /// - "café" → "cafe"
 /// - "tummalachērla" → "tummalacherla"
 /// - "harīṣh" → "harish"
 /// - "naïve" → "naive"
-///
-/// # Algorithm (with unicode-normalization feature)
-///
-/// 1. NFD normalize (decompose characters into base + combining marks)
-/// 2. Filter out combining marks (category Mn = Mark, Nonspacing)
-/// 3. Lowercase
-/// 4. Collapse whitespace
-///
-/// # Algorithm (without unicode-normalization, e.g. WASM)
-///
-/// 1. Lowercase only (assumes input is pre-normalized or ASCII)
-/// 2. Collapse whitespace
-#[cfg(feature = "unicode-normalization")]
+/// - "Straße" → "strasse"
 pub fn normalize(value: &str) -> String {
-    value
-        .nfd()
-        .filter(|c| !is_combining_mark(*c))
-        .collect::<String>()
-        .to_lowercase()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
+    fold(value).split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
-/// Lightweight normalization for WASM (no unicode-normalization dependency).
-/// Just lowercases and collapses whitespace. Assumes input is ASCII or pre-normalized.
-#[cfg(not(feature = "unicode-normalization"))]
-pub fn normalize(value: &str) -> String {
-    value
-        .to_lowercase()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
+/// Fold case and diacritics, leaving everything else (whitespace,
+/// punctuation) in place.
+///
+/// Use this for a single query word; [`normalize`] for running text.
+pub fn fold(value: &str) -> String {
+    if value.is_ascii() {
+        return to_lowercase_ascii_simd(value);
+    }
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        fold_char(c, &mut out);
+    }
+    out
+}
+
+/// Append the folded form of `c` to `out`.
+#[inline]
+pub fn fold_char(c: char, out: &mut String) {
+    if c.is_ascii() {
+        out.push(c.to_ascii_lowercase());
+    } else if is_combining_mark(c) {
+        // Dropped: the base letter carries the meaning
+    } else if let Ok(i) = FOLD_SINGLE.binary_search_by_key(&c, |&(from, _)| from) {
+        out.push(FOLD_SINGLE[i].1);
+    } else if let Ok(i) = FOLD_MULTI.binary_search_by_key(&c, |&(from, _)| from) {
+        out.push_str(FOLD_MULTI[i].1);
+    } else {
+        out.extend(c.to_lowercase());
+    }
 }
 
 /// Check if a character is a combining mark (diacritic).
 ///
 /// Combining marks have Unicode category "Mn" (Mark, Nonspacing).
 /// Examples: ́ (acute), ̄ (macron), ̣ (dot below)
-#[cfg(feature = "unicode-normalization")]
-fn is_combining_mark(c: char) -> bool {
+pub(super) fn is_combining_mark(c: char) -> bool {
     // Unicode category Mn (Mark, Nonspacing) range
     // This covers the most common combining diacritical marks
     matches!(c,
@@ -109,3 +124,72 @@ pub fn common_prefix_len_chars(a: &str, b: &str) -> usize {
         .take_while(|(ca, cb)| ca == cb)
         .count()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_normalization::UnicodeNormalization;
+
+    #[test]
+    fn test_fold_examples() {
+        assert_eq!(normalize("Café"), "cafe");
+        assert_eq!(normalize("Ñandú"), "nandu");
+        assert_eq!(normalize("STRASSE"), normalize("straße"));
+        assert_eq!(normalize("Tummalachērla  Harīṣh"), "tummalacherla harish");
+        assert_eq!(normalize("Øresund Łódź"), "oresund lodz");
+        assert_eq!(fold("ΟΔΟΣ"), fold("οδος"));
+        // Decomposed input folds like precomposed
+        assert_eq!(fold("cafe\u{0301}"), fold("caf\u{00E9}"));
+        // Scripts without case or diacritics pass through
+        assert_eq!(fold("東京"), "東京");
+    }
+
+    #[test]
+    fn test_fold_table_sorted() {
+        assert!(FOLD_SINGLE.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(FOLD_MULTI.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_fold_is_idempotent() {
+        for &(from, _) in FOLD_SINGLE.iter() {
+            let once = fold(&from.to_string());
+            assert_eq!(fold(&once), once, "U+{:04X}", from as u32);
+        }
+        for &(from, _) in FOLD_MULTI.iter() {
+            let once = fold(&from.to_string());
+            assert_eq!(fold(&once), once, "U+{:04X}", from as u32);
+        }
+    }
+
+    /// Every character folds the way NFD + mark stripping + lowercasing
+    /// would, as long as its decomposition has a mark to strip.
+    #[test]
+    fn test_fold_table_matches_nfd() {
+        let by_hand: Vec<char> = FOLD_SINGLE
+            .iter()
+            .map(|&(from, _)| from)
+            .chain(FOLD_MULTI.iter().map(|&(from, _)| from))
+            .filter(|c| !c.to_string().nfd().any(is_combining_mark))
+            .collect();
+
+        for c in (0x80..=0x10FFFF).filter_map(char::from_u32) {
+            if is_combining_mark(c) || by_hand.contains(&c) {
+                continue;
+            }
+            let decomposed: String = c.to_string().nfd().collect();
+            if !decomposed.chars().any(is_combining_mark) {
+                continue;
+            }
+            let expected: String = decomposed
+                .chars()
+                .filter(|&m| !is_combining_mark(m))
+                .collect::<String>()
+                .to_lowercase()
+                .chars()
+                .map(|f| fold(&f.to_string()))
+                .collect();
+            assert_eq!(fold(&c.to_string()), expected, "U+{:04X}", c as u32);
+        }
+    }
+}
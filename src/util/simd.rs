@@ -437,6 +437,45 @@ pub fn levenshtein_within_str(a: &str, b: &str, max: u8) -> Option<u8> {
     }
 }
 
+/// Distance from `query` to the closest non-empty prefix of `term`, if within `max`.
+///
+/// Same DP as [`levenshtein_within_str`], but the answer is the minimum of
+/// the last row (whole query vs. each prefix of `term`) rather than its last
+/// cell. Prefixes longer than `query.len() + max` chars can't be close enough,
+/// so the rest of `term` is never looked at.
+pub fn levenshtein_prefix_within_str(query: &str, term: &str, max: u8) -> Option<u8> {
+    let a_chars: Vec<char> = query.chars().collect();
+    let b_chars: Vec<char> = term.chars().take(a_chars.len() + max as usize).collect();
+    let n = b_chars.len();
+    if n == 0 {
+        return None;
+    }
+
+    let mut dp: Vec<u8> = (0..=n).map(|i| i.min(255) as u8).collect();
+
+    for (i, &ac) in a_chars.iter().enumerate() {
+        let mut prev = dp[0];
+        dp[0] = (i + 1).min(255) as u8;
+        let mut min_row = dp[0];
+
+        for (j, &bc) in b_chars.iter().enumerate() {
+            let temp = dp[j + 1];
+            let cost = if ac == bc { 0 } else { 1 };
+            dp[j + 1] = (dp[j + 1].saturating_add(1))
+                .min(dp[j].saturating_add(1))
+                .min(prev.saturating_add(cost));
+            prev = temp;
+            min_row = min_row.min(dp[j + 1]);
+        }
+
+        if min_row > max {
+            return None;
+        }
+    }
+
+    dp[1..].iter().copied().min().filter(|&d| d <= max)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -558,4 +597,31 @@ mod tests {
         // Unicode falls back to character comparison
         assert_eq!(levenshtein_within_str("cafe", "cafe", 2), Some(0));
     }
+
+    #[test]
+    fn test_levenshtein_prefix_str() {
+        // Closest prefix, not the whole term
+        assert_eq!(
+            levenshtein_prefix_within_str("optim", "optimization", 2),
+            Some(0)
+        );
+        assert_eq!(
+            levenshtein_prefix_within_str("optimz", "optimization", 2),
+            Some(1)
+        );
+        assert_eq!(levenshtein_within_str("optimz", "optimization", 2), None);
+        assert_eq!(
+            levenshtein_prefix_within_str("optmz", "optimization", 2),
+            Some(2)
+        );
+        assert_eq!(
+            levenshtein_prefix_within_str("xyzzy", "optimization", 2),
+            None
+        );
+
+        // A term shorter than the query is its own longest prefix
+        assert_eq!(levenshtein_prefix_within_str("rustc", "rust", 1), Some(1));
+        assert_eq!(levenshtein_prefix_within_str("дом", "дома", 0), Some(0));
+        assert_eq!(levenshtein_prefix_within_str("ab", "", 2), None);
+    }
 }
@@ -11,6 +11,9 @@ mod properties;
 #[path = "property/multilingual.rs"]
 mod multilingual;
 
+#[path = "property/segmentation.rs"]
+mod segmentation;
+
 #[path = "property/binary_search.rs"]
 mod binary_search;
 
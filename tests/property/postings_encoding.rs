@@ -67,6 +67,7 @@ fn sparse_doc_ids_strategy() -> impl Strategy<Value = Vec<PostingEntry>> {
                     score,
                 })
                 .collect();
+            #[allow(clippy::unnecessary_sort_by)]
             postings.sort_by(|a, b| b.score.cmp(&a.score)); // Sort by score descending
                                                             // Remove duplicates by doc_id
             postings.dedup_by_key(|e| e.doc_id);
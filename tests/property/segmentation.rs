@@ -0,0 +1,239 @@
+//! Segmentation tests for scripts written without spaces.
+//!
+//! Chinese, Japanese and Thai run words together and Korean spaces whole
+//! phrases, so the index cuts them into overlapping bigrams and queries are
+//! cut the same way. Each corpus below is a few sentences in one script,
+//! with words a reader would type and the sentences that should match.
+//!
+//! Key properties verified:
+//! 1. A word typed on its own finds the sentences that use it
+//! 2. Quoting a word requires its bigrams side by side
+//! 3. Segment spans cover the word in order, on char boundaries
+//! 4. Every bigram of a stretch of Han text is a bigram of the whole text
+
+use proptest::prelude::*;
+use sorex::{build_inverted_index, segment, InvertedIndex, QueryNode};
+use std::collections::BTreeSet;
+
+fn pieces(word: &str) -> Vec<&str> {
+    segment(word)
+        .into_iter()
+        .map(|(s, e)| &word[s..e])
+        .collect()
+}
+
+/// Byte offsets of `term` in `doc`.
+fn offsets(index: &InvertedIndex, term: &str, doc: usize) -> BTreeSet<usize> {
+    index.terms.get(term).map_or_else(BTreeSet::new, |list| {
+        list.postings
+            .iter()
+            .filter(|p| p.doc_id == doc)
+            .map(|p| p.offset)
+            .collect()
+    })
+}
+
+/// Docs where `words` occur one right after the other.
+fn phrase_docs(index: &InvertedIndex, words: &[String]) -> BTreeSet<usize> {
+    (0..index.total_docs)
+        .filter(|&doc| {
+            // Every indexed word start in the doc, in order
+            let starts: Vec<usize> = index
+                .terms
+                .values()
+                .flat_map(|list| &list.postings)
+                .filter(|p| p.doc_id == doc)
+                .map(|p| p.offset)
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            offsets(index, &words[0], doc).into_iter().any(|first| {
+                let at = starts.binary_search(&first).expect("indexed start");
+                words.iter().enumerate().skip(1).all(|(i, word)| {
+                    starts
+                        .get(at + i)
+                        .is_some_and(|start| offsets(index, word, doc).contains(start))
+                })
+            })
+        })
+        .collect()
+}
+
+/// Docs the parsed query matches, by exact term lookup.
+fn matching_docs(index: &InvertedIndex, query: &str) -> Vec<usize> {
+    let docs = |node: &QueryNode| -> BTreeSet<usize> {
+        match node {
+            QueryNode::Term { text, .. } => index
+                .terms
+                .get(text)
+                .map(|list| list.postings.iter().map(|p| p.doc_id).collect())
+                .unwrap_or_default(),
+            QueryNode::Phrase(words) => phrase_docs(index, words),
+            other => panic!("unexpected query node {other:?}"),
+        }
+    };
+    let ast = QueryNode::parse(query).expect("query parses");
+    let children = match &ast {
+        QueryNode::And(children) => children.clone(),
+        node => vec![node.clone()],
+    };
+    let mut sets = children.iter().map(docs);
+    let first = sets.next().unwrap_or_default();
+    sets.fold(first, |acc, set| &acc & &set)
+        .into_iter()
+        .collect()
+}
+
+/// Index `texts` and check each `(query, matching doc ids)`.
+fn check_corpus(texts: &[&str], queries: &[(&str, &[usize])]) {
+    let texts: Vec<String> = texts.iter().map(|t| t.to_string()).collect();
+    let index = build_inverted_index(&texts, &[]);
+    for &(query, expected) in queries {
+        assert_eq!(matching_docs(&index, query), expected, "query {query:?}");
+    }
+}
+
+// ============================================================================
+// CORPORA
+// ============================================================================
+
+#[test]
+fn chinese_words_match_inside_sentences() {
+    check_corpus(
+        &[
+            "东京都厅的展望台可以免费参观。",
+            "我去了京都，然后去了东京。",
+            "Rust语言入门教程",
+        ],
+        &[
+            ("展望台", &[0]),
+            // Both have "东京" and "京都", only one side by side
+            ("东京都", &[0, 1]),
+            ("\"东京都\"", &[0]),
+            ("京都", &[0, 1]),
+            // A Latin word glued to Han text is still its own word
+            ("rust", &[2]),
+            ("入门", &[2]),
+        ],
+    );
+}
+
+#[test]
+fn japanese_words_match_across_kanji_and_kana() {
+    check_corpus(
+        &[
+            "全文検索エンジンは転置インデックスを使います。",
+            "東京都庁の展望台は無料です。",
+            "インデックスを作る",
+        ],
+        &[
+            ("検索エンジン", &[0]),
+            ("インデックス", &[0, 2]),
+            ("\"転置インデックス\"", &[0]),
+            ("展望台", &[1]),
+            ("東京", &[1]),
+        ],
+    );
+}
+
+#[test]
+fn korean_words_match_inside_inflected_words() {
+    check_corpus(
+        &[
+            "한국어 문서를 검색하는 방법을 설명합니다.",
+            "검색 엔진의 색인 구조",
+            "서울의 날씨",
+        ],
+        &[
+            // "검색하는" is indexed as "검색", "색하", "하는"
+            ("검색", &[0, 1]),
+            ("한국어 문서", &[0]),
+            ("날씨", &[2]),
+            ("색인", &[1]),
+        ],
+    );
+}
+
+#[test]
+fn thai_words_match_by_cluster() {
+    check_corpus(
+        &["ฉันกินข้าวที่บ้านทุกวัน", "อาหารไทยอร่อยมาก", "บ้านของเราอยู่ใกล้ทะเล"],
+        &[
+            ("กินข้าว", &[0]),
+            ("บ้าน", &[0, 2]),
+            ("\"ข้าวที่บ้าน\"", &[0]),
+            ("อาหาร", &[1]),
+            ("ทะเล", &[2]),
+        ],
+    );
+}
+
+#[test]
+fn segments_cut_in_context_like_alone() {
+    assert_eq!(pieces("东京都厅"), vec!["东京", "京都", "都厅"]);
+    assert_eq!(pieces("กินข้าว"), vec!["กิน", "นข้า", "ข้าว"]);
+    assert_eq!(pieces("Rust语言"), vec!["Rust", "语言"]);
+}
+
+// ============================================================================
+// PROPERTIES
+// ============================================================================
+
+/// Strings mixing Han, Kana, Hangul, Thai and Latin letters.
+fn mixed_word_strategy() -> impl Strategy<Value = String> {
+    let chars = prop_oneof![
+        prop::char::range('\u{4E00}', '\u{4E2F}'),
+        prop::char::range('\u{3041}', '\u{3096}'),
+        prop::char::range('\u{30A1}', '\u{30FA}'),
+        prop::char::range('\u{AC00}', '\u{AC2F}'),
+        prop::char::range('\u{0E01}', '\u{0E3A}'),
+        prop::char::range('\u{0E40}', '\u{0E4E}'),
+        prop::char::range('a', 'z'),
+    ];
+    prop::collection::vec(chars, 1..16).prop_map(|chars| chars.into_iter().collect())
+}
+
+fn han_text_strategy() -> impl Strategy<Value = Vec<char>> {
+    prop::collection::vec(prop::char::range('\u{4E00}', '\u{4E2F}'), 2..24)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    /// Property: spans are on char boundaries, start in order and together
+    /// cover the whole word.
+    #[test]
+    fn prop_segments_cover_the_word(word in mixed_word_strategy()) {
+        let spans = segment(&word);
+        prop_assert!(!spans.is_empty());
+        prop_assert_eq!(spans[0].0, 0);
+        prop_assert_eq!(spans.last().unwrap().1, word.len());
+        for &(start, end) in &spans {
+            prop_assert!(start < end);
+            prop_assert!(word.is_char_boundary(start) && word.is_char_boundary(end));
+        }
+        for pair in spans.windows(2) {
+            prop_assert!(pair[0].0 < pair[1].0, "starts go up: {:?}", spans);
+            prop_assert!(pair[1].0 <= pair[0].1, "no gaps: {:?}", spans);
+        }
+    }
+
+    /// Property: a query cut from the middle of Han text has only bigrams the
+    /// text has, so an exact search for it finds the text.
+    #[test]
+    fn prop_han_substring_bigrams_are_indexed(
+        text in han_text_strategy(),
+        from in 0usize..24,
+        len in 2usize..8,
+    ) {
+        let from = from % (text.len() - 1);
+        let to = (from + len).min(text.len());
+        let text_str: String = text.iter().collect();
+        let query: String = text[from..to].iter().collect();
+
+        let indexed = pieces(&text_str);
+        for bigram in pieces(&query) {
+            prop_assert!(indexed.contains(&bigram), "{} from {} in {}", bigram, query, text_str);
+        }
+    }
+}
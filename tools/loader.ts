@@ -33,9 +33,15 @@ declare class SorexSearcher {
 		onFinish: (r: SearchResult[]) => void
 	): void;
 	searchSync(query: string, limit: number): SearchResult[];
+	searchSyncWithOptions(query: string, limit: number, options?: SearchOptions): SearchResult[];
 	searchWithTierTiming(query: string, limit: number): TierTimingResult;
+	searchWithFacets(query: string, limit: number, options?: SearchOptions): FacetedResults;
+	searchPage(query: string, limit: number, options?: SearchOptions): SearchPage;
+	complete(input: string, limit: number, options?: CompletionOptions): Completion[];
+	suggest(query: string): Suggestion | null;
 	doc_count(): number;
 	vocab_size(): number;
+	hasText(): boolean;
 	free(): void;
 }
 
@@ -50,6 +56,7 @@ declare class SorexIncrementalLoader {
 	loadSectionTable(bytes: Uint8Array): void;
 	loadSkipLists(bytes: Uint8Array): void;
 	loadLevDfa(bytes: Uint8Array): void;
+	loadExtensions(bytes: Uint8Array): void;
 	finalize(): SorexSearcher;
 }
 
@@ -67,6 +74,14 @@ interface SearchResult {
 	sectionId: string | null;
 	matchType: string;
 	matchedTerm: string | null;
+	snippet?: Snippet;
+}
+
+interface Snippet {
+	text: string;
+	highlights: [number, number][];
+	clippedStart: boolean;
+	clippedEnd: boolean;
 }
 
 interface TierTimingResult {
@@ -79,6 +94,66 @@ interface TierTimingResult {
 	t3TimeUs: number;
 }
 
+interface SearchOptions {
+	dedupSections?: boolean;
+	field?: 'title' | 'heading' | 'content';
+	filter?: {
+		category?: string[];
+		tags?: string[];
+		author?: string;
+		type?: string[];
+		hrefPrefix?: string;
+	};
+	snippetLength?: number;
+	offset?: number;
+	maxEditDistance?: number;
+	fuzziness?: 'adaptive' | 'uniform' | number[];
+	asYouType?: boolean;
+	phonetic?: boolean;
+	lang?: string;
+	synonymPenalty?: number;
+}
+
+interface SearchPage {
+	results: SearchResult[];
+	offset: number;
+	total: number;
+	hasMore: boolean;
+}
+
+interface Completion {
+	term: string;
+	docCount: number;
+	matchType: number;
+	infix: boolean;
+}
+
+interface CompletionOptions {
+	infix?: boolean;
+}
+
+interface Suggestion {
+	query: string;
+	corrected: [number, number][];
+	docCount: number;
+}
+
+interface FacetCount {
+	value: string;
+	count: number;
+}
+
+interface FacetedResults {
+	results: SearchResult[];
+	facets: {
+		total: number;
+		categories: FacetCount[];
+		authors: FacetCount[];
+		tags: FacetCount[];
+		types: FacetCount[];
+	};
+}
+
 interface RawOffsets {
 	vocabularyStart: number;
 	vocabularyEnd: number;
@@ -96,6 +171,8 @@ interface RawOffsets {
 	skipListsEnd: number;
 	levDfaStart: number;
 	levDfaEnd: number;
+	extensionsStart: number;
+	extensionsEnd: number;
 }
 
 interface SearchCallback {
@@ -109,7 +186,8 @@ type Range = readonly [start: number, end: number];
 // Constants
 // =============================================================================
 
-const HEADER_SIZE = 52;
+// The v14 header; validateMagic rejects files of any other version
+const HEADER_SIZE = 56;
 const MAGIC = Uint8Array.from([0x53, 0x4f, 0x52, 0x58]); // "SORX"
 const FOOTER_MAGIC = Uint8Array.from([0x58, 0x52, 0x4f, 0x53]); // "XROS"
 
@@ -218,7 +296,7 @@ const parseIndex = (buffer: ArrayBuffer): ParsedIndex => {
 	validateMagic(data);
 
 	const wasmLen = view.getUint32(42, true);
-	const sectionOffsets = [14, 18, 22, 26, 30, 34, 38, 46] as const;
+	const sectionOffsets = [14, 18, 22, 26, 30, 34, 38, 46, 52] as const;
 	const sectionLengths = sectionOffsets.map((off) => view.getUint32(off, true));
 	const sectionsLen = sum(sectionLengths);
 	const sectionsStart = HEADER_SIZE + wasmLen;
@@ -307,7 +385,11 @@ const createSections = (loader: SorexIncrementalLoader, offsets: RawOffsets): Se
 		load: (b) => loader.loadSectionTable(b)
 	},
 	{ range: [offsets.skipListsStart, offsets.skipListsEnd], load: (b) => loader.loadSkipLists(b) },
-	{ range: [offsets.levDfaStart, offsets.levDfaEnd], load: (b) => loader.loadLevDfa(b) }
+	{ range: [offsets.levDfaStart, offsets.levDfaEnd], load: (b) => loader.loadLevDfa(b) },
+	{
+		range: [offsets.extensionsStart, offsets.extensionsEnd],
+		load: (b) => loader.loadExtensions(b)
+	}
 ];
 
 const dispatchReadySections = (
@@ -372,6 +454,7 @@ const loadIncremental = async (url: string): Promise<SorexSearcherWrapper> => {
 	// Phase 1-2: Read header and WASM
 	await readUntil(reader, buffer, HEADER_SIZE);
 	const header = buffer.slice(0, HEADER_SIZE);
+	validateMagic(header);
 	const wasmEnd = HEADER_SIZE + getWasmLength(header);
 
 	await readUntil(reader, buffer, wasmEnd);
@@ -449,9 +532,9 @@ class SorexSearcherWrapper {
 		);
 	}
 
-	searchSync(query: string, limit: number): SearchResult[] {
+	searchSync(query: string, limit: number, options?: SearchOptions): SearchResult[] {
 		this.restore();
-		return this.inner.searchSync(query, limit);
+		return this.inner.searchSyncWithOptions(query, limit, options);
 	}
 
 	searchWithTierTiming(query: string, limit: number): TierTimingResult {
@@ -459,6 +542,26 @@ class SorexSearcherWrapper {
 		return this.inner.searchWithTierTiming(query, limit);
 	}
 
+	searchWithFacets(query: string, limit: number, options?: SearchOptions): FacetedResults {
+		this.restore();
+		return this.inner.searchWithFacets(query, limit, options);
+	}
+
+	searchPage(query: string, limit: number, options?: SearchOptions): SearchPage {
+		this.restore();
+		return this.inner.searchPage(query, limit, options);
+	}
+
+	complete(input: string, limit: number, options?: CompletionOptions): Completion[] {
+		this.restore();
+		return this.inner.complete(input, limit, options);
+	}
+
+	suggest(query: string): Suggestion | null {
+		this.restore();
+		return this.inner.suggest(query);
+	}
+
 	docCount(): number {
 		this.restore();
 		return this.inner.doc_count();
@@ -469,6 +572,11 @@ class SorexSearcherWrapper {
 		return this.inner.vocab_size();
 	}
 
+	hasText(): boolean {
+		this.restore();
+		return this.inner.hasText();
+	}
+
 	free(): void {
 		this.restore();
 		this.inner.free();